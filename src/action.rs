@@ -29,7 +29,12 @@ pub enum Action {
     DecrementPage,
     NextSummaryMode,
     PreviousSummaryMode,
-    ToggleSortBy { reload: bool, forward: bool },
+    NextFullCrateInfoTab,
+    PreviousFullCrateInfoTab,
+    ToggleSortBy {
+        reload: bool,
+        forward: bool,
+    },
     ScrollBottom,
     ScrollTop,
     ScrollDown,
@@ -51,4 +56,75 @@ pub enum Action {
     OpenDocsUrlInBrowser,
     OpenCratesIOUrlInBrowser,
     ShowFullCrateInfo,
+    CloseFullCrateInfo,
+    ShowDependencyTree,
+    CloseDependencyTree,
+    ToggleDependencyTreeFocus,
+    ToggleDependencyTreeSelection,
+    UpdateDependencyTree,
+    ToggleHealthColumn,
+    RequestSearchResultsHealth,
+    RequestSearchResultsLicenses,
+    ToggleHideIncompatible,
+    RequestSearchResultsCompatibility,
+    AddDependency,
+    SubmitAddDependency,
+    CancelAddDependency,
+    UpdateProject,
+    UpdateProjectDependencies,
+    OpenVersionDiffInBrowser,
+    BumpDependencyRequirement,
+    ShowLockfile,
+    UpdateLockfileEntries,
+    ToggleDuplicatesOnly,
+    ShowInverseDependencies,
+    CloseInverseDependencies,
+    UpdateWorkspaceGraph,
+    ShowFeatureUnification,
+    CloseFeatureUnification,
+    ToggleFavorite,
+    NextCollection,
+    PreviousCollection,
+    MoveFavoriteUp,
+    MoveFavoriteDown,
+    EditFavoriteNote,
+    NewCollection,
+    RenameCollection,
+    DeleteCollection,
+    SubmitFavoritesPrompt,
+    CancelFavoritesPrompt,
+    PreviousSearchQuery,
+    NextSearchQuery,
+    ShowSearchHistory,
+    SelectSearchHistory,
+    CloseSearchHistory,
+    ClearSearchHistory,
+    ShowRecentlyViewed,
+    CloseRecentlyViewed,
+    RecordRecentlyViewed(String),
+    ToggleWatch,
+    ShowWatchlist,
+    CloseWatchlist,
+    AcknowledgeWatched,
+    AcknowledgeAllWatched,
+    CheckWatchlist,
+    UpdateWatchlist,
+    EditCrateNote,
+    EditCrateTags,
+    SubmitAnnotationPrompt,
+    CancelAnnotationPrompt,
+    ShowSavedSearches,
+    CloseSavedSearches,
+    SelectSavedSearch,
+    RunSavedSearch(String),
+    Export,
+    SubmitExportPrompt,
+    CancelExportPrompt,
+    CopyJustificationReport,
+    WriteJustificationReport,
+    DeliverJustificationReport {
+        name: String,
+        report: String,
+        write: bool,
+    },
 }
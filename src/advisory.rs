@@ -0,0 +1,343 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use itertools::Itertools;
+use serde::Deserialize;
+use strum::Display;
+use tracing::warn;
+
+/// Qualitative severity rating of a CVSS v3 base score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Severity {
+    None,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    fn from_score(score: f64) -> Self {
+        match score {
+            s if s >= 9.0 => Self::Critical,
+            s if s >= 7.0 => Self::High,
+            s if s >= 4.0 => Self::Medium,
+            s if s > 0.0 => Self::Low,
+            _ => Self::None,
+        }
+    }
+}
+
+/// Computes the base score of a CVSS v3 vector, e.g.
+/// `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`.
+pub fn cvss_base_score(vector: &str) -> Option<f64> {
+    let mut parts = vector.split('/');
+    if !parts.next()?.starts_with("CVSS:3") {
+        return None;
+    }
+    let metrics: HashMap<&str, &str> = parts.filter_map(|p| p.split_once(':')).collect();
+    let changed = match *metrics.get("S")? {
+        "U" => false,
+        "C" => true,
+        _ => return None,
+    };
+    let attack_vector = match *metrics.get("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let attack_complexity = match *metrics.get("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let privileges_required = match (*metrics.get("PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let user_interaction = match *metrics.get("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let impact = |metric| match *metrics.get(metric)? {
+        "H" => Some(0.56),
+        "L" => Some(0.22),
+        "N" => Some(0.0),
+        _ => None,
+    };
+    let iss = 1.0 - (1.0 - impact("C")?) * (1.0 - impact("I")?) * (1.0 - impact("A")?);
+    let impact = if changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02_f64).powi(15)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let exploitability =
+        8.22 * attack_vector * attack_complexity * privileges_required * user_interaction;
+    let score = if changed {
+        1.08 * (impact + exploitability)
+    } else {
+        impact + exploitability
+    };
+    Some(round_up(score.min(10.0)))
+}
+
+/// Rounds up to one decimal as defined by the CVSS v3.1 specification.
+fn round_up(value: f64) -> f64 {
+    let int = (value * 100_000.0).round() as i64;
+    if int % 10_000 == 0 {
+        int as f64 / 100_000.0
+    } else {
+        ((int / 10_000) + 1) as f64 / 10.0
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FrontMatter {
+    advisory: AdvisoryMetadata,
+    #[serde(default)]
+    versions: AdvisoryVersions,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdvisoryMetadata {
+    id: String,
+    package: String,
+    date: String,
+    cvss: Option<String>,
+    informational: Option<String>,
+    withdrawn: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AdvisoryVersions {
+    #[serde(default)]
+    patched: Vec<String>,
+    #[serde(default)]
+    unaffected: Vec<String>,
+}
+
+/// A security advisory from the RustSec advisory database.
+#[derive(Debug, Clone)]
+pub struct Advisory {
+    pub id: String,
+    pub package: String,
+    pub title: String,
+    pub date: String,
+    pub cvss: Option<String>,
+    /// The kind of informational advisory, e.g. `unmaintained` or `unsound`.
+    pub informational: Option<String>,
+    pub withdrawn: bool,
+    pub patched: Vec<semver::VersionReq>,
+    pub unaffected: Vec<semver::VersionReq>,
+}
+
+impl Advisory {
+    /// Parses an advisory from its Markdown file, which starts with the
+    /// metadata in a TOML code block followed by the title as a heading.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let (front_matter, body) = contents
+            .trim_start()
+            .strip_prefix("```toml")
+            .and_then(|rest| rest.split_once("\n```"))
+            .ok_or("missing TOML front matter")?;
+        let FrontMatter { advisory, versions } =
+            toml::from_str(front_matter).map_err(|err| err.to_string())?;
+        let title = body
+            .lines()
+            .find_map(|line| line.strip_prefix("# "))
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        let requirements = |reqs: Vec<String>| {
+            reqs.iter()
+                .map(|req| semver::VersionReq::parse(req).map_err(|err| format!("`{req}`: {err}")))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            id: advisory.id,
+            package: advisory.package,
+            title,
+            date: advisory.date,
+            cvss: advisory.cvss,
+            informational: advisory.informational,
+            withdrawn: advisory.withdrawn.is_some(),
+            patched: requirements(versions.patched)?,
+            unaffected: requirements(versions.unaffected)?,
+        })
+    }
+
+    /// Whether `version` is neither patched nor unaffected.
+    pub fn affects(&self, version: &semver::Version) -> bool {
+        !self
+            .patched
+            .iter()
+            .chain(&self.unaffected)
+            .any(|req| req.matches(version))
+    }
+
+    pub fn severity(&self) -> Option<Severity> {
+        self.cvss
+            .as_deref()
+            .and_then(cvss_base_score)
+            .map(Severity::from_score)
+    }
+
+    /// The severity, or the kind of advisory for informational ones.
+    pub fn severity_label(&self) -> String {
+        match (self.severity(), &self.informational) {
+            (Some(severity), _) => severity.to_string(),
+            (None, Some(informational)) => informational.clone(),
+            (None, None) => "unknown severity".into(),
+        }
+    }
+
+    pub fn patched_versions(&self) -> String {
+        if self.patched.is_empty() {
+            "no patched versions".into()
+        } else {
+            self.patched.iter().join(", ")
+        }
+    }
+
+    pub fn link(&self) -> String {
+        format!("https://rustsec.org/advisories/{}", self.id)
+    }
+}
+
+/// The advisories of a local clone of the RustSec advisory database, keyed by
+/// crate name.
+#[derive(Debug, Clone, Default)]
+pub struct AdvisoryDb {
+    advisories: HashMap<String, Vec<Advisory>>,
+}
+
+impl AdvisoryDb {
+    /// Reads every advisory in the `crates` directory of the database,
+    /// skipping withdrawn ones and the ones that cannot be parsed.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let crates = path.join("crates");
+        let entries = std::fs::read_dir(&crates)
+            .map_err(|err| format!("Unable to read `{}`: {err}", crates.display()))?;
+        let advisories = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| std::fs::read_dir(entry.path()).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+            .filter_map(|path| {
+                std::fs::read_to_string(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|contents| Advisory::parse(&contents))
+                    .inspect_err(|err| warn!("Skipping advisory `{}`: {err}", path.display()))
+                    .ok()
+            })
+            .filter(|advisory| !advisory.withdrawn)
+            .sorted_by(|a, b| b.date.cmp(&a.date))
+            .into_group_map_by(|advisory| advisory.package.clone());
+        Ok(Self { advisories })
+    }
+
+    /// Every advisory for a crate, newest first.
+    pub fn for_crate(&self, name: &str) -> &[Advisory] {
+        self.advisories
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The advisories affecting a version of a crate.
+    pub fn affecting(&self, name: &str, version: &semver::Version) -> Vec<Advisory> {
+        self.for_crate(name)
+            .iter()
+            .filter(|advisory| advisory.affects(version))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Loads the advisory database in the background.
+pub async fn request_advisory_db(
+    path: PathBuf,
+    advisory_db: Arc<Mutex<Option<AdvisoryDb>>>,
+) -> Result<(), String> {
+    let loaded = tokio::task::spawn_blocking(move || AdvisoryDb::load(&path))
+        .await
+        .map_err(|err| format!("Unable to load the advisory database: {err}"))??;
+    *advisory_db.lock().unwrap() = Some(loaded);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADVISORY: &str = r#"```toml
+[advisory]
+id = "RUSTSEC-2021-0124"
+package = "tokio"
+date = "2021-11-16"
+url = "https://github.com/tokio-rs/tokio/issues/4225"
+categories = ["memory-corruption"]
+cvss = "CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:N/I:N/A:H"
+
+[versions]
+patched = [
+    ">= 1.8.4, < 1.9.0",
+    ">= 1.13.1",
+]
+unaffected = ["< 0.1.14"]
+```
+
+# Data race when sending and receiving after closing a `oneshot` channel
+
+If a `tokio::sync::oneshot` channel is closed...
+"#;
+
+    #[test]
+    fn parses_advisory() {
+        let advisory = Advisory::parse(ADVISORY).unwrap();
+        assert_eq!(advisory.id, "RUSTSEC-2021-0124");
+        assert_eq!(advisory.package, "tokio");
+        assert_eq!(
+            advisory.title,
+            "Data race when sending and receiving after closing a `oneshot` channel"
+        );
+        assert_eq!(advisory.severity(), Some(Severity::Medium));
+        assert_eq!(advisory.patched_versions(), ">=1.8.4, <1.9.0, >=1.13.1");
+        let affects = |v: &str| advisory.affects(&semver::Version::parse(v).unwrap());
+        assert!(affects("1.13.0"));
+        assert!(affects("1.0.0"));
+        assert!(!affects("1.8.4"));
+        assert!(!affects("1.13.1"));
+        assert!(!affects("0.1.0"));
+    }
+
+    #[test]
+    fn computes_cvss_base_scores() {
+        for (vector, score) in [
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H", 9.8),
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H", 10.0),
+            ("CVSS:3.0/AV:L/AC:L/PR:L/UI:N/S:U/C:H/I:N/A:N", 5.5),
+            ("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:N/I:N/A:H", 5.9),
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N", 0.0),
+        ] {
+            assert_eq!(cvss_base_score(vector), Some(score), "{vector}");
+        }
+        assert_eq!(cvss_base_score("CVSS:4.0/AV:N"), None);
+    }
+}
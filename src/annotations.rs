@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const ANNOTATIONS_FILE: &str = "annotations.toml";
+
+/// A personal note and tags attached to a crate, e.g. `approved` or `avoid`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotation {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Annotation {
+    pub fn is_empty(&self) -> bool {
+        self.note.is_empty() && self.tags.is_empty()
+    }
+}
+
+/// Notes and tags keyed by crate name, persisted in the data directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotations {
+    #[serde(default)]
+    pub crates: BTreeMap<String, Annotation>,
+}
+
+impl Annotations {
+    pub fn load() -> Result<Self, String> {
+        storage::load(ANNOTATIONS_FILE)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save(ANNOTATIONS_FILE, self)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Annotation> {
+        self.crates.get(name)
+    }
+
+    pub fn tags(&self, name: &str) -> &[String] {
+        self.get(name).map_or(&[], |a| &a.tags)
+    }
+
+    pub fn set_note(&mut self, name: &str, note: &str) {
+        self.update(name, |a| a.note = note.trim().into());
+    }
+
+    /// Replaces the tags of a crate with the ones listed in `tags`.
+    pub fn set_tags(&mut self, name: &str, tags: &str) {
+        self.update(name, |a| a.tags = parse_tags(tags));
+    }
+
+    /// Changes the annotation of a crate, forgetting it once it is empty.
+    fn update(&mut self, name: &str, f: impl FnOnce(&mut Annotation)) {
+        let annotation = self.crates.entry(name.into()).or_default();
+        f(annotation);
+        if annotation.is_empty() {
+            self.crates.remove(name);
+        }
+    }
+}
+
+/// Splits a list of tags separated by commas or whitespace, lowercasing them
+/// and dropping duplicates.
+pub fn parse_tags(tags: &str) -> Vec<String> {
+    tags.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .unique()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotates_crates() {
+        let mut annotations = Annotations::default();
+        annotations.set_tags("serde", "Approved, core  approved");
+        annotations.set_note("serde", "  derive everything ");
+        assert_eq!(annotations.tags("serde"), ["approved", "core"]);
+        assert_eq!(annotations.get("serde").unwrap().note, "derive everything");
+        let toml = toml::to_string(&annotations).unwrap();
+        assert_eq!(toml::from_str::<Annotations>(&toml).unwrap(), annotations);
+
+        annotations.set_tags("serde", " ");
+        annotations.set_note("serde", "");
+        assert!(annotations.get("serde").is_none());
+        assert!(annotations.tags("tokio").is_empty());
+    }
+}
@@ -21,21 +21,51 @@ use tui_input::backend::crossterm::EventHandler;
 
 use crate::{
     action::Action,
-    config, crates_io_api_helper,
+    advisory::{self, AdvisoryDb},
+    annotations::Annotations,
+    config, crates_index_helper, crates_io_api_helper,
     events::{Event, Events},
+    export::Export,
+    favorites::Favorites,
+    health::HealthReport,
+    license::{self, LicenseCache, LicensePolicy},
+    project::{self, FileWatcher, Project},
+    recently_viewed::RecentlyViewed,
+    rust_version::{self, Compatibility},
+    search_history::{SearchHistory, SearchHistoryEntry},
     serde_helper::keybindings::key_event_to_string,
+    session::{self, Session},
     tui::Tui,
+    watchlist::{CrateSnapshot, Watchlist},
     widgets::{
+        add_dependency::{AddDependencyForm, AddDependencyFormWidget, ManifestPreview, Member},
+        annotation_editor::{AnnotationEditor, AnnotationEditorWidget, AnnotationField},
         crate_info_table::{CrateInfo, CrateInfoTableWidget},
+        dependency_tree::{DependencyTree, DependencyTreeWidget},
+        export_prompt::{ExportPrompt, ExportPromptWidget},
+        favorites::{FavoritesPrompt, FavoritesView, FavoritesWidget},
+        feature_unification::{FeatureUnification, FeatureUnificationWidget},
+        full_crate_info::{FullCrateInfo, FullCrateInfoWidget},
         help::{Help, HelpWidget},
+        inverse_dependencies::{InverseDependencies, InverseDependenciesWidget},
+        lockfile::{LockfileExplorer, LockfileExplorerWidget},
         popup_message::{PopupMessageState, PopupMessageWidget},
+        project_dependencies::{ProjectDependencies, ProjectDependenciesWidget},
+        recently_viewed::{RecentlyViewedView, RecentlyViewedWidget},
+        saved_searches::{SavedSearchesView, SavedSearchesWidget},
         search_filter_prompt::{SearchFilterPrompt, SearchFilterPromptWidget},
+        search_history::{SearchHistoryView, SearchHistoryWidget},
         search_results_table::{SearchResultsTable, SearchResultsTableWidget},
         summary::{Summary, SummaryWidget},
         tabs::SelectedTab,
+        watchlist::{WatchlistView, WatchlistWidget},
     },
+    workspace_graph::{self, WorkspaceGraph},
 };
 
+/// The number of crates fetched per page, unless a saved search asks for another.
+pub const DEFAULT_PAGE_SIZE: u64 = 25;
+
 #[derive(
     Default, Debug, Display, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIs,
 )]
@@ -45,10 +75,25 @@ pub enum Mode {
     #[default]
     Summary,
     Search,
+    SearchHistory,
     Filter,
     // Picker(CrateInfo), unable to make configuration file work with this
     PickerShowCrateInfo,
     PickerHideCrateInfo,
+    FullCrateDetails,
+    DependencyTree,
+    InverseDependencies,
+    FeatureUnification,
+    AddDependency,
+    Project,
+    Lockfile,
+    Favorites,
+    RecentlyViewed,
+    Watchlist,
+    SavedSearches,
+    FavoritesPrompt,
+    AnnotationPrompt,
+    ExportPrompt,
     Popup,
     Help,
     Quit,
@@ -56,7 +101,7 @@ pub enum Mode {
 
 impl Mode {
     pub fn focused(&self) -> bool {
-        matches!(self, Mode::Search | Mode::Filter)
+        matches!(self, Mode::Search | Mode::SearchHistory | Mode::Filter)
     }
 
     pub fn is_picker(&self) -> bool {
@@ -74,10 +119,27 @@ impl Mode {
     pub fn should_show_crate_info(&self) -> bool {
         matches!(self, Mode::PickerShowCrateInfo)
     }
+
+    /// Whether the info panel of the selected crate is shown below the view.
+    pub fn shows_crate_info_panel(&self) -> bool {
+        matches!(
+            self,
+            Mode::PickerShowCrateInfo | Mode::Favorites | Mode::RecentlyViewed | Mode::Watchlist
+        )
+    }
 }
 
 struct AppWidget;
 
+/// The value of a data file, or the default one with the error kept to be
+/// shown once the app starts.
+fn loaded<T: Default>(result: Result<T, String>, errors: &mut Vec<String>) -> T {
+    result.unwrap_or_else(|err| {
+        errors.push(err);
+        T::default()
+    })
+}
+
 #[derive(Debug)]
 pub struct App {
     /// Receiver end of an asynchronous channel for actions that the app needs
@@ -94,6 +156,12 @@ pub struct App {
     /// The number of crates displayed per page in the UI.
     page_size: u64,
 
+    /// The row of the search results to select once they are fetched.
+    pending_selection: Option<usize>,
+
+    /// The row of the summary list to select once the summary is fetched.
+    pending_summary_selection: Option<usize>,
+
     /// Sort preference for search results
     sort: crates_io_api::Sort,
 
@@ -130,6 +198,121 @@ pub struct App {
     /// contains table state for info popup
     crate_info: CrateInfo,
 
+    /// A thread-safe cache of health reports, keyed by crate name.
+    crate_health: Arc<Mutex<HashMap<String, HealthReport>>>,
+
+    /// Whether the search results show a health score column.
+    show_health_column: bool,
+
+    /// Handle of the task evaluating the health of the search results, which
+    /// is aborted when a new search is made.
+    health_task: Option<JoinHandle<()>>,
+
+    /// The licenses crates are checked against, if any are allowed or
+    /// denied.
+    license_policy: Option<LicensePolicy>,
+
+    /// A thread-safe cache of the licenses of every version of a crate, keyed
+    /// by crate name.
+    crate_licenses: Arc<Mutex<LicenseCache>>,
+
+    /// Handle of the task fetching the licenses of the search results, which
+    /// is aborted when a new search is made.
+    license_task: Option<JoinHandle<()>>,
+
+    /// A thread-safe shared container holding the RustSec advisory
+    /// database, if one is configured.
+    advisory_db: Arc<Mutex<Option<AdvisoryDb>>>,
+
+    /// The Rust version search results are checked against, if any.
+    toolchain: Option<semver::Version>,
+
+    /// Whether search results without a version compatible with the
+    /// toolchain are hidden.
+    hide_incompatible: bool,
+
+    /// Handle of the task fetching index entries to check the search results
+    /// against the toolchain, which is aborted when a new search is made.
+    compatibility_task: Option<JoinHandle<()>>,
+
+    /// contains sub-tab and table states for the full crate details page
+    full_crate: FullCrateInfo,
+
+    /// A thread-safe cache of crates.io index entries, shared by dependency
+    /// resolution tasks so that each crate is only fetched once.
+    index_entries: Arc<Mutex<HashMap<String, Vec<crates_index_helper::IndexVersion>>>>,
+
+    /// A thread-safe shared container holding the most recently resolved
+    /// dependency tree.
+    dependency_tree_resolution: Arc<Mutex<Option<crate::resolver::Resolution>>>,
+
+    /// Handle of the task resolving the dependency tree, which is aborted when
+    /// another crate or feature set is resolved.
+    dependency_tree_task: Option<JoinHandle<()>>,
+
+    /// contains the root, features and tree state for the dependency tree view
+    dependency_tree: DependencyTree,
+
+    /// contains the fields of the form for adding a dependency to the current
+    /// project
+    add_dependency: AddDependencyForm,
+
+    /// A thread-safe shared container holding the Cargo workspace crates-tui
+    /// was started in, if any.
+    project: Arc<Mutex<Option<Project>>>,
+
+    /// Watches the manifests and lockfile of the project so that it is loaded
+    /// again when they change.
+    project_watcher: FileWatcher,
+
+    /// Handle of the task loading the project.
+    project_task: Option<JoinHandle<()>>,
+
+    /// contains the dependency rows and table state for the project tab
+    project_dependencies: ProjectDependencies,
+
+    /// contains the locked packages and table state for the lockfile explorer
+    lockfile: LockfileExplorer,
+
+    /// The resolved dependency graph of the workspace, loaded on demand.
+    workspace_graph: Arc<Mutex<Option<WorkspaceGraph>>>,
+
+    /// Handle of the task loading the dependency graph.
+    workspace_graph_task: Option<JoinHandle<()>>,
+
+    /// contains the paths to a crate and list state for the inverse
+    /// dependencies view
+    inverse_dependencies: InverseDependencies,
+
+    /// contains the unified features and table state for the feature
+    /// unification view
+    feature_unification: FeatureUnification,
+
+    /// contains the bookmarked crates and list state for the favorites tab
+    favorites: FavoritesView,
+
+    /// contains the personal notes and tags of crates and the prompt
+    /// editing them
+    annotations: AnnotationEditor,
+
+    /// The prompt for the file the current view is exported to.
+    export: ExportPrompt,
+
+    /// contains the crates whose details were opened and the list state of
+    /// the recently viewed list
+    recently_viewed: RecentlyViewedView,
+
+    /// contains the watched crates and table state for the watchlist view
+    watchlist: WatchlistView,
+
+    /// A thread-safe cache of the state of the watched crates on crates.io,
+    /// keyed by crate name.
+    watchlist_snapshots: Arc<Mutex<HashMap<String, CrateSnapshot>>>,
+
+    /// A thread-safe cache of why watched crates could not be checked, keyed
+    /// by crate name.
+    watchlist_failures: Arc<Mutex<HashMap<String, String>>>,
+
     last_task_details_handle: HashMap<uuid::Uuid, JoinHandle<()>>,
 
     /// The total number of crates fetchable from crates.io, which may not be
@@ -140,6 +323,12 @@ pub struct App {
     /// crates.io as a query
     search: String,
 
+    /// Every submitted search and the state of the search history finder.
+    search_history: SearchHistoryView,
+
+    /// The list state of the saved searches picker.
+    saved_searches: SavedSearchesView,
+
     /// A string for the current filter input by the user, used only locally
     /// for filtering for the list of crates in the current view.
     filter: String,
@@ -182,22 +371,70 @@ pub struct App {
 impl App {
     pub fn new() -> Self {
         let (tx, rx) = mpsc::unbounded_channel();
+        let mut load_errors = vec![];
+        let search_history = loaded(SearchHistory::load(), &mut load_errors);
+        let favorites = loaded(Favorites::load(), &mut load_errors);
+        let annotations = loaded(Annotations::load(), &mut load_errors);
+        let recently_viewed = loaded(RecentlyViewed::load(), &mut load_errors);
+        let watchlist = loaded(Watchlist::load(), &mut load_errors);
+        if !load_errors.is_empty() {
+            let _ = tx.send(Action::ShowErrorPopup(load_errors.join("\n")));
+        }
         Self {
             rx,
             tx,
             page: 1,
-            page_size: 25,
+            page_size: DEFAULT_PAGE_SIZE,
+            pending_selection: None,
+            pending_summary_selection: None,
             sort: crates_io_api::Sort::Relevance,
             mode: Mode::default(),
             last_mode: Mode::default(),
             loading_status: Default::default(),
             search: Default::default(),
+            search_history: SearchHistoryView::new(search_history),
+            saved_searches: Default::default(),
             filter: Default::default(),
             crates: Default::default(),
             versions: Default::default(),
             full_crate_info: Default::default(),
             crate_response: Default::default(),
             crate_info: Default::default(),
+            crate_health: Default::default(),
+            show_health_column: config::get().health.show_in_search_results,
+            health_task: Default::default(),
+            license_policy: LicensePolicy::load(
+                &config::get().policy.licenses,
+                &std::env::current_dir().unwrap_or_default(),
+            ),
+            crate_licenses: Default::default(),
+            license_task: Default::default(),
+            advisory_db: Default::default(),
+            toolchain: rust_version::target_toolchain(),
+            hide_incompatible: config::get().msrv.hide_incompatible,
+            compatibility_task: Default::default(),
+            full_crate: Default::default(),
+            index_entries: Default::default(),
+            dependency_tree_resolution: Default::default(),
+            dependency_tree_task: Default::default(),
+            dependency_tree: Default::default(),
+            add_dependency: Default::default(),
+            project: Default::default(),
+            project_watcher: Default::default(),
+            project_task: Default::default(),
+            project_dependencies: Default::default(),
+            lockfile: Default::default(),
+            workspace_graph: Default::default(),
+            workspace_graph_task: Default::default(),
+            inverse_dependencies: Default::default(),
+            feature_unification: Default::default(),
+            favorites: FavoritesView::new(favorites),
+            annotations: AnnotationEditor::new(annotations),
+            export: Default::default(),
+            recently_viewed: RecentlyViewedView::new(recently_viewed),
+            watchlist: WatchlistView::new(watchlist),
+            watchlist_snapshots: Default::default(),
+            watchlist_failures: Default::default(),
             summary_data: Default::default(),
             summary: Default::default(),
             last_task_details_handle: Default::default(),
@@ -270,6 +507,28 @@ impl App {
                 self.input.handle_event(&crossterm::event::Event::Key(key));
                 self.tx.send(Action::HandleFilterPromptChange)?
             }
+            Mode::AddDependency => self.add_dependency.handle_key(key),
+            Mode::SearchHistory => {
+                self.search_history
+                    .input
+                    .handle_event(&crossterm::event::Event::Key(key));
+                self.search_history.update_matches();
+            }
+            Mode::FavoritesPrompt => {
+                self.favorites
+                    .input
+                    .handle_event(&crossterm::event::Event::Key(key));
+            }
+            Mode::AnnotationPrompt => {
+                self.annotations
+                    .input
+                    .handle_event(&crossterm::event::Event::Key(key));
+            }
+            Mode::ExportPrompt => {
+                self.export
+                    .input
+                    .handle_event(&crossterm::event::Event::Key(key));
+            }
             _ => (),
         };
         Ok(())
@@ -319,8 +578,8 @@ impl App {
             Action::StoreTotalNumberOfCrates(n) => self.store_total_number_of_crates(n),
             Action::ScrollUp => self.scroll_up(),
             Action::ScrollDown => self.scroll_down(),
-            Action::ScrollTop => self.search_results.scroll_to_top(),
-            Action::ScrollBottom => self.search_results.scroll_to_bottom(),
+            Action::ScrollTop => self.scroll_to_top(),
+            Action::ScrollBottom => self.scroll_to_bottom(),
             Action::ScrollCrateInfoUp => self.crate_info.scroll_previous(),
             Action::ScrollCrateInfoDown => self.crate_info.scroll_next(),
             Action::ScrollSearchResultsUp => self.search_results.scroll_previous(1),
@@ -330,6 +589,8 @@ impl App {
             Action::DecrementPage => self.decrement_page(),
             Action::NextSummaryMode => self.summary.next_mode(),
             Action::PreviousSummaryMode => self.summary.previous_mode(),
+            Action::NextFullCrateInfoTab => self.full_crate.next_tab(),
+            Action::PreviousFullCrateInfoTab => self.full_crate.previous_tab(),
             Action::NextTab => self.goto_next_tab(),
             Action::PreviousTab => self.goto_previous_tab(),
             Action::SwitchMode(mode) if mode.is_search() || mode.is_filter() => {
@@ -337,6 +598,7 @@ impl App {
             }
             Action::SwitchMode(Mode::PickerHideCrateInfo) => self.enter_normal_mode(),
             Action::SwitchMode(Mode::PickerShowCrateInfo) => self.enter_normal_mode(),
+            Action::SwitchMode(Mode::Favorites) => self.show_favorites(),
             Action::SwitchMode(mode) => self.switch_mode(mode),
             Action::SwitchToLastMode => self.switch_to_last_mode(),
             Action::HandleFilterPromptChange => self.handle_filter_prompt_change(),
@@ -346,6 +608,114 @@ impl App {
             Action::UpdateSearchTableResults => self.update_search_table_results(),
             Action::UpdateSummary => self.update_summary(),
             Action::ShowFullCrateInfo => self.show_full_crate_details(),
+            Action::CloseFullCrateInfo => self.switch_mode(self.full_crate.return_mode),
+            Action::ShowDependencyTree => self.show_dependency_tree(),
+            Action::CloseDependencyTree => self.switch_mode(self.dependency_tree.return_mode),
+            Action::ToggleDependencyTreeFocus => self.dependency_tree.toggle_focus(),
+            Action::ToggleDependencyTreeSelection => self.toggle_dependency_tree_selection(),
+            Action::UpdateDependencyTree => self.update_dependency_tree(),
+            Action::ToggleHealthColumn => self.toggle_health_column(),
+            Action::RequestSearchResultsHealth => self.request_search_results_health(),
+            Action::RequestSearchResultsLicenses => self.request_search_results_licenses(),
+            Action::ToggleHideIncompatible => self.toggle_hide_incompatible(),
+            Action::RequestSearchResultsCompatibility => {
+                self.request_search_results_compatibility()
+            }
+            Action::AddDependency => self.show_add_dependency_form(),
+            Action::SubmitAddDependency => self.submit_add_dependency(),
+            Action::CancelAddDependency => self.cancel_add_dependency(),
+            Action::UpdateProject => self.update_project(),
+            Action::UpdateProjectDependencies => self.update_project_dependencies(),
+            Action::OpenVersionDiffInBrowser => self.open_version_diff_in_browser()?,
+            Action::BumpDependencyRequirement => self.bump_dependency_requirement(),
+            Action::ShowLockfile => self.show_lockfile(),
+            Action::UpdateLockfileEntries => self.update_lockfile_entries(),
+            Action::ToggleDuplicatesOnly => self.lockfile.toggle_duplicates_only(),
+            Action::ShowInverseDependencies => self.show_inverse_dependencies(),
+            Action::CloseInverseDependencies => {
+                self.switch_mode(self.inverse_dependencies.return_mode)
+            }
+            Action::UpdateWorkspaceGraph => self.update_workspace_graph(),
+            Action::ShowFeatureUnification => self.show_feature_unification(),
+            Action::CloseFeatureUnification => {
+                self.switch_mode(self.feature_unification.return_mode)
+            }
+            Action::PreviousSearchQuery => self.previous_search_query(),
+            Action::NextSearchQuery => self.next_search_query(),
+            Action::ShowSearchHistory => self.show_search_history(),
+            Action::SelectSearchHistory => self.select_search_history(),
+            Action::CloseSearchHistory => self.switch_mode(Mode::Search),
+            Action::ClearSearchHistory => self.clear_search_history(),
+            Action::ShowRecentlyViewed => self.show_recently_viewed(),
+            Action::CloseRecentlyViewed => self.switch_mode(self.recently_viewed.return_mode),
+            Action::RecordRecentlyViewed(ref name) => self.record_recently_viewed(name),
+            Action::ToggleWatch => self.toggle_watch(),
+            Action::ShowWatchlist => self.show_watchlist(),
+            Action::CloseWatchlist => self.switch_mode(self.watchlist.return_mode),
+            Action::AcknowledgeWatched => self.acknowledge_watched(false),
+            Action::AcknowledgeAllWatched => self.acknowledge_watched(true),
+            Action::CheckWatchlist => {
+                let names = self
+                    .watchlist
+                    .watchlist
+                    .crates
+                    .iter()
+                    .map(|c| c.name.clone())
+                    .collect();
+                self.request_watchlist(names);
+            }
+            Action::UpdateWatchlist => self.update_watchlist(),
+            Action::ShowSavedSearches => {
+                if !self.mode.is_saved_searches() {
+                    self.saved_searches.open(self.mode);
+                }
+                self.switch_mode(Mode::SavedSearches);
+            }
+            Action::CloseSavedSearches => self.switch_mode(self.saved_searches.return_mode),
+            Action::SelectSavedSearch => {
+                if let Some(name) = self.saved_searches.selected() {
+                    self.run_saved_search(&name);
+                }
+            }
+            Action::RunSavedSearch(ref name) => self.run_saved_search(name),
+            Action::Export => self.start_export_prompt(),
+            Action::SubmitExportPrompt => self.submit_export_prompt(),
+            Action::CancelExportPrompt => self.switch_mode(self.export.return_mode),
+            Action::CopyJustificationReport => self.request_justification_report(false),
+            Action::WriteJustificationReport => self.request_justification_report(true),
+            Action::DeliverJustificationReport {
+                ref name,
+                ref report,
+                write,
+            } => self.deliver_justification_report(name, report, write),
+            Action::EditCrateNote => self.start_annotation_prompt(AnnotationField::Note),
+            Action::EditCrateTags => self.start_annotation_prompt(AnnotationField::Tags),
+            Action::SubmitAnnotationPrompt => self.submit_annotation_prompt(),
+            Action::CancelAnnotationPrompt => self.switch_mode(self.annotations.return_mode),
+            Action::ToggleFavorite => self.toggle_favorite(),
+            Action::NextCollection => {
+                self.favorites.next_collection();
+                self.update_current_selection_crate_info();
+            }
+            Action::PreviousCollection => {
+                self.favorites.previous_collection();
+                self.update_current_selection_crate_info();
+            }
+            Action::MoveFavoriteUp => self.move_favorite(-1),
+            Action::MoveFavoriteDown => self.move_favorite(1),
+            Action::EditFavoriteNote if self.favorites.selected().is_some() => {
+                self.start_favorites_prompt(FavoritesPrompt::Note)
+            }
+            Action::NewCollection => self.start_favorites_prompt(FavoritesPrompt::NewCollection),
+            Action::RenameCollection => {
+                self.start_favorites_prompt(FavoritesPrompt::RenameCollection)
+            }
+            Action::DeleteCollection => self.delete_collection(),
+            Action::SubmitFavoritesPrompt => self.submit_favorites_prompt(),
+            Action::CancelFavoritesPrompt => {
+                self.favorites.prompt = None;
+                self.switch_mode(Mode::Favorites);
+            }
             Action::ShowErrorPopup(ref err) => self.show_error_popup(err.clone()),
             Action::ShowInfoPopup(ref info) => self.show_info_popup(info.clone()),
             Action::ClosePopup => self.close_popup(),
@@ -357,13 +727,30 @@ impl App {
             Action::OpenCratesIOUrlInBrowser if self.mode.is_summary() => {
                 self.open_summary_url_in_browser()?
             }
+            Action::OpenCratesIOUrlInBrowser if self.mode.is_lockfile() => {
+                self.open_locked_package_url_in_browser()?
+            }
             Action::OpenCratesIOUrlInBrowser => self.open_crates_io_url_in_browser()?,
             Action::CopyCargoAddCommandToClipboard => self.copy_cargo_add_command_to_clipboard()?,
             _ => {}
         }
         let maybe_action = match action {
             Action::ScrollUp | Action::ScrollDown | Action::ScrollTop | Action::ScrollBottom
-                if self.mode.is_summary() || self.mode.is_popup() || self.mode.is_help() =>
+                if self.mode.is_summary()
+                    || self.mode.is_popup()
+                    || self.mode.is_help()
+                    || self.mode.is_full_crate_details()
+                    || self.mode.is_dependency_tree()
+                    || self.mode.is_inverse_dependencies()
+                    || self.mode.is_feature_unification()
+                    || self.mode.is_add_dependency()
+                    || self.mode.is_project()
+                    || self.mode.is_lockfile()
+                    || self.mode.is_favorites_prompt()
+                    || self.mode.is_annotation_prompt()
+                    || self.mode.is_export_prompt()
+                    || self.mode.is_search_history()
+                    || self.mode.is_saved_searches() =>
             {
                 None
             }
@@ -390,13 +777,193 @@ impl App {
 impl App {
     fn tick(&mut self) {
         self.update_search_table_results();
+        // the summary is loaded once its first crate is selected
+        let state = self.summary.get_state_mut(self.summary.mode);
+        if state.selected().is_some() {
+            if let Some(i) = self.pending_summary_selection.take() {
+                state.select(Some(i));
+            }
+        }
+        if self.project_watcher.changed() {
+            self.request_project();
+        }
     }
 
     fn init(&mut self) -> Result<()> {
         self.request_summary()?;
+        self.request_project();
+        self.request_advisory_db();
+        self.request_watchlist(
+            self.watchlist
+                .watchlist
+                .crates
+                .iter()
+                .map(|c| c.name.clone())
+                .collect(),
+        );
         Ok(())
     }
 
+    fn update_project(&mut self) {
+        if let Some(ref project) = *self.project.lock().unwrap() {
+            self.project_watcher = FileWatcher::new(project.watched_files());
+            self.search_results.used = project.used_crates();
+        } else if let Some(manifest_path) = std::env::current_dir()
+            .ok()
+            .and_then(|dir| project::find_manifest(&dir))
+        {
+            // a project that failed to load is loaded again once it is fixed
+            self.project_watcher = FileWatcher::new(project::manifest_files(&manifest_path));
+        }
+        self.update_project_dependencies();
+        self.update_lockfile_entries();
+        // the graph is loaded again the next time it is needed
+        *self.workspace_graph.lock().unwrap() = None;
+        if self.mode.is_inverse_dependencies() || self.mode.is_feature_unification() {
+            self.request_workspace_graph();
+        }
+        if self.mode.is_project() {
+            self.request_project_index_entries();
+            self.request_project_licenses();
+        }
+        if self.mode.is_lockfile() {
+            self.request_lockfile_index_entries();
+        }
+    }
+
+    fn update_lockfile_entries(&mut self) {
+        if let Some(ref project) = *self.project.lock().unwrap() {
+            self.lockfile.entries = project
+                .lockfile
+                .entries(&self.index_entries.lock().unwrap());
+        }
+    }
+
+    /// Shows every path from the workspace members to the selected crate.
+    fn show_inverse_dependencies(&mut self) {
+        if self.project.lock().unwrap().is_none() {
+            self.show_error_popup("crates-tui was not started inside a Cargo project".into());
+            return;
+        }
+        let target = self
+            .selected_project_crate()
+            .or_else(|| self.selected_crate_version().map(|(name, _)| (name, None)));
+        let Some((name, version)) = target else {
+            return;
+        };
+        self.inverse_dependencies = InverseDependencies::new(name, version, self.mode);
+        self.switch_mode(Mode::InverseDependencies);
+        if self.workspace_graph.lock().unwrap().is_some() {
+            self.update_workspace_graph();
+        } else {
+            self.request_workspace_graph();
+        }
+    }
+
+    /// Shows the unified features of every package of the workspace.
+    fn show_feature_unification(&mut self) {
+        if self.project.lock().unwrap().is_none() {
+            self.show_error_popup("crates-tui was not started inside a Cargo project".into());
+            return;
+        }
+        self.feature_unification =
+            FeatureUnification::new(self.selected_project_crate(), self.mode);
+        self.switch_mode(Mode::FeatureUnification);
+        if self.workspace_graph.lock().unwrap().is_some() {
+            self.update_workspace_graph();
+        } else {
+            self.request_workspace_graph();
+        }
+    }
+
+    /// The crate selected in the Project tab or the lockfile explorer.
+    fn selected_project_crate(&self) -> Option<(String, Option<semver::Version>)> {
+        match self.mode {
+            Mode::Lockfile => self
+                .lockfile
+                .selected()
+                .map(|e| (e.package.name.clone(), Some(e.package.version.clone()))),
+            Mode::Project => self
+                .project_dependencies
+                .selected()
+                .map(|d| (d.name.clone(), d.locked.clone())),
+            _ => None,
+        }
+    }
+
+    fn update_workspace_graph(&mut self) {
+        match self.mode {
+            Mode::InverseDependencies => self.update_inverse_dependencies(),
+            Mode::FeatureUnification => {
+                if let Some(ref graph) = *self.workspace_graph.lock().unwrap() {
+                    self.feature_unification.set_graph(graph);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn update_inverse_dependencies(&mut self) {
+        let Some(ref graph) = *self.workspace_graph.lock().unwrap() else {
+            return;
+        };
+        let state = &mut self.inverse_dependencies;
+        let mut truncated = false;
+        let paths = graph
+            .find(&state.name, state.version.as_ref())
+            .iter()
+            .flat_map(|target| {
+                let (paths, more) = graph.paths_to(target);
+                truncated |= more;
+                paths
+            })
+            .map(|path| path.into_iter().cloned().collect_vec())
+            .collect_vec();
+        state.paths = Some(paths);
+        state.truncated = truncated;
+    }
+
+    fn show_lockfile(&mut self) {
+        self.switch_mode(Mode::Lockfile);
+        self.request_lockfile_index_entries();
+    }
+
+    fn update_project_dependencies(&mut self) {
+        if let Some(ref project) = *self.project.lock().unwrap() {
+            self.project_dependencies.dependencies =
+                project.dependencies(&self.index_entries.lock().unwrap());
+        }
+        if let Some(ref db) = *self.advisory_db.lock().unwrap() {
+            self.project_dependencies.advisories = self
+                .project_dependencies
+                .dependencies
+                .iter()
+                .filter_map(|d| {
+                    let locked = d.locked.as_ref()?;
+                    let advisories = db.affecting(&d.name, locked);
+                    (!advisories.is_empty()).then(|| ((d.name.clone(), locked.clone()), advisories))
+                })
+                .collect();
+        }
+        if let Some(ref policy) = self.license_policy {
+            let licenses = self.crate_licenses.lock().unwrap();
+            self.project_dependencies.license_violations = self
+                .project_dependencies
+                .dependencies
+                .iter()
+                .filter_map(|d| {
+                    let version = d.version()?;
+                    let license = licenses.get(&d.name)?.get(&version.to_string())?;
+                    let verdict = policy.evaluate(license.as_deref());
+                    Some((
+                        (d.name.clone(), version.clone()),
+                        verdict.violation()?.to_string(),
+                    ))
+                })
+                .collect();
+        }
+    }
+
     fn update_summary(&mut self) {
         if let Some(summary) = self.summary_data.lock().unwrap().clone() {
             self.summary.summary_data = Some(summary);
@@ -412,13 +979,40 @@ impl App {
         let filter = self.filter.clone();
         let filter_words = filter.split_whitespace().collect::<Vec<_>>();
 
+        if let Some(ref toolchain) = self.toolchain {
+            let index_entries = self.index_entries.lock().unwrap();
+            self.search_results.compatibility = self
+                .crates
+                .lock()
+                .unwrap()
+                .iter()
+                .filter_map(|c| {
+                    let versions = index_entries.get(&c.name)?;
+                    Some((
+                        c.name.clone(),
+                        rust_version::compatibility(versions, toolchain),
+                    ))
+                })
+                .collect();
+        }
+        let compatibility = &self.search_results.compatibility;
+        let hide_incompatible = self.hide_incompatible;
+        let annotations = &self.annotations.annotations;
+
         let crates: Vec<_> = self
             .crates
             .lock()
             .unwrap()
             .iter()
+            .filter(|c| {
+                !(hide_incompatible
+                    && compatibility.get(&c.name) == Some(&Compatibility::Incompatible))
+            })
             .filter(|c| {
                 filter_words.iter().all(|word| {
+                    if let Some(tag) = word.strip_prefix("tag:") {
+                        return annotations.tags(&c.name).iter().any(|t| t == tag);
+                    }
                     c.name.to_lowercase().contains(word)
                         || c.description
                             .clone()
@@ -430,6 +1024,48 @@ impl App {
             .cloned()
             .collect_vec();
         self.search_results.crates = crates;
+
+        // the results are in once the first crate is selected, and the
+        // restored row is one of the filtered ones
+        if self.search_results.selected().is_some() {
+            if let Some(i) = self.pending_selection.take() {
+                let last = self.search_results.crates.len().saturating_sub(1);
+                self.search_results.select(Some(i.min(last)));
+                let _ = self.tx.send(Action::UpdateCurrentSelectionCrateInfo);
+            }
+        }
+        self.search_results.favorites = self
+            .search_results
+            .crates
+            .iter()
+            .filter(|c| self.favorites.favorites.contains(&c.name))
+            .map(|c| c.name.clone())
+            .collect();
+        self.search_results.tags = self
+            .search_results
+            .crates
+            .iter()
+            .filter_map(|c| {
+                let tags = self.annotations.annotations.tags(&c.name);
+                (!tags.is_empty()).then(|| (c.name.clone(), tags.to_vec()))
+            })
+            .collect();
+        if self.show_health_column {
+            self.search_results.health = self.crate_health.lock().unwrap().clone();
+        }
+        if let Some(ref policy) = self.license_policy {
+            let licenses = self.crate_licenses.lock().unwrap();
+            self.search_results.license_violations = self
+                .search_results
+                .crates
+                .iter()
+                .filter_map(|c| {
+                    let license = licenses.get(&c.name)?.get(&c.max_version)?;
+                    let verdict = policy.evaluate(license.as_deref());
+                    Some((c.name.clone(), verdict.violation()?.to_string()))
+                })
+                .collect();
+        }
     }
 
     fn key_refresh_tick(&mut self) {
@@ -447,9 +1083,73 @@ impl App {
     }
 
     fn quit(&mut self) {
+        self.save_session();
         self.mode = Mode::Quit
     }
 
+    /// Remembers what is shown so that the next launch can restore it.
+    fn save_session(&mut self) {
+        self.summary.save_state();
+        let mode = match self.mode {
+            Mode::Popup | Mode::Help => self.last_mode,
+            Mode::FullCrateDetails => self.full_crate.return_mode,
+            Mode::DependencyTree => self.dependency_tree.return_mode,
+            Mode::InverseDependencies => self.inverse_dependencies.return_mode,
+            Mode::FeatureUnification => self.feature_unification.return_mode,
+            Mode::AddDependency => self.add_dependency.return_mode,
+            Mode::AnnotationPrompt => self.annotations.return_mode,
+            Mode::ExportPrompt => self.export.return_mode,
+            Mode::SavedSearches => self.saved_searches.return_mode,
+            mode => mode,
+        };
+        let session = Session {
+            mode: session::restored_mode(mode),
+            search: self.search.clone(),
+            sort: self.sort.clone(),
+            page: self.page,
+            page_size: self.page_size,
+            filter: self.filter.clone(),
+            selected: self.search_results.selected(),
+            summary_mode: self.summary.mode,
+            summary_selection: self.summary.last_selection,
+        };
+        if let Err(err) = session.save() {
+            error!("Unable to save the session: {err}");
+        }
+    }
+
+    /// Picks up from a session saved when crates-tui was last quit, running
+    /// its search again.
+    pub fn restore_session(&mut self, session: Session) {
+        self.summary.mode = session.summary_mode;
+        self.summary.last_selection = session.summary_selection;
+        // selections are applied once the lists they belong to are loaded,
+        // which scrolls the list shown to its first crate
+        if session.mode.is_summary() {
+            self.pending_summary_selection =
+                Some(session.summary_selection[session.summary_mode as usize]);
+        }
+        self.sort = session.sort;
+        self.page = session.page.max(1);
+        self.page_size = session.page_size.clamp(1, 100);
+        self.filter = session.filter;
+        self.search = session.search;
+        self.input = tui_input::Input::new(self.search.clone());
+        if !self.search.is_empty() {
+            self.reload_data();
+            if session.mode.is_search() || session.mode.is_picker() {
+                self.pending_selection = session.selected;
+            }
+        }
+        match session.mode {
+            Mode::Project => self.show_project(),
+            Mode::Favorites => self.show_favorites(),
+            Mode::RecentlyViewed => self.show_recently_viewed(),
+            Mode::Watchlist => self.show_watchlist(),
+            mode => self.switch_mode(mode),
+        }
+    }
+
     fn scroll_up(&mut self) {
         match self.mode {
             Mode::Popup => {
@@ -459,6 +1159,19 @@ impl App {
             }
             Mode::Summary => self.summary.scroll_previous(),
             Mode::Help => self.help.scroll_previous(),
+            Mode::FullCrateDetails => self.full_crate.scroll_previous(),
+            Mode::DependencyTree => self.dependency_tree.scroll_previous(),
+            Mode::InverseDependencies => self.inverse_dependencies.scroll_previous(),
+            Mode::FeatureUnification => self.feature_unification.scroll_previous(),
+            Mode::AddDependency => self.add_dependency.previous(),
+            Mode::Project => self.project_dependencies.scroll_previous(),
+            Mode::Lockfile => self.lockfile.scroll_previous(),
+            Mode::Favorites => self.favorites.scroll_previous(),
+            Mode::RecentlyViewed => self.recently_viewed.scroll_previous(),
+            Mode::Watchlist => self.watchlist.scroll_previous(),
+            Mode::SearchHistory => self.search_history.scroll_previous(),
+            Mode::SavedSearches => self.saved_searches.scroll_previous(),
+            Mode::FavoritesPrompt | Mode::AnnotationPrompt | Mode::ExportPrompt => {}
             _ => self.search_results.scroll_previous(1),
         }
     }
@@ -472,10 +1185,65 @@ impl App {
             }
             Mode::Summary => self.summary.scroll_next(),
             Mode::Help => self.help.scroll_next(),
+            Mode::FullCrateDetails => self.full_crate.scroll_next(),
+            Mode::DependencyTree => self.dependency_tree.scroll_next(),
+            Mode::InverseDependencies => self.inverse_dependencies.scroll_next(),
+            Mode::FeatureUnification => self.feature_unification.scroll_next(),
+            Mode::AddDependency => self.add_dependency.next(),
+            Mode::Project => self.project_dependencies.scroll_next(),
+            Mode::Lockfile => self.lockfile.scroll_next(),
+            Mode::Favorites => self.favorites.scroll_next(),
+            Mode::RecentlyViewed => self.recently_viewed.scroll_next(),
+            Mode::Watchlist => self.watchlist.scroll_next(),
+            Mode::SearchHistory => self.search_history.scroll_next(),
+            Mode::SavedSearches => self.saved_searches.scroll_next(),
+            Mode::FavoritesPrompt | Mode::AnnotationPrompt | Mode::ExportPrompt => {}
             _ => self.search_results.scroll_next(1),
         }
     }
 
+    fn scroll_to_top(&mut self) {
+        match self.mode {
+            Mode::FullCrateDetails => self.full_crate.scroll_to_top(),
+            Mode::DependencyTree => self.dependency_tree.scroll_to_top(),
+            Mode::InverseDependencies => self.inverse_dependencies.scroll_to_top(),
+            Mode::FeatureUnification => self.feature_unification.scroll_to_top(),
+            Mode::Project => self.project_dependencies.scroll_to_top(),
+            Mode::Lockfile => self.lockfile.scroll_to_top(),
+            Mode::Favorites => self.favorites.scroll_to_top(),
+            Mode::RecentlyViewed => self.recently_viewed.scroll_to_top(),
+            Mode::Watchlist => self.watchlist.scroll_to_top(),
+            Mode::SavedSearches => self.saved_searches.scroll_to_top(),
+            Mode::AddDependency
+            | Mode::FavoritesPrompt
+            | Mode::AnnotationPrompt
+            | Mode::ExportPrompt
+            | Mode::SearchHistory => {}
+            _ => self.search_results.scroll_to_top(),
+        }
+    }
+
+    fn scroll_to_bottom(&mut self) {
+        match self.mode {
+            Mode::FullCrateDetails => self.full_crate.scroll_to_bottom(),
+            Mode::DependencyTree => self.dependency_tree.scroll_to_bottom(),
+            Mode::InverseDependencies => self.inverse_dependencies.scroll_to_bottom(),
+            Mode::FeatureUnification => self.feature_unification.scroll_to_bottom(),
+            Mode::Project => self.project_dependencies.scroll_to_bottom(),
+            Mode::Lockfile => self.lockfile.scroll_to_bottom(),
+            Mode::Favorites => self.favorites.scroll_to_bottom(),
+            Mode::RecentlyViewed => self.recently_viewed.scroll_to_bottom(),
+            Mode::Watchlist => self.watchlist.scroll_to_bottom(),
+            Mode::SavedSearches => self.saved_searches.scroll_to_bottom(),
+            Mode::AddDependency
+            | Mode::FavoritesPrompt
+            | Mode::AnnotationPrompt
+            | Mode::ExportPrompt
+            | Mode::SearchHistory => {}
+            _ => self.search_results.scroll_to_bottom(),
+        }
+    }
+
     fn increment_page(&mut self) {
         if let Some(n) = self.total_num_crates {
             let max_page_size = (n / self.page_size) + 1;
@@ -496,6 +1264,7 @@ impl App {
 
     fn enter_insert_mode(&mut self, mode: Mode) {
         self.switch_mode(mode);
+        self.search_history.reset_browsing();
         self.input = self.input.clone().with_value(if self.mode.is_search() {
             self.search.clone()
         } else if self.mode.is_filter() {
@@ -516,10 +1285,32 @@ impl App {
         self.last_mode = self.mode;
         self.mode = mode;
         match self.mode {
-            Mode::Search | Mode::Filter | Mode::PickerHideCrateInfo | Mode::PickerShowCrateInfo => {
-                self.selected_tab.select(SelectedTab::Search)
+            Mode::Search
+            | Mode::SearchHistory
+            | Mode::Filter
+            | Mode::PickerHideCrateInfo
+            | Mode::PickerShowCrateInfo
+            | Mode::FullCrateDetails
+            | Mode::DependencyTree
+            | Mode::AddDependency => self.selected_tab.select(SelectedTab::Search),
+            Mode::Summary | Mode::RecentlyViewed | Mode::Watchlist => {
+                self.selected_tab.select(SelectedTab::Summary)
+            }
+            Mode::Project | Mode::Lockfile | Mode::FeatureUnification => {
+                self.selected_tab.select(SelectedTab::Project)
+            }
+            Mode::Favorites | Mode::FavoritesPrompt => {
+                self.selected_tab.select(SelectedTab::Favorites)
+            }
+            Mode::InverseDependencies => {
+                let tab = match self.inverse_dependencies.return_mode {
+                    Mode::Project | Mode::Lockfile => SelectedTab::Project,
+                    _ => SelectedTab::Search,
+                };
+                self.selected_tab.select(tab)
             }
-            Mode::Summary => self.selected_tab.select(SelectedTab::Summary),
+            // these are shown over the view they were opened from
+            Mode::AnnotationPrompt | Mode::ExportPrompt | Mode::SavedSearches => {}
             Mode::Help => {
                 self.help.mode = Some(self.last_mode);
                 self.selected_tab.select(SelectedTab::None)
@@ -534,115 +1325,840 @@ impl App {
 
     fn goto_next_tab(&mut self) {
         match self.mode {
-            Mode::Summary => self.switch_mode(Mode::Search),
-            Mode::Search => self.switch_mode(Mode::Summary),
-            _ => self.switch_mode(Mode::Summary),
+            // switching tabs would abandon what is being typed
+            Mode::FavoritesPrompt | Mode::AnnotationPrompt | Mode::ExportPrompt => {}
+            Mode::Summary | Mode::RecentlyViewed | Mode::Watchlist => {
+                self.switch_mode(Mode::Search)
+            }
+            Mode::Project | Mode::Lockfile => self.show_favorites(),
+            Mode::Favorites => self.switch_mode(Mode::Summary),
+            _ => self.show_project(),
         }
     }
 
     fn goto_previous_tab(&mut self) {
         match self.mode {
-            Mode::Summary => self.switch_mode(Mode::Search),
-            Mode::Search => self.switch_mode(Mode::Summary),
+            Mode::FavoritesPrompt | Mode::AnnotationPrompt | Mode::ExportPrompt => {}
+            Mode::Summary | Mode::RecentlyViewed | Mode::Watchlist => self.show_favorites(),
+            Mode::Project | Mode::Lockfile => self.switch_mode(Mode::Search),
+            Mode::Favorites => self.show_project(),
             _ => self.switch_mode(Mode::Summary),
         }
     }
 
-    fn handle_filter_prompt_change(&mut self) {
-        self.filter = self.input.value().into();
-        self.search_results.select(None);
+    fn show_project(&mut self) {
+        self.switch_mode(Mode::Project);
+        self.request_project_index_entries();
+        self.request_project_licenses();
     }
 
-    fn submit_search(&mut self) {
-        self.clear_all_previous_task_details_handles();
-        self.switch_mode(Mode::PickerHideCrateInfo);
-        self.filter.clear();
-        self.search = self.input.value().into();
+    fn show_favorites(&mut self) {
+        self.favorites.prompt = None;
+        self.switch_mode(Mode::Favorites);
+        self.update_current_selection_crate_info();
     }
 
-    fn toggle_show_crate_info(&mut self) {
-        self.mode.toggle_crate_info();
-        if self.mode.should_show_crate_info() {
-            self.request_crate_details()
-        } else {
-            self.clear_all_previous_task_details_handles();
+    fn show_recently_viewed(&mut self) {
+        if !self.mode.is_recently_viewed() {
+            self.recently_viewed.open(self.mode);
         }
+        self.switch_mode(Mode::RecentlyViewed);
+        self.update_current_selection_crate_info();
     }
 
-    fn toggle_sort_by_forward(&mut self) {
-        use crates_io_api::Sort as S;
-        self.sort = match self.sort {
-            S::Alphabetical => S::Relevance,
-            S::Relevance => S::Downloads,
-            S::Downloads => S::RecentDownloads,
-            S::RecentDownloads => S::RecentUpdates,
-            S::RecentUpdates => S::NewlyAdded,
-            S::NewlyAdded => S::Alphabetical,
-        };
+    /// Remembers that the details of a crate were opened, unless it was from
+    /// the recently viewed list, which would reorder it while browsing it.
+    fn record_recently_viewed(&mut self, name: &str) {
+        if self.mode.is_recently_viewed() {
+            return;
+        }
+        self.recently_viewed.recent.record(
+            name,
+            chrono::Utc::now(),
+            config::get().recently_viewed.max_entries,
+        );
+        if let Err(err) = self.recently_viewed.recent.save() {
+            error!("Unable to save the recently viewed crates: {err}");
+        }
     }
 
-    fn toggle_sort_by_backward(&mut self) {
-        use crates_io_api::Sort as S;
-        self.sort = match self.sort {
-            S::Relevance => S::Alphabetical,
-            S::Downloads => S::Relevance,
-            S::RecentDownloads => S::Downloads,
-            S::RecentUpdates => S::RecentDownloads,
-            S::NewlyAdded => S::RecentUpdates,
-            S::Alphabetical => S::NewlyAdded,
+    fn show_watchlist(&mut self) {
+        if !self.mode.is_watchlist() {
+            self.watchlist.open(self.mode);
+        }
+        self.switch_mode(Mode::Watchlist);
+        self.update_current_selection_crate_info();
+    }
+
+    /// Starts watching the selected crate, or stops watching it if it is
+    /// already watched.
+    fn toggle_watch(&mut self) {
+        let Some(name) = self.selected_crate_name() else {
+            return;
         };
+        let watched = self.watchlist.watchlist.toggle(&name);
+        if let Err(err) = self.watchlist.watchlist.save() {
+            self.show_error_popup(format!("Unable to save the watchlist: {err}"));
+            return;
+        }
+        if watched {
+            self.request_watchlist(vec![name.clone()]);
+        }
+        if self.mode.is_watchlist() {
+            self.watchlist.clamp_selection();
+            self.update_current_selection_crate_info();
+        } else {
+            self.show_info_popup(if watched {
+                format!("Watching `{name}` for new releases, yanks and owner changes")
+            } else {
+                format!("Stopped watching `{name}`")
+            });
+        }
     }
 
-    fn toggle_sort_by(&mut self, reload: bool, forward: bool) -> Result<()> {
-        if forward {
-            self.toggle_sort_by_forward()
+    /// Marks the current state of the selected watched crate, or of every
+    /// watched crate, as seen.
+    fn acknowledge_watched(&mut self, all: bool) {
+        let names = if all {
+            self.watchlist.changed()
         } else {
-            self.toggle_sort_by_backward()
+            self.watchlist
+                .selected()
+                .map(|c| c.name.clone())
+                .into_iter()
+                .collect()
         };
-        if reload {
-            self.tx.send(Action::ReloadData)?;
+        for name in names {
+            if let Some(current) = self.watchlist.current.get(&name).cloned() {
+                self.watchlist.watchlist.acknowledge(&name, &current);
+            }
+        }
+        if let Err(err) = self.watchlist.watchlist.save() {
+            self.show_error_popup(format!("Unable to save the watchlist: {err}"));
         }
-        Ok(())
     }
 
-    fn show_error_popup(&mut self, message: String) {
-        error!("Error: {message}");
-        self.popup = Some((
-            PopupMessageWidget::new("Error".into(), message),
-            PopupMessageState::default(),
-        ));
-        self.last_mode = self.mode;
-        self.mode = Mode::Popup;
+    /// Takes the state of the watched crates fetched so far. Crates that were
+    /// just watched are acknowledged, and the watchlist is shown if crates
+    /// changed since they were last seen while the summary is still shown.
+    fn update_watchlist(&mut self) {
+        self.watchlist.loading = false;
+        self.watchlist.current = self.watchlist_snapshots.lock().unwrap().clone();
+        self.watchlist.failed = self.watchlist_failures.lock().unwrap().clone();
+        let current = &self.watchlist.current;
+        if self
+            .watchlist
+            .watchlist
+            .acknowledge_new(|name| current.get(name))
+        {
+            if let Err(err) = self.watchlist.watchlist.save() {
+                error!("Unable to save the watchlist: {err}");
+            }
+        }
+        if self.mode.is_summary() && !self.watchlist.changed().is_empty() {
+            self.show_watchlist();
+        }
     }
 
-    fn show_info_popup(&mut self, info: String) {
-        info!("Info: {info}");
-        self.popup = Some((
-            PopupMessageWidget::new("Info".into(), info),
-            PopupMessageState::default(),
-        ));
-        self.last_mode = self.mode;
-        self.mode = Mode::Popup;
+    fn start_annotation_prompt(&mut self, field: AnnotationField) {
+        let Some(name) = self.selected_crate_name() else {
+            return;
+        };
+        self.annotations.start(&name, field, self.mode);
+        self.switch_mode(Mode::AnnotationPrompt);
     }
 
-    fn close_popup(&mut self) {
-        self.popup = None;
-        self.mode = if self.last_mode.is_popup() {
-            Mode::Search
-        } else {
-            self.last_mode
+    fn submit_annotation_prompt(&mut self) {
+        self.annotations.submit();
+        self.switch_mode(self.annotations.return_mode);
+        if let Err(err) = self.annotations.annotations.save() {
+            self.show_error_popup(format!("Unable to save the crate notes: {err}"));
         }
+        self.update_search_table_results();
     }
 
-    fn update_current_selection_crate_info(&mut self) {
-        self.clear_all_previous_task_details_handles();
-        self.request_crate_details();
+    /// Asks which file the rows of the current view are exported to.
+    fn start_export_prompt(&mut self) {
+        let file_name = match self.mode {
+            Mode::PickerShowCrateInfo | Mode::PickerHideCrateInfo => "crates.csv",
+            Mode::Favorites => "favorites.csv",
+            Mode::Lockfile => "lockfile.csv",
+            _ => return,
+        };
+        self.export.start(file_name, self.mode);
+        self.switch_mode(Mode::ExportPrompt);
     }
 
-    fn show_full_crate_details(&mut self) {
-        self.clear_all_previous_task_details_handles();
+    fn submit_export_prompt(&mut self) {
+        let Some(path) = self.export.confirmed_path() else {
+            return;
+        };
+        self.switch_mode(self.export.return_mode);
+        if let Some(report) = self.export.report.take() {
+            match std::fs::write(&path, report) {
+                Ok(()) => self.show_info_popup(format!("Wrote the report to `{}`", path.display())),
+                Err(err) => {
+                    self.show_error_popup(format!("Unable to write `{}`: {err}", path.display()))
+                }
+            }
+            return;
+        }
+        let export = match self.export.return_mode {
+            Mode::Favorites => Export::favorites(&[self.favorites.collection()]),
+            Mode::Lockfile => Export::lockfile(&self.lockfile.visible()),
+            _ => Export::search_results(&self.search_results.crates),
+        };
+        match export.write(&path, None) {
+            Ok(()) => self.show_info_popup(format!(
+                "Exported {} rows to `{}`",
+                export.len(),
+                path.display()
+            )),
+            Err(err) => self.show_error_popup(err),
+        }
+    }
+
+    /// Bookmarks the selected crate in the shown collection, or removes it if
+    /// it is already there.
+    fn toggle_favorite(&mut self) {
+        let Some(name) = self.selected_crate_name() else {
+            return;
+        };
+        let collection = self.favorites.collection;
+        let added = self.favorites.favorites.toggle(collection, &name);
+        if let Err(err) = self.favorites.favorites.save() {
+            self.show_error_popup(format!("Unable to save favorites: {err}"));
+            return;
+        }
+        self.update_search_table_results();
+        if self.mode.is_favorites() {
+            self.favorites.clamp_selection();
+            self.update_current_selection_crate_info();
+        } else {
+            let collection = &self.favorites.collection().name;
+            self.show_info_popup(if added {
+                format!("Added `{name}` to `{collection}`")
+            } else {
+                format!("Removed `{name}` from `{collection}`")
+            });
+        }
+    }
+
+    fn move_favorite(&mut self, offset: isize) {
+        let Some(index) = self.favorites.selected_index() else {
+            return;
+        };
+        let collection = self.favorites.collection;
+        if let Some(index) = self
+            .favorites
+            .favorites
+            .move_crate(collection, index, offset)
+        {
+            self.favorites.select(Some(index));
+            if let Err(err) = self.favorites.favorites.save() {
+                self.show_error_popup(format!("Unable to save favorites: {err}"));
+            }
+        }
+    }
+
+    fn start_favorites_prompt(&mut self, prompt: FavoritesPrompt) {
+        self.favorites.start_prompt(prompt);
+        self.switch_mode(Mode::FavoritesPrompt);
+    }
+
+    fn submit_favorites_prompt(&mut self) {
+        let Some(prompt) = self.favorites.prompt.take() else {
+            return;
+        };
+        let value = self.favorites.input.value().to_string();
+        let collection = self.favorites.collection;
+        let result = match prompt {
+            FavoritesPrompt::Note => {
+                if let Some(i) = self.favorites.selected_index() {
+                    self.favorites.favorites.collections[collection].crates[i].note =
+                        value.trim().into();
+                }
+                Ok(())
+            }
+            FavoritesPrompt::NewCollection => self
+                .favorites
+                .favorites
+                .add_collection(&value)
+                .map(|i| self.favorites.select_collection(i)),
+            FavoritesPrompt::RenameCollection => self
+                .favorites
+                .favorites
+                .rename_collection(collection, &value),
+        };
+        self.switch_mode(Mode::Favorites);
+        match result.and_then(|_| self.favorites.favorites.save()) {
+            Ok(()) => self.update_current_selection_crate_info(),
+            Err(err) => self.show_error_popup(err),
+        }
+    }
+
+    fn delete_collection(&mut self) {
+        let collection = self.favorites.collection;
+        let result = self
+            .favorites
+            .favorites
+            .remove_collection(collection)
+            .and_then(|_| self.favorites.favorites.save());
+        match result {
+            Ok(()) => {
+                self.favorites
+                    .select_collection(collection.saturating_sub(1));
+                self.update_current_selection_crate_info();
+            }
+            Err(err) => self.show_error_popup(err),
+        }
+    }
+
+    /// The name of the crate selected in the current view.
+    fn selected_crate_name(&self) -> Option<String> {
+        match self.mode {
+            Mode::Summary => self.summary.selected_crate_name(),
+            Mode::Favorites => self.favorites.selected().map(|c| c.name.clone()),
+            Mode::RecentlyViewed => self.recently_viewed.selected().map(|c| c.name.clone()),
+            Mode::Watchlist => self.watchlist.selected().map(|c| c.name.clone()),
+            Mode::FullCrateDetails => self
+                .full_crate_info
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|c| c.name.clone()),
+            _ => self.search_results.selected_crate_name(),
+        }
+    }
+
+    fn handle_filter_prompt_change(&mut self) {
+        self.filter = self.input.value().into();
+        self.search_results.select(None);
+    }
+
+    fn submit_search(&mut self) {
+        self.clear_all_previous_task_details_handles();
+        self.switch_mode(Mode::PickerHideCrateInfo);
+        self.filter.clear();
+        self.page_size = DEFAULT_PAGE_SIZE;
+        self.search = self.input.value().into();
+        self.search_history.reset_browsing();
+        self.search_history.history.push(
+            &self.search,
+            self.sort.clone(),
+            config::get().search_history.max_entries,
+        );
+        if let Err(err) = self.search_history.history.save() {
+            error!("Unable to save the search history: {err}");
+        }
+    }
+
+    /// Shows the previous submitted search in the search prompt.
+    fn previous_search_query(&mut self) {
+        let current = SearchHistoryEntry {
+            query: self.input.value().into(),
+            sort: self.sort.clone(),
+        };
+        if let Some(entry) = self.search_history.previous(current) {
+            self.input = self.input.clone().with_value(entry.query);
+            self.sort = entry.sort;
+        }
+    }
+
+    /// Shows the next submitted search in the search prompt, or what was
+    /// typed before going through the history.
+    fn next_search_query(&mut self) {
+        if let Some(entry) = self.search_history.next() {
+            self.input = self.input.clone().with_value(entry.query);
+            self.sort = entry.sort;
+        }
+    }
+
+    fn show_search_history(&mut self) {
+        self.search_history.open_finder(self.input.value());
+        self.switch_mode(Mode::SearchHistory);
+    }
+
+    fn select_search_history(&mut self) {
+        if let Some(entry) = self.search_history.selected().cloned() {
+            self.input = self.input.clone().with_value(entry.query);
+            self.sort = entry.sort;
+        }
+        self.search_history.reset_browsing();
+        self.switch_mode(Mode::Search);
+    }
+
+    /// Runs the saved search named `name` from the configuration, with its
+    /// own sort, filter and page size.
+    fn run_saved_search(&mut self, name: &str) {
+        let Some(saved) = config::get().saved_searches.iter().find(|s| s.name == name) else {
+            self.show_error_popup(format!("There is no saved search named `{name}`"));
+            return;
+        };
+        self.clear_all_previous_task_details_handles();
+        self.search = saved.query.clone();
+        self.input = tui_input::Input::new(self.search.clone());
+        self.sort = saved.sort.clone();
+        self.page = 1;
+        self.page_size = saved.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, 100);
+        self.filter = saved.filter.clone();
+        self.switch_mode(Mode::PickerHideCrateInfo);
+        self.reload_data();
+    }
+
+    fn clear_search_history(&mut self) {
+        let count = self.search_history.history.entries.len();
+        self.search_history.history.clear();
+        self.search_history.update_matches();
+        if let Err(err) = self.search_history.history.save() {
+            self.show_error_popup(format!("Unable to clear the search history: {err}"));
+            return;
+        }
+        self.show_info_popup(format!("Cleared {count} searches from the history"));
+    }
+
+    fn toggle_show_crate_info(&mut self) {
+        self.mode.toggle_crate_info();
+        if self.mode.should_show_crate_info() {
+            self.request_crate_details()
+        } else {
+            self.clear_all_previous_task_details_handles();
+        }
+    }
+
+    fn toggle_sort_by_forward(&mut self) {
+        use crates_io_api::Sort as S;
+        self.sort = match self.sort {
+            S::Alphabetical => S::Relevance,
+            S::Relevance => S::Downloads,
+            S::Downloads => S::RecentDownloads,
+            S::RecentDownloads => S::RecentUpdates,
+            S::RecentUpdates => S::NewlyAdded,
+            S::NewlyAdded => S::Alphabetical,
+        };
+    }
+
+    fn toggle_sort_by_backward(&mut self) {
+        use crates_io_api::Sort as S;
+        self.sort = match self.sort {
+            S::Relevance => S::Alphabetical,
+            S::Downloads => S::Relevance,
+            S::RecentDownloads => S::Downloads,
+            S::RecentUpdates => S::RecentDownloads,
+            S::NewlyAdded => S::RecentUpdates,
+            S::Alphabetical => S::NewlyAdded,
+        };
+    }
+
+    fn toggle_sort_by(&mut self, reload: bool, forward: bool) -> Result<()> {
+        if forward {
+            self.toggle_sort_by_forward()
+        } else {
+            self.toggle_sort_by_backward()
+        };
+        if reload {
+            self.tx.send(Action::ReloadData)?;
+        }
+        Ok(())
+    }
+
+    pub fn show_error_popup(&mut self, message: String) {
+        error!("Error: {message}");
+        self.popup = Some((
+            PopupMessageWidget::new("Error".into(), message),
+            PopupMessageState::default(),
+        ));
+        self.last_mode = self.mode;
+        self.mode = Mode::Popup;
+    }
+
+    fn show_info_popup(&mut self, info: String) {
+        info!("Info: {info}");
+        self.popup = Some((
+            PopupMessageWidget::new("Info".into(), info),
+            PopupMessageState::default(),
+        ));
+        self.last_mode = self.mode;
+        self.mode = Mode::Popup;
+    }
+
+    fn close_popup(&mut self) {
+        self.popup = None;
+        self.mode = if self.last_mode.is_popup() {
+            Mode::Search
+        } else {
+            self.last_mode
+        }
+    }
+
+    fn update_current_selection_crate_info(&mut self) {
+        self.clear_all_previous_task_details_handles();
+        self.request_crate_details();
+    }
+
+    fn show_full_crate_details(&mut self) {
+        if self.search_results.selected_crate_name().is_none() {
+            return;
+        }
+        self.clear_all_previous_task_details_handles();
         self.request_full_crate_details();
-        // self.mode = Mode::FullCrateDetails;
+        self.full_crate.open(self.mode);
+        self.switch_mode(Mode::FullCrateDetails);
+    }
+
+    /// The name and version of the selected crate.
+    ///
+    /// On the versions tab of the full crate details page this is the
+    /// selected version, and otherwise the latest version.
+    fn selected_crate_version(&self) -> Option<(String, String)> {
+        let version = if self.mode.is_full_crate_details() {
+            let versions = self
+                .crate_response
+                .lock()
+                .unwrap()
+                .clone()
+                .map(|ci| ci.versions);
+            self.full_crate
+                .selected_version()
+                .and_then(|i| versions?.get(i).cloned())
+                .map(|v| (v.crate_name, v.num))
+        } else {
+            None
+        };
+        version.or_else(|| {
+            let selected = self.search_results.selected()?;
+            self.search_results
+                .crates
+                .get(selected)
+                .map(|c| (c.name.clone(), c.max_version.clone()))
+        })
+    }
+
+    /// Shows the dependency tree of the version selected on the versions tab
+    /// of the full crate details page, or of the newest version of the crate
+    /// selected in the picker.
+    fn show_dependency_tree(&mut self) {
+        let Some((name, version)) = self.selected_crate_version() else {
+            return;
+        };
+        let version = match semver::Version::parse(&version) {
+            Ok(version) => version,
+            Err(err) => {
+                let _ = self.tx.send(Action::ShowErrorPopup(format!(
+                    "Unable to parse version `{version}` of `{name}`: {err}"
+                )));
+                return;
+            }
+        };
+        self.dependency_tree = DependencyTree::new((name, version), self.mode);
+        *self.dependency_tree_resolution.lock().unwrap() = None;
+        self.request_dependency_tree();
+        self.switch_mode(Mode::DependencyTree);
+    }
+
+    fn show_add_dependency_form(&mut self) {
+        let Some((name, mut version)) = self.selected_crate_version() else {
+            return;
+        };
+        // suggest a version that works with the toolchain unless one was picked
+        if self.full_crate.selected_version().is_none() || !self.mode.is_full_crate_details() {
+            if let Some(Compatibility::Older(compatible)) =
+                self.search_results.compatibility.get(&name)
+            {
+                version = compatible.to_string();
+            }
+        }
+        let Some(manifest_path) = std::env::current_dir()
+            .ok()
+            .and_then(|dir| project::find_manifest(&dir))
+        else {
+            self.show_error_popup("crates-tui was not started inside a Cargo project".into());
+            return;
+        };
+        let (members, workspace_root) = match *self.project.lock().unwrap() {
+            Some(ref project) => (
+                project
+                    .metadata
+                    .workspace_packages()
+                    .into_iter()
+                    .map(|p| Member {
+                        name: p.name.clone(),
+                        manifest_path: p.manifest_path.clone().into_std_path_buf(),
+                    })
+                    .collect_vec(),
+                Some(project.metadata.workspace_root.clone().into_std_path_buf()),
+            ),
+            // only the nearest manifest is known until the project is loaded
+            None => (
+                vec![Member {
+                    name: manifest_path
+                        .parent()
+                        .and_then(|dir| dir.file_name())
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    manifest_path: manifest_path.clone(),
+                }],
+                None,
+            ),
+        };
+        let nearest = members
+            .iter()
+            .find(|m| m.manifest_path == manifest_path)
+            .map(|m| m.name.clone());
+        let mut form = AddDependencyForm::new(name, version, members, self.mode);
+        if let Some(member) = workspace_root
+            .as_deref()
+            .and_then(project::last_member)
+            .or(nearest)
+        {
+            form.select_member(&member);
+        }
+        if let Some(root) = workspace_root {
+            let workspace_manifest = root.join("Cargo.toml");
+            if let Some(dependencies) = project::workspace_dependencies(&workspace_manifest) {
+                // follow the convention of the workspace if it has one
+                let inherit = !dependencies.is_empty();
+                form.set_workspace(Some(workspace_manifest), inherit);
+            }
+        }
+        self.add_dependency = form;
+        self.switch_mode(Mode::AddDependency);
+    }
+
+    /// Shows the diff of the edited manifests, or writes them if the diff is
+    /// already shown.
+    fn submit_add_dependency(&mut self) {
+        let Some(previews) = self.add_dependency.preview.clone() else {
+            match self.edit_manifests_for_dependency() {
+                Ok(previews) => self.add_dependency.set_preview(Some(previews)),
+                Err(err) => self.show_error_popup(err),
+            }
+            return;
+        };
+        for preview in &previews {
+            if std::fs::read_to_string(&preview.path).ok().as_ref() != Some(&preview.original) {
+                self.add_dependency.set_preview(None);
+                self.show_error_popup(format!(
+                    "`{}` changed since the preview was made, please review the changes again",
+                    preview.path.display()
+                ));
+                return;
+            }
+        }
+        let spec = self.add_dependency.spec();
+        if previews.iter().all(|p| p.updated == p.original) {
+            self.switch_mode(self.add_dependency.return_mode);
+            self.show_info_popup(format!(
+                "`{}` is already in `[{}]` as specified",
+                spec.name,
+                spec.table_name()
+            ));
+            return;
+        }
+        let changed = previews.iter().filter(|p| p.updated != p.original);
+        for (i, preview) in changed.clone().enumerate() {
+            if let Err(err) = std::fs::write(&preview.path, &preview.updated) {
+                // the manifests written so far are restored so that they
+                // aren't left half edited
+                for written in changed.take(i) {
+                    if let Err(err) = std::fs::write(&written.path, &written.original) {
+                        error!("Unable to restore `{}`: {err}", written.path.display());
+                    }
+                }
+                self.show_error_popup(format!(
+                    "Unable to write `{}`: {err}",
+                    preview.path.display()
+                ));
+                return;
+            }
+        }
+        let member = self
+            .add_dependency
+            .member()
+            .map(|m| m.name.clone())
+            .unwrap_or_default();
+        let workspace_root = self
+            .project
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|p| p.metadata.workspace_root.clone().into_std_path_buf());
+        if let Some(root) = workspace_root {
+            if let Err(err) = project::remember_member(&root, &member) {
+                error!("Unable to remember the workspace member: {err}");
+            }
+        }
+        self.switch_mode(self.add_dependency.return_mode);
+        let workspace = if spec.workspace {
+            " and `[workspace.dependencies]`"
+        } else {
+            ""
+        };
+        self.show_info_popup(format!(
+            "Added `{}` to `[{}]` of `{member}`{workspace}",
+            spec.name,
+            spec.table_name(),
+        ));
+    }
+
+    /// Edits the manifests for the dependency in the form, without writing
+    /// them.
+    fn edit_manifests_for_dependency(&self) -> Result<Vec<ManifestPreview>, String> {
+        let form = &self.add_dependency;
+        let spec = form.spec();
+        let member = form
+            .member()
+            .ok_or("There is no workspace member to add the dependency to")?;
+        let mut previews: Vec<ManifestPreview> = vec![];
+        let mut edit = |path: &std::path::Path,
+                        f: &dyn Fn(&str) -> Result<String, String>|
+         -> Result<(), String> {
+            // the workspace root can also be the member
+            match previews.iter_mut().find(|p| p.path == path) {
+                Some(preview) => preview.updated = f(&preview.updated)?,
+                None => {
+                    let original = std::fs::read_to_string(path)
+                        .map_err(|err| format!("Unable to read `{}`: {err}", path.display()))?;
+                    let updated = f(&original)?;
+                    previews.push(ManifestPreview {
+                        path: path.to_path_buf(),
+                        original,
+                        updated,
+                    });
+                }
+            }
+            Ok(())
+        };
+        if let Some(root) = form.workspace_manifest.as_ref().filter(|_| spec.workspace) {
+            edit(root, &|manifest| {
+                project::add_workspace_dependency(manifest, &spec.workspace_entry())
+            })?;
+        }
+        edit(&member.manifest_path, &|manifest| {
+            project::add_dependency(manifest, &spec)
+        })?;
+        Ok(previews)
+    }
+
+    fn open_version_diff_in_browser(&self) -> Result<()> {
+        let Some(dependency) = self.project_dependencies.selected() else {
+            return Ok(());
+        };
+        let name = &dependency.name;
+        let from = dependency
+            .locked
+            .as_ref()
+            .or(dependency.compatible.as_ref());
+        match (from, &dependency.latest) {
+            (Some(from), Some(to)) if from != to => {
+                webbrowser::open(&format!("https://diff.rs/{name}/{from}/{to}"))?
+            }
+            _ => webbrowser::open(&format!("https://crates.io/crates/{name}/versions"))?,
+        }
+        Ok(())
+    }
+
+    /// Changes the requirement of the selected project dependency to the
+    /// latest version, editing the workspace manifest for dependencies
+    /// inherited with `workspace = true`.
+    fn bump_dependency_requirement(&mut self) {
+        let Some(dependency) = self.project_dependencies.selected().cloned() else {
+            return;
+        };
+        let Some(ref latest) = dependency.latest else {
+            self.show_error_popup(format!(
+                "No version of `{}` found in the index",
+                dependency.name
+            ));
+            return;
+        };
+        let workspace_manifest = self.project.lock().unwrap().as_ref().map(|p| {
+            p.metadata
+                .workspace_root
+                .join("Cargo.toml")
+                .into_std_path_buf()
+        });
+        let bump = |path: &std::path::Path, table: &[String]| {
+            let manifest = std::fs::read_to_string(path)
+                .map_err(|err| format!("Unable to read `{}`: {err}", path.display()))?;
+            project::bump_requirement(&manifest, table, &dependency.key, latest)
+        };
+        let result =
+            bump(&dependency.manifest_path, &dependency.table()).and_then(|bumped| {
+                match (bumped, workspace_manifest) {
+                    (Some(bumped), _) => Ok((dependency.manifest_path.clone(), bumped)),
+                    (None, Some(path)) => {
+                        let table = ["workspace".to_string(), "dependencies".to_string()];
+                        match bump(&path, &table)? {
+                            Some(bumped) => Ok((path, bumped)),
+                            None => Err(format!(
+                                "`{}` is not a workspace dependency",
+                                dependency.key
+                            )),
+                        }
+                    }
+                    (None, None) => Err("Project is not loaded".into()),
+                }
+            });
+        let (path, bumped) = match result {
+            Ok(result) => result,
+            Err(err) => {
+                self.show_error_popup(err);
+                return;
+            }
+        };
+        if let Err(err) = std::fs::write(&path, bumped.manifest) {
+            self.show_error_popup(format!("Unable to write `{}`: {err}", path.display()));
+            return;
+        }
+        self.show_info_popup(format!(
+            "Bumped `{}` from `{}` to `{}` in `{}`. Run `cargo update -p {}` to update Cargo.lock.",
+            dependency.key,
+            bumped.old,
+            bumped.new,
+            path.display(),
+            dependency.name
+        ));
+    }
+
+    fn cancel_add_dependency(&mut self) {
+        if self.add_dependency.preview.is_some() {
+            self.add_dependency.set_preview(None);
+        } else {
+            self.switch_mode(self.add_dependency.return_mode);
+        }
+    }
+
+    fn toggle_dependency_tree_selection(&mut self) {
+        if self.dependency_tree.toggle_selected() {
+            self.request_dependency_tree();
+        }
+    }
+
+    fn update_dependency_tree(&mut self) {
+        let resolution = self.dependency_tree_resolution.lock().unwrap().clone();
+        // ignore results of a crate that is no longer shown
+        if resolution.as_ref().and_then(|r| r.root.as_ref()) == self.dependency_tree.root.as_ref() {
+            self.dependency_tree.resolution = resolution;
+        }
+    }
+
+    fn toggle_health_column(&mut self) {
+        self.show_health_column = !self.show_health_column;
+        self.request_search_results_health();
+    }
+
+    fn toggle_hide_incompatible(&mut self) {
+        if self.toolchain.is_none() {
+            self.show_info_popup(
+                "No toolchain to check against. Set `msrv.toolchain` in the config, or run \
+                 crates-tui in a project with a `rust-version`."
+                    .into(),
+            );
+            return;
+        }
+        self.hide_incompatible = !self.hide_incompatible;
+        self.update_search_table_results();
     }
 
     fn store_total_number_of_crates(&mut self, n: u64) {
@@ -676,6 +2192,17 @@ impl App {
         Ok(())
     }
 
+    fn open_locked_package_url_in_browser(&self) -> Result<()> {
+        if let Some(entry) = self.lockfile.selected() {
+            if entry.package.is_from_registry() {
+                let name = &entry.package.name;
+                let version = &entry.package.version;
+                webbrowser::open(&format!("https://crates.io/crates/{name}/{version}"))?;
+            }
+        }
+        Ok(())
+    }
+
     fn copy_cargo_add_command_to_clipboard(&self) -> Result<()> {
         use copypasta::ClipboardProvider;
         match copypasta::ClipboardContext::new() {
@@ -706,6 +2233,57 @@ impl App {
         Ok(())
     }
 
+    /// Spawns an asynchronous task writing the dependency justification
+    /// report of the selected crate, which is then copied to the clipboard or
+    /// written to a file.
+    fn request_justification_report(&self, write: bool) {
+        let Some(name) = self.selected_crate_name() else {
+            return;
+        };
+        let advisories = self
+            .advisory_db
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|db| db.for_crate(&name).to_vec());
+        let tx = self.tx.clone();
+        let loading_status = self.loading_status.clone();
+        tokio::spawn(async move {
+            loading_status.store(true, Ordering::SeqCst);
+            let _ = match crates_io_api_helper::request_justification(&name, advisories).await {
+                Ok(justification) => tx.send(Action::DeliverJustificationReport {
+                    name,
+                    report: justification.to_markdown(),
+                    write,
+                }),
+                Err(error_message) => tx.send(Action::ShowErrorPopup(error_message)),
+            };
+            loading_status.store(false, Ordering::SeqCst);
+        });
+    }
+
+    fn deliver_justification_report(&mut self, name: &str, report: &str, write: bool) {
+        if write {
+            self.export.start_report(
+                &format!("{name}-justification.md"),
+                report.into(),
+                self.mode,
+            );
+            self.switch_mode(Mode::ExportPrompt);
+            return;
+        }
+        use copypasta::ClipboardProvider;
+        match copypasta::ClipboardContext::new() {
+            Ok(mut ctx) => match ctx.set_contents(report.into()) {
+                Ok(()) => self.show_info_popup(format!(
+                    "Copied the justification report of `{name}` to the clipboard"
+                )),
+                Err(err) => self.show_error_popup(format!("Unable to copy to clipboard: {err}")),
+            },
+            Err(err) => self.show_error_popup(format!("Unable to create ClipboardContext: {err}")),
+        }
+    }
+
     fn clear_task_details_handle(&mut self, id: uuid::Uuid) -> Result<()> {
         if let Some((_, handle)) = self.last_task_details_handle.remove_entry(&id) {
             handle.abort()
@@ -735,6 +2313,7 @@ impl App {
     /// Clears current search results and resets the UI to prepare for new data.
     fn prepare_reload(&mut self) {
         self.search_results.select(None);
+        self.pending_selection = None;
         *self.full_crate_info.lock().unwrap() = None;
         *self.crate_response.lock().unwrap() = None;
     }
@@ -762,31 +2341,228 @@ impl App {
                 let _ = params.tx.send(Action::ShowErrorPopup(error_message));
             }
             let _ = params.tx.send(Action::UpdateSearchTableResults);
+            let _ = params.tx.send(Action::RequestSearchResultsHealth);
+            let _ = params.tx.send(Action::RequestSearchResultsLicenses);
+            let _ = params.tx.send(Action::RequestSearchResultsCompatibility);
             params.loading_status.store(false, Ordering::SeqCst);
         });
     }
 
+    /// Spawns an asynchronous task to evaluate the health of every crate in
+    /// the search results, if the health column is shown.
+    fn request_search_results_health(&mut self) {
+        if let Some(handle) = self.health_task.take() {
+            handle.abort();
+        }
+        if !self.show_health_column {
+            return;
+        }
+        let crate_names = self
+            .crates
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|c| c.name.clone())
+            .collect_vec();
+        let health = self.crate_health.clone();
+        let tx = self.tx.clone();
+        self.health_task = Some(tokio::spawn(async move {
+            if let Err(error_message) =
+                crates_io_api_helper::request_crates_health(crate_names, health).await
+            {
+                error!("Unable to evaluate crate health: {error_message}");
+            }
+            let _ = tx.send(Action::UpdateSearchTableResults);
+        }));
+    }
+
+    /// Spawns an asynchronous task to fetch the licenses of the search
+    /// results, if there is a license policy.
+    fn request_search_results_licenses(&mut self) {
+        if let Some(handle) = self.license_task.take() {
+            handle.abort();
+        }
+        let crate_names = self
+            .crates
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|c| c.name.clone())
+            .collect_vec();
+        self.license_task = self.request_licenses(crate_names, Action::UpdateSearchTableResults);
+    }
+
+    /// Spawns an asynchronous task to fetch the licenses of the direct
+    /// dependencies of the project, if there is a license policy.
+    fn request_project_licenses(&mut self) {
+        let crate_names = self
+            .project_dependencies
+            .dependencies
+            .iter()
+            .map(|d| d.name.clone())
+            .unique()
+            .collect_vec();
+        self.request_licenses(crate_names, Action::UpdateProjectDependencies);
+    }
+
+    /// Spawns an asynchronous task to fetch the licenses of the given crates,
+    /// sending `action` when done. Nothing is fetched without a license
+    /// policy.
+    fn request_licenses(&self, crate_names: Vec<String>, action: Action) -> Option<JoinHandle<()>> {
+        if self.license_policy.is_none() || crate_names.is_empty() {
+            return None;
+        }
+        let licenses = self.crate_licenses.clone();
+        let tx = self.tx.clone();
+        Some(tokio::spawn(async move {
+            if let Err(error_message) =
+                crates_io_api_helper::request_crates_licenses(crate_names, licenses).await
+            {
+                error!("Unable to fetch crate licenses: {error_message}");
+            }
+            let _ = tx.send(action);
+        }))
+    }
+
+    /// Spawns an asynchronous task to fetch the index entries of the search
+    /// results, which are needed to check them against the toolchain.
+    fn request_search_results_compatibility(&mut self) {
+        if let Some(handle) = self.compatibility_task.take() {
+            handle.abort();
+        }
+        if self.toolchain.is_none() {
+            return;
+        }
+        let crate_names = self
+            .crates
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|c| c.name.clone())
+            .collect_vec();
+        self.compatibility_task =
+            Some(self.request_index_entries(crate_names, Action::UpdateSearchTableResults));
+    }
+
+    /// Spawns an asynchronous task to fetch the index entries of the direct
+    /// dependencies of the project.
+    fn request_project_index_entries(&mut self) {
+        let crate_names = self
+            .project_dependencies
+            .dependencies
+            .iter()
+            .map(|d| d.name.clone())
+            .unique()
+            .collect_vec();
+        if !crate_names.is_empty() {
+            self.request_index_entries(crate_names, Action::UpdateProjectDependencies);
+        }
+    }
+
+    /// Spawns an asynchronous task to fetch the index entries of every locked
+    /// crates.io package.
+    fn request_lockfile_index_entries(&mut self) {
+        let crate_names = self
+            .lockfile
+            .entries
+            .iter()
+            .filter(|e| e.package.is_from_registry())
+            .map(|e| e.package.name.clone())
+            .unique()
+            .collect_vec();
+        if !crate_names.is_empty() {
+            self.request_index_entries(crate_names, Action::UpdateLockfileEntries);
+        }
+    }
+
+    /// Spawns an asynchronous task to fetch the index entries of the given
+    /// crates that are not cached yet, sending `action` when done.
+    fn request_index_entries(&self, crate_names: Vec<String>, action: Action) -> JoinHandle<()> {
+        let index_entries = self.index_entries.clone();
+        let crate_names = {
+            let cached = index_entries.lock().unwrap();
+            crate_names
+                .into_iter()
+                .filter(|name| !cached.contains_key(name))
+                .collect_vec()
+        };
+        let tx = self.tx.clone();
+        let loading_status = self.loading_status.clone();
+        tokio::spawn(async move {
+            loading_status.store(true, Ordering::SeqCst);
+            match crates_index_helper::request_index_entries(crate_names).await {
+                Ok((entries, failures)) => {
+                    index_entries.lock().unwrap().extend(entries);
+                    if !failures.is_empty() {
+                        let _ = tx.send(Action::ShowErrorPopup(format!(
+                            "Unable to fetch the index entries of {} crates:\n{}",
+                            failures.len(),
+                            failures.join("\n")
+                        )));
+                    }
+                }
+                Err(error_message) => {
+                    let _ = tx.send(Action::ShowErrorPopup(format!(
+                        "Unable to fetch index entries: {error_message}"
+                    )));
+                }
+            }
+            loading_status.store(false, Ordering::SeqCst);
+            let _ = tx.send(action);
+        })
+    }
+
     /// Spawns an asynchronous task to fetch crate details from crates.io based
     /// on currently selected crate
     fn request_crate_details(&mut self) {
-        if self.search_results.crates.is_empty() {
-            return;
-        }
-        if let Some(crate_name) = self.search_results.selected_crate_name() {
+        if let Some(crate_name) = self.selected_crate_name() {
             let tx = self.tx.clone();
             let crate_response = self.crate_response.clone();
+            let crate_health = self.crate_health.clone();
+            let crate_licenses = self.crate_licenses.clone();
             let loading_status = self.loading_status.clone();
+            // the health is only evaluated when it is shown
+            let evaluate_health = self.show_health_column || self.mode.shows_crate_info_panel();
+            // crates the cursor merely passes over aren't recently viewed
+            let record_view = self.mode.shows_crate_info_panel();
 
             // Spawn the async work to fetch crate details.
             let uuid = uuid::Uuid::new_v4();
             let last_task_details_handle = tokio::spawn(async move {
                 info!("Requesting details for {crate_name}: {uuid}");
                 loading_status.store(true, Ordering::SeqCst);
-                if let Err(error_message) =
-                    crates_io_api_helper::request_crate_details(&crate_name, crate_response).await
+                // after a failed fetch the response still holds the details of
+                // the previously selected crate
+                let details = match crates_io_api_helper::request_crate_details(
+                    &crate_name,
+                    crate_response.clone(),
+                )
+                .await
                 {
-                    let _ = tx.send(Action::ShowErrorPopup(error_message));
+                    Ok(()) => {
+                        if record_view {
+                            let _ = tx.send(Action::RecordRecentlyViewed(crate_name.clone()));
+                        }
+                        crate_response.lock().unwrap().clone()
+                    }
+                    Err(error_message) => {
+                        let _ = tx.send(Action::ShowErrorPopup(error_message));
+                        None
+                    }
                 };
+                if let Some(details) = details {
+                    crate_licenses.lock().unwrap().insert(
+                        details.crate_data.name.clone(),
+                        license::versions_licenses(&details),
+                    );
+                    if evaluate_health && !crate_health.lock().unwrap().contains_key(&crate_name) {
+                        if let Err(error_message) =
+                            crates_io_api_helper::request_crate_health(details, crate_health).await
+                        {
+                            error!("Unable to evaluate health of {crate_name}: {error_message}");
+                        }
+                    }
+                }
                 loading_status.store(false, Ordering::SeqCst);
                 info!("Retrieved details for {crate_name}: {uuid}");
                 let _ = tx.send(Action::ClearTaskDetailsHandle(uuid.to_string()));
@@ -804,6 +2580,7 @@ impl App {
         }
         if let Some(crate_name) = self.search_results.selected_crate_name() {
             let tx = self.tx.clone();
+            let crate_response = self.crate_response.clone();
             let full_crate_info = self.full_crate_info.clone();
             let loading_status = self.loading_status.clone();
 
@@ -812,11 +2589,19 @@ impl App {
             let last_task_details_handle = tokio::spawn(async move {
                 info!("Requesting details for {crate_name}: {uuid}");
                 loading_status.store(true, Ordering::SeqCst);
-                if let Err(error_message) =
-                    crates_io_api_helper::request_full_crate_details(&crate_name, full_crate_info)
-                        .await
+                match crates_io_api_helper::request_full_crate_details(
+                    &crate_name,
+                    crate_response,
+                    full_crate_info,
+                )
+                .await
                 {
-                    let _ = tx.send(Action::ShowErrorPopup(error_message));
+                    Ok(()) => {
+                        let _ = tx.send(Action::RecordRecentlyViewed(crate_name.clone()));
+                    }
+                    Err(error_message) => {
+                        let _ = tx.send(Action::ShowErrorPopup(error_message));
+                    }
                 };
                 loading_status.store(false, Ordering::SeqCst);
                 info!("Retrieved details for {crate_name}: {uuid}");
@@ -827,6 +2612,130 @@ impl App {
         }
     }
 
+    /// Spawns an asynchronous task to resolve the dependency tree with the
+    /// currently enabled features.
+    fn request_dependency_tree(&mut self) {
+        if let Some(handle) = self.dependency_tree_task.take() {
+            // an aborted task never clears the loading status itself
+            if !handle.is_finished() {
+                handle.abort();
+                self.loading_status.store(false, Ordering::SeqCst);
+            }
+        }
+        let Some(root) = self.dependency_tree.root.clone() else {
+            return;
+        };
+        let features = self.dependency_tree.features.clone();
+        let default_features = self.dependency_tree.default_features;
+        let tx = self.tx.clone();
+        let loading_status = self.loading_status.clone();
+        let index_entries = self.index_entries.clone();
+        let resolution = self.dependency_tree_resolution.clone();
+        self.dependency_tree_task = Some(tokio::spawn(async move {
+            loading_status.store(true, Ordering::SeqCst);
+            if let Err(error_message) = crates_index_helper::request_dependency_tree(
+                root,
+                features,
+                default_features,
+                index_entries,
+                resolution,
+            )
+            .await
+            {
+                let _ = tx.send(Action::ShowErrorPopup(error_message));
+            }
+            loading_status.store(false, Ordering::SeqCst);
+            let _ = tx.send(Action::UpdateDependencyTree);
+        }));
+    }
+
+    /// Spawns an asynchronous task to load the Cargo workspace of the current
+    /// directory.
+    fn request_project(&mut self) {
+        if self.project_task.as_ref().is_some_and(|t| !t.is_finished()) {
+            return;
+        }
+        let Some(manifest_path) = std::env::current_dir()
+            .ok()
+            .and_then(|dir| project::find_manifest(&dir))
+        else {
+            return;
+        };
+        let project = self.project.clone();
+        let tx = self.tx.clone();
+        self.project_task = Some(tokio::spawn(async move {
+            if let Err(error_message) = project::request_project(manifest_path, project).await {
+                let _ = tx.send(Action::ShowErrorPopup(error_message));
+            }
+            let _ = tx.send(Action::UpdateProject);
+        }));
+    }
+
+    /// Spawns an asynchronous task to load the resolved dependency graph of
+    /// the workspace.
+    fn request_workspace_graph(&mut self) {
+        if self
+            .workspace_graph_task
+            .as_ref()
+            .is_some_and(|t| !t.is_finished())
+        {
+            return;
+        }
+        let Some(project) = self.project.lock().unwrap().clone() else {
+            return;
+        };
+        let graph = self.workspace_graph.clone();
+        let tx = self.tx.clone();
+        let loading_status = self.loading_status.clone();
+        self.workspace_graph_task = Some(tokio::spawn(async move {
+            loading_status.store(true, Ordering::SeqCst);
+            if let Err(error_message) =
+                workspace_graph::request_workspace_graph(project, graph).await
+            {
+                let _ = tx.send(Action::ShowErrorPopup(error_message));
+            }
+            loading_status.store(false, Ordering::SeqCst);
+            let _ = tx.send(Action::UpdateWorkspaceGraph);
+        }));
+    }
+
+    /// Spawns an asynchronous task to load the advisory database, if one is
+    /// configured.
+    fn request_advisory_db(&self) {
+        let Some(ref path) = config::get().advisories.db_path else {
+            return;
+        };
+        let path = path.into();
+        let tx = self.tx.clone();
+        let advisory_db = self.advisory_db.clone();
+        tokio::spawn(async move {
+            if let Err(error_message) = advisory::request_advisory_db(path, advisory_db).await {
+                let _ = tx.send(Action::ShowErrorPopup(error_message));
+            }
+            let _ = tx.send(Action::UpdateProjectDependencies);
+        });
+    }
+
+    /// Spawns an asynchronous task to fetch the state of the given watched
+    /// crates.
+    fn request_watchlist(&mut self, crate_names: Vec<String>) {
+        if crate_names.is_empty() {
+            return;
+        }
+        self.watchlist.loading = true;
+        let tx = self.tx.clone();
+        let snapshots = self.watchlist_snapshots.clone();
+        let failures = self.watchlist_failures.clone();
+        tokio::spawn(async move {
+            if let Err(error_message) =
+                crates_io_api_helper::request_watchlist(crate_names, snapshots, failures).await
+            {
+                error!("Unable to check the watchlist: {error_message}");
+            }
+            let _ = tx.send(Action::UpdateWatchlist);
+        });
+    }
+
     fn request_summary(&self) -> Result<()> {
         let tx = self.tx.clone();
         let loading_status = self.loading_status.clone();
@@ -850,7 +2759,20 @@ impl App {
 
     // Sets cursor for the prompt
     fn update_cursor(&mut self, frame: &mut Frame<'_>) {
-        if let Some(cursor_position) = self.prompt.cursor_position() {
+        let cursor_position = if self.mode.is_add_dependency() {
+            self.add_dependency.cursor_position()
+        } else if self.mode.is_favorites_prompt() {
+            self.favorites.cursor_position()
+        } else if self.mode.is_search_history() {
+            self.search_history.cursor_position()
+        } else if self.mode.is_annotation_prompt() {
+            self.annotations.cursor_position()
+        } else if self.mode.is_export_prompt() {
+            self.export.cursor_position()
+        } else {
+            self.prompt.cursor_position()
+        };
+        if let Some(cursor_position) = cursor_position {
             frame.set_cursor(cursor_position.x, cursor_position.y)
         }
     }
@@ -858,11 +2780,81 @@ impl App {
     fn render_crate_info(&mut self, area: Rect, buf: &mut Buffer) {
         if let Some(ci) = self.crate_response.lock().unwrap().clone() {
             Clear.render(area, buf);
-            CrateInfoTableWidget::new(ci).render(area, buf, &mut self.crate_info);
+            let health = self
+                .crate_health
+                .lock()
+                .unwrap()
+                .get(&ci.crate_data.name)
+                .cloned();
+            let compatibility = self.toolchain.clone().zip(
+                self.search_results
+                    .compatibility
+                    .get(&ci.crate_data.name)
+                    .cloned(),
+            );
+            let license_verdict = self.license_policy.as_ref().map(|policy| {
+                let license = ci
+                    .versions
+                    .iter()
+                    .find(|v| v.num == ci.crate_data.max_version)
+                    .and_then(|v| v.license.as_deref());
+                policy.evaluate(license)
+            });
+            let advisories = self
+                .advisory_db
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|db| db.for_crate(&ci.crate_data.name).to_vec());
+            let annotation = self
+                .annotations
+                .annotations
+                .get(&ci.crate_data.name)
+                .cloned();
+            CrateInfoTableWidget::new(ci, health)
+                .compatibility(compatibility)
+                .license_verdict(license_verdict)
+                .advisories(advisories)
+                .annotation(annotation)
+                .render(area, buf, &mut self.crate_info);
         }
     }
 
-    fn events_widget(&self) -> Option<Block> {
+    fn render_full_crate_info(&mut self, area: Rect, buf: &mut Buffer) {
+        let full_crate = self.full_crate_info.lock().unwrap().clone();
+        let versions = self
+            .crate_response
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|ci| ci.versions.clone())
+            .unwrap_or_default();
+        match full_crate {
+            Some(full_crate) => {
+                let advisories = self
+                    .advisory_db
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .map(|db| db.for_crate(&full_crate.name).to_vec())
+                    .unwrap_or_default();
+                FullCrateInfoWidget::new(full_crate, versions)
+                    .advisories(advisories)
+                    .render(area, buf, &mut self.full_crate)
+            }
+            None => Line::styled("Loading crate details...", config::get().color.base03)
+                .centered()
+                .render(
+                    area.inner(&Margin {
+                        horizontal: 0,
+                        vertical: area.height / 2,
+                    }),
+                    buf,
+                ),
+        }
+    }
+
+    fn events_widget(&self) -> Option<Block<'_>> {
         if self.last_tick_key_events.is_empty() {
             return None;
         }
@@ -911,11 +2903,9 @@ impl App {
     }
 
     fn render_search_results(&mut self, area: Rect, buf: &mut Buffer) {
-        SearchResultsTableWidget::new(self.mode.is_picker()).render(
-            area,
-            buf,
-            &mut self.search_results,
-        );
+        SearchResultsTableWidget::new(self.mode.is_picker())
+            .show_health(self.show_health_column)
+            .render(area, buf, &mut self.search_results);
 
         Line::from(self.page_number_status()).left_aligned().render(
             area.inner(&Margin {
@@ -969,8 +2959,78 @@ impl App {
                 self.render_crate_info(info, buf);
             }
             Mode::PickerHideCrateInfo => self.render_search_results(area, buf),
+            Mode::FullCrateDetails => self.render_full_crate_info(area, buf),
+            Mode::DependencyTree => {
+                DependencyTreeWidget::new(self.loading()).render(
+                    area,
+                    buf,
+                    &mut self.dependency_tree,
+                );
+            }
+            Mode::FeatureUnification => {
+                FeatureUnificationWidget.render(area, buf, &mut self.feature_unification);
+            }
+            Mode::InverseDependencies => {
+                InverseDependenciesWidget.render(area, buf, &mut self.inverse_dependencies);
+            }
+            Mode::Project => {
+                let has_project = self.project.lock().unwrap().is_some();
+                ProjectDependenciesWidget::new(has_project).render(
+                    area,
+                    buf,
+                    &mut self.project_dependencies,
+                );
+            }
+            Mode::Lockfile => {
+                let has_project = self.project.lock().unwrap().is_some();
+                LockfileExplorerWidget::new(has_project).render(area, buf, &mut self.lockfile);
+            }
+            Mode::Watchlist => {
+                let [area, info] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Max(15)]).areas(area);
+                WatchlistWidget.render(area, buf, &mut self.watchlist);
+                if self.watchlist.selected().is_some() {
+                    self.render_crate_info(info, buf);
+                }
+            }
+            Mode::RecentlyViewed => {
+                let [area, info] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Max(15)]).areas(area);
+                RecentlyViewedWidget.render(area, buf, &mut self.recently_viewed);
+                if self.recently_viewed.selected().is_some() {
+                    self.render_crate_info(info, buf);
+                }
+            }
+            Mode::Favorites | Mode::FavoritesPrompt => {
+                let [area, info] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Max(15)]).areas(area);
+                FavoritesWidget.render(area, buf, &mut self.favorites);
+                if self.favorites.selected().is_some() {
+                    self.render_crate_info(info, buf);
+                }
+            }
+            Mode::AnnotationPrompt => {
+                self.render_main(area, buf, self.annotations.return_mode);
+                AnnotationEditorWidget.render(area, buf, &mut self.annotations);
+            }
+            Mode::ExportPrompt => {
+                self.render_main(area, buf, self.export.return_mode);
+                ExportPromptWidget.render(area, buf, &mut self.export);
+            }
+            Mode::SavedSearches => {
+                self.render_main(area, buf, self.saved_searches.return_mode);
+                SavedSearchesWidget.render(area, buf, &mut self.saved_searches);
+            }
+            Mode::AddDependency => {
+                self.render_main(area, buf, self.add_dependency.return_mode);
+                AddDependencyFormWidget.render(area, buf, &mut self.add_dependency);
+            }
             Mode::Common => self.render_search_results(area, buf),
             Mode::Search => self.render_search_results(area, buf),
+            Mode::SearchHistory => {
+                self.render_search_results(area, buf);
+                SearchHistoryWidget.render(area, buf, &mut self.search_history);
+            }
             Mode::Filter => self.render_search_results(area, buf),
             Mode::Popup => self.render_main(area, buf, self.last_mode),
             Mode::Quit => self.render_main(area, buf, self.last_mode),
@@ -1,10 +1,17 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
 use serde::Serialize;
 use serde_with::{serde_as, skip_serializing_none, NoneAsEmptyString};
 use tracing::level_filters::LevelFilter;
 
+use crate::{
+    export::{ExportFormat, ExportSource},
+    pick::PickOutput,
+    serde_helper::sort::SortDef,
+    subcommands::OutputFormat,
+};
+
 const VERSION_MESSAGE: &str = concat!(
     env!("CARGO_PKG_VERSION"),
     "-",
@@ -58,6 +65,10 @@ pub struct Cli {
     #[arg(long, value_name = "DIR")]
     pub data_dir: Option<PathBuf>,
 
+    /// Start without restoring the session crates-tui was last quit in.
+    #[arg(long)]
+    pub fresh: bool,
+
     /// The log level to use.
     ///
     /// Valid values are: error, warn, info, debug, trace, off. The default is
@@ -65,6 +76,118 @@ pub struct Cli {
     #[arg(long, value_name = "LEVEL", alias = "log")]
     #[serde_as(as = "NoneAsEmptyString")]
     pub log_level: Option<LevelFilter>,
+
+    #[command(subcommand)]
+    #[serde(skip)]
+    pub command: Option<Commands>,
+}
+
+/// Commands that run without starting the TUI.
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    /// Export search results, favorites or the locked packages of the current
+    /// project to a CSV, JSON or Markdown file.
+    Export(ExportArgs),
+    /// Search crates.io and print a page of results.
+    Search(SearchArgs),
+    /// Print the details, versions, dependencies, features and owners of a
+    /// crate.
+    Info(InfoArgs),
+    /// Pick a crate from search results shown below the prompt and print it,
+    /// exiting with status 1 when cancelled.
+    Pick(PickArgs),
+}
+
+/// Which page of search results is fetched, and how they are sorted.
+#[derive(Debug, Args)]
+pub struct PageArgs {
+    /// How search results are sorted: alphabetical, relevance, downloads,
+    /// recent_downloads, recent_updates or newly_added.
+    #[arg(long, value_parser = parse_sort, default_value = "relevance")]
+    pub sort: crates_io_api::Sort,
+
+    /// The page of search results.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    pub page: u64,
+
+    /// The number of search results per page.
+    #[arg(long, default_value_t = 25, value_parser = clap::value_parser!(u64).range(1..=100))]
+    pub per_page: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// What to export.
+    #[arg(value_enum)]
+    pub source: ExportSource,
+
+    /// The file to write, whose extension picks the format unless `--format`
+    /// is given.
+    #[arg(short, long, value_name = "FILE")]
+    pub output: PathBuf,
+
+    /// The format to write.
+    #[arg(long, value_enum)]
+    pub format: Option<ExportFormat>,
+
+    /// The search query, when exporting search results.
+    #[arg(short, long, default_value = "")]
+    pub query: String,
+
+    #[command(flatten)]
+    pub page: PageArgs,
+
+    /// The favorites collection to export, every one if omitted.
+    #[arg(long)]
+    pub collection: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct SearchArgs {
+    /// The search query.
+    pub query: String,
+
+    #[command(flatten)]
+    pub page: PageArgs,
+
+    /// How the results are printed.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    pub format: OutputFormat,
+}
+
+#[derive(Debug, Args)]
+pub struct InfoArgs {
+    /// The crate, as `name` for its newest version or `name@version`.
+    #[arg(value_name = "CRATE")]
+    pub spec: String,
+
+    /// Print JSON instead of human-readable text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct PickArgs {
+    /// The search query to start with.
+    #[arg(short, long, default_value = "")]
+    pub query: String,
+
+    #[command(flatten)]
+    pub page: PageArgs,
+
+    /// What is printed for the picked crate.
+    #[arg(long, value_enum, default_value_t = PickOutput::Name)]
+    pub print: PickOutput,
+
+    /// The number of terminal lines the picker takes up.
+    #[arg(long, default_value_t = 15, value_parser = clap::value_parser!(u16).range(3..))]
+    pub height: u16,
+}
+
+/// Parses the snake case name of a sort, as written in the configuration.
+pub fn parse_sort(sort: &str) -> Result<crates_io_api::Sort, String> {
+    SortDef::deserialize(serde::de::value::StrDeserializer::<serde::de::value::Error>::new(sort))
+        .map_err(|err| err.to_string())
 }
 
 pub fn parse() -> Cli {
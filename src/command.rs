@@ -16,6 +16,8 @@ pub enum Command {
     DecrementPage,
     NextSummaryMode,
     PreviousSummaryMode,
+    NextFullCrateInfoTab,
+    PreviousFullCrateInfoTab,
     ToggleSortBy { reload: bool, forward: bool },
     ScrollBottom,
     ScrollTop,
@@ -31,4 +33,61 @@ pub enum Command {
     CopyCargoAddCommandToClipboard,
     OpenDocsUrlInBrowser,
     OpenCratesIOUrlInBrowser,
+    ShowFullCrateInfo,
+    CloseFullCrateInfo,
+    ShowDependencyTree,
+    CloseDependencyTree,
+    ToggleDependencyTreeFocus,
+    ToggleDependencyTreeSelection,
+    ToggleHealthColumn,
+    ToggleHideIncompatible,
+    AddDependency,
+    SubmitAddDependency,
+    CancelAddDependency,
+    OpenVersionDiffInBrowser,
+    BumpDependencyRequirement,
+    ShowLockfile,
+    ToggleDuplicatesOnly,
+    ShowInverseDependencies,
+    CloseInverseDependencies,
+    ShowFeatureUnification,
+    CloseFeatureUnification,
+    ToggleFavorite,
+    NextCollection,
+    PreviousCollection,
+    MoveFavoriteUp,
+    MoveFavoriteDown,
+    EditFavoriteNote,
+    NewCollection,
+    RenameCollection,
+    DeleteCollection,
+    SubmitFavoritesPrompt,
+    CancelFavoritesPrompt,
+    PreviousSearchQuery,
+    NextSearchQuery,
+    ShowSearchHistory,
+    SelectSearchHistory,
+    CloseSearchHistory,
+    ClearSearchHistory,
+    ShowRecentlyViewed,
+    CloseRecentlyViewed,
+    ToggleWatch,
+    ShowWatchlist,
+    CloseWatchlist,
+    AcknowledgeWatched,
+    AcknowledgeAllWatched,
+    CheckWatchlist,
+    EditCrateNote,
+    EditCrateTags,
+    SubmitAnnotationPrompt,
+    CancelAnnotationPrompt,
+    ShowSavedSearches,
+    CloseSavedSearches,
+    SelectSavedSearch,
+    RunSavedSearch(String),
+    Export,
+    SubmitExportPrompt,
+    CancelExportPrompt,
+    CopyJustificationReport,
+    WriteJustificationReport,
 }
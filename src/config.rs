@@ -11,7 +11,10 @@ use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr, NoneAsEmptyString};
 use tracing::level_filters::LevelFilter;
 
-use crate::{cli::Cli, serde_helper::keybindings::KeyBindings};
+use crate::{
+    cli::Cli,
+    serde_helper::{keybindings::KeyBindings, sort::SortDef},
+};
 
 static CONFIG: OnceLock<Config> = OnceLock::new();
 pub const CONFIG_DEFAULT: &str = include_str!("../.config/config.default.toml");
@@ -107,6 +110,150 @@ impl Default for Base16Palette {
     }
 }
 
+/// Thresholds for the crate health scorecard.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HealthConfig {
+    /// The latest release must be at most this many days old.
+    pub max_days_since_release: i64,
+
+    /// The crate must have at least this many owners.
+    pub min_owners: usize,
+
+    /// The `rust-version` of the latest release must not be newer than this.
+    pub max_rust_version: String,
+
+    /// The crate must have at least this many reverse dependencies.
+    pub min_reverse_dependencies: u64,
+
+    /// Show the health score as a column in the search results.
+    pub show_in_search_results: bool,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            max_days_since_release: 365,
+            min_owners: 2,
+            max_rust_version: "1.70".into(),
+            min_reverse_dependencies: 5,
+            show_in_search_results: false,
+        }
+    }
+}
+
+/// Settings for checking crates against a Rust toolchain.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MsrvConfig {
+    /// The Rust version to check crates against, e.g. `1.70`. When empty, the
+    /// `rust-version` of the project in the current directory is used.
+    #[serde_as(as = "NoneAsEmptyString")]
+    pub toolchain: Option<String>,
+
+    /// Fall back to the `rust-version` of the current project when no
+    /// toolchain is configured.
+    pub use_project_rust_version: bool,
+
+    /// Hide search results that have no version compatible with the
+    /// toolchain.
+    pub hide_incompatible: bool,
+}
+
+impl Default for MsrvConfig {
+    fn default() -> Self {
+        Self {
+            toolchain: None,
+            use_project_rust_version: true,
+            hide_incompatible: false,
+        }
+    }
+}
+
+/// Settings for flagging crates with security advisories.
+#[serde_as]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AdvisoriesConfig {
+    /// A local clone of the RustSec advisory database, see
+    /// <https://github.com/rustsec/advisory-db>. Advisories are not checked
+    /// when empty.
+    #[serde_as(as = "NoneAsEmptyString")]
+    pub db_path: Option<String>,
+}
+
+/// Licenses crates are checked against.
+#[serde_as]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LicensePolicyConfig {
+    /// Licenses that are accepted, e.g. `MIT` or
+    /// `Apache-2.0 WITH LLVM-exception`. When empty, every license that is
+    /// not denied is accepted.
+    pub allow: Vec<String>,
+
+    /// Licenses that are never accepted.
+    pub deny: Vec<String>,
+
+    /// The `deny.toml` whose `[licenses]` section is used when no license is
+    /// allowed or denied above. When empty, it is looked up from the current
+    /// directory upwards.
+    #[serde_as(as = "NoneAsEmptyString")]
+    pub deny_file: Option<String>,
+}
+
+/// Settings for the history of submitted searches.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SearchHistoryConfig {
+    /// The number of searches that are remembered, the oldest are dropped
+    /// first.
+    pub max_entries: usize,
+}
+
+impl Default for SearchHistoryConfig {
+    fn default() -> Self {
+        Self { max_entries: 100 }
+    }
+}
+
+/// A named search that can be run from the saved searches picker or bound
+/// to a key with `{ RunSavedSearch = "<name>" }`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SavedSearch {
+    pub name: String,
+    #[serde(default)]
+    pub query: String,
+    #[serde(with = "SortDef", default = "default_saved_search_sort")]
+    pub sort: crates_io_api::Sort,
+    /// Words the results are filtered with, as typed in the filter prompt.
+    #[serde(default)]
+    pub filter: String,
+    /// The number of crates per page, up to 100.
+    #[serde(default)]
+    pub page_size: Option<u64>,
+}
+
+fn default_saved_search_sort() -> crates_io_api::Sort {
+    crates_io_api::Sort::Relevance
+}
+
+/// Settings for the list of recently viewed crates.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RecentlyViewedConfig {
+    /// The number of crates that are remembered, the least recently viewed
+    /// are dropped first.
+    pub max_entries: usize,
+}
+
+impl Default for RecentlyViewedConfig {
+    fn default() -> Self {
+        Self { max_entries: 50 }
+    }
+}
+
+/// Policies crates are checked against.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PolicyConfig {
+    pub licenses: LicensePolicyConfig,
+}
+
 /// Application configuration.
 ///
 /// This is the main configuration struct for the application.
@@ -144,6 +291,20 @@ pub struct Config {
     pub key_bindings: KeyBindings,
 
     pub color: Base16Palette,
+
+    pub health: HealthConfig,
+
+    pub msrv: MsrvConfig,
+
+    pub policy: PolicyConfig,
+
+    pub advisories: AdvisoriesConfig,
+
+    pub search_history: SearchHistoryConfig,
+
+    pub recently_viewed: RecentlyViewedConfig,
+
+    pub saved_searches: Vec<SavedSearch>,
 }
 
 impl Default for Config {
@@ -164,6 +325,13 @@ impl Default for Config {
             prompt_padding: 1,
             key_bindings,
             color: rose_pine,
+            health: HealthConfig::default(),
+            msrv: MsrvConfig::default(),
+            policy: PolicyConfig::default(),
+            advisories: AdvisoriesConfig::default(),
+            search_history: SearchHistoryConfig::default(),
+            recently_viewed: RecentlyViewedConfig::default(),
+            saved_searches: Vec::new(),
         }
     }
 }
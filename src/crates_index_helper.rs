@@ -0,0 +1,179 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use futures::{stream, StreamExt};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::resolver::{self, NodeId, Resolution, ResolveError};
+
+/// Number of index files fetched concurrently.
+///
+/// The sparse index is served from a CDN and is not subject to the crates.io
+/// API crawler policy, so it is fine to fetch a handful of files at once.
+const CONCURRENT_REQUESTS: usize = 8;
+
+/// A single published version of a crate as recorded in the crates.io index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexVersion {
+    pub name: String,
+    pub vers: semver::Version,
+    pub deps: Vec<IndexDependency>,
+    pub features: BTreeMap<String, Vec<String>>,
+    /// Features using the `dep:` or `?` syntax are stored separately so that
+    /// older versions of cargo don't choke on them.
+    #[serde(default)]
+    pub features2: Option<BTreeMap<String, Vec<String>>>,
+    pub yanked: bool,
+    #[serde(default)]
+    pub rust_version: Option<String>,
+}
+
+impl IndexVersion {
+    /// All features of this version, including the ones from `features2`.
+    pub fn all_features(&self) -> BTreeMap<String, Vec<String>> {
+        let mut features = self.features.clone();
+        for (name, values) in self.features2.clone().unwrap_or_default() {
+            features.entry(name).or_default().extend(values);
+        }
+        features
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyKind {
+    #[default]
+    Normal,
+    Build,
+    Dev,
+}
+
+/// A dependency of an [`IndexVersion`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexDependency {
+    /// The name used to refer to the dependency, which differs from the crate
+    /// name when the dependency is renamed.
+    pub name: String,
+    pub req: semver::VersionReq,
+    pub features: Vec<String>,
+    pub optional: bool,
+    pub default_features: bool,
+    pub target: Option<String>,
+    #[serde(default)]
+    pub kind: DependencyKind,
+    /// The actual crate name if the dependency is renamed.
+    #[serde(default)]
+    pub package: Option<String>,
+}
+
+impl IndexDependency {
+    /// The name of the crate this dependency refers to.
+    pub fn crate_name(&self) -> &str {
+        self.package.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Returns the path of the index file for a crate, e.g. `se/rd/serde`.
+fn index_path(crate_name: &str) -> String {
+    let name = crate_name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{name}"),
+        2 => format!("2/{name}"),
+        3 => format!("3/{}/{name}", &name[..1]),
+        _ => format!("{}/{}/{name}", &name[..2], &name[2..4]),
+    }
+}
+
+fn create_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .user_agent("crates-tui (crates-tui@kdheepak.com)")
+        .build()
+        .map_err(|err| format!("Index Client Error: {err:#?}"))
+}
+
+/// Fetches every published version of a crate from the sparse index.
+async fn fetch_versions(
+    client: &reqwest::Client,
+    crate_name: &str,
+) -> Result<Vec<IndexVersion>, String> {
+    let url = format!("https://index.crates.io/{}", index_path(crate_name));
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| format!("Error fetching index entry for `{crate_name}`: {err}"))?;
+    let body = response
+        .text()
+        .await
+        .map_err(|err| format!("Error fetching index entry for `{crate_name}`: {err}"))?;
+    Ok(body
+        .lines()
+        .filter_map(|line| {
+            serde_json::from_str(line)
+                .inspect_err(|err| warn!("Skipping index entry of `{crate_name}`: {err}"))
+                .ok()
+        })
+        .collect())
+}
+
+/// Fetches the index entries for all the given crates.
+///
+/// A crate that fails is skipped, and the entries of the others are returned
+/// along with the failures.
+pub async fn request_index_entries(
+    crate_names: Vec<String>,
+) -> Result<(HashMap<String, Vec<IndexVersion>>, Vec<String>), String> {
+    let client = create_client()?;
+    let results: Vec<_> = stream::iter(crate_names)
+        .map(|name| {
+            let client = &client;
+            async move { fetch_versions(client, &name).await.map(|v| (name, v)) }
+        })
+        .buffer_unordered(CONCURRENT_REQUESTS)
+        .collect()
+        .await;
+    let (entries, failures) = results.into_iter().partition_result();
+    Ok((entries, failures))
+}
+
+/// Resolves the dependency tree of a crate version, fetching index entries as
+/// they are needed.
+///
+/// Fetched entries are kept in `index` so that re-resolving with different
+/// features doesn't hit the network again.
+pub async fn request_dependency_tree(
+    root: NodeId,
+    features: BTreeSet<String>,
+    default_features: bool,
+    index: Arc<Mutex<HashMap<String, Vec<IndexVersion>>>>,
+    resolution: Arc<Mutex<Option<Resolution>>>,
+) -> Result<(), String> {
+    loop {
+        let result = resolver::resolve(&index.lock().unwrap(), &root, &features, default_features);
+        match result {
+            Ok(result) => {
+                *resolution.lock().unwrap() = Some(result);
+                return Ok(());
+            }
+            Err(ResolveError::MissingIndexEntries(crate_names)) => {
+                let (entries, failures) = request_index_entries(crate_names).await?;
+                index.lock().unwrap().extend(entries);
+                if !failures.is_empty() {
+                    return Err(failures.join("\n"));
+                }
+            }
+            Err(ResolveError::VersionNotFound(name, version)) => {
+                return Err(format!(
+                    "Version `{version}` of `{name}` was not found in the index"
+                ))
+            }
+        }
+    }
+}
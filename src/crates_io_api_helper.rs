@@ -1,9 +1,21 @@
-use std::sync::{atomic::AtomicBool, Arc, Mutex};
+use std::{
+    collections::HashMap,
+    sync::{atomic::AtomicBool, Arc, Mutex},
+};
 
 use crates_io_api::CratesQuery;
+use serde::Deserialize;
 use tokio::sync::mpsc::UnboundedSender;
 
-use crate::action::Action;
+use crate::{
+    action::Action,
+    advisory::Advisory,
+    config,
+    health::{HealthData, HealthReport},
+    license::{self, LicenseCache},
+    report::Justification,
+    watchlist::CrateSnapshot,
+};
 use color_eyre::Result;
 
 /// Represents the parameters needed for fetching crates asynchronously.
@@ -29,6 +41,25 @@ pub async fn request_search_results(params: &SearchParameters) -> Result<(), Str
     Ok(())
 }
 
+/// Fetches a page of search results along with the total number of crates
+/// matching the query, for use outside the TUI.
+pub async fn search_crates(
+    search: &str,
+    page: u64,
+    page_size: u64,
+    sort: crates_io_api::Sort,
+) -> Result<(Vec<crates_io_api::Crate>, u64), String> {
+    let client = create_client()?;
+    let query = crates_io_api::CratesQueryBuilder::default()
+        .search(search)
+        .page(page)
+        .page_size(page_size)
+        .sort(sort)
+        .build();
+    let (crates, _, total) = fetch_crates_and_metadata(client, query).await?;
+    Ok((crates, total))
+}
+
 /// Helper function to create client and fetch crates, wrapping both actions
 /// into a result pattern.
 fn create_client() -> Result<crates_io_api::AsyncClient, String> {
@@ -110,22 +141,312 @@ pub async fn request_crate_details(
     Ok(())
 }
 
-// Performs the async fetch of crate details.
+/// Performs the async fetch of everything shown on the full crate details
+/// page.
+///
+/// This deliberately does not use `AsyncClient::full_crate`, which pages
+/// through every reverse dependency of a crate and takes minutes for popular
+/// crates. Only the first page of reverse dependencies is fetched, which is
+/// enough to know the total count. The `CrateResponse` fetched along the way is
+/// stored as well because it is the only place that lists every version.
 pub async fn request_full_crate_details(
     crate_name: &str,
+    crate_info: Arc<Mutex<Option<crates_io_api::CrateResponse>>>,
     full_crate_info: Arc<Mutex<Option<crates_io_api::FullCrate>>>,
 ) -> Result<(), String> {
     let client = create_client()?;
 
-    let full_crate_data = client
-        .full_crate(crate_name, false)
+    let crate_data = client
+        .get_crate(crate_name)
         .await
         .map_err(|err| format!("Error fetching crate details: {err:#?}"))?;
+    let latest = crate_data
+        .versions
+        .first()
+        .cloned()
+        .ok_or_else(|| format!("Crate `{crate_name}` has no published versions"))?;
+
+    let (dependencies, downloads, owners, reverse_dependencies) = tokio::try_join!(
+        client.crate_dependencies(crate_name, &latest.num),
+        client.crate_downloads(crate_name),
+        client.crate_owners(crate_name),
+        client.crate_reverse_dependencies_page(crate_name, 1),
+    )
+    .map_err(|err| format!("Error fetching crate details: {err:#?}"))?;
+
+    let full_crate_data = crates_io_api::FullCrate {
+        id: crate_data.crate_data.id.clone(),
+        name: crate_data.crate_data.name.clone(),
+        description: crate_data.crate_data.description.clone(),
+        license: latest.license.clone(),
+        documentation: crate_data.crate_data.documentation.clone(),
+        homepage: crate_data.crate_data.homepage.clone(),
+        repository: crate_data.crate_data.repository.clone(),
+        total_downloads: crate_data.crate_data.downloads,
+        recent_downloads: crate_data.crate_data.recent_downloads,
+        max_version: crate_data.crate_data.max_version.clone(),
+        max_stable_version: crate_data.crate_data.max_stable_version.clone(),
+        created_at: crate_data.crate_data.created_at,
+        updated_at: crate_data.crate_data.updated_at,
+        categories: crate_data.categories.clone(),
+        keywords: crate_data.keywords.clone(),
+        downloads,
+        owners,
+        reverse_dependencies,
+        versions: vec![crates_io_api::FullVersion {
+            created_at: latest.created_at,
+            updated_at: latest.updated_at,
+            dl_path: latest.dl_path,
+            downloads: latest.downloads,
+            features: latest.features,
+            id: latest.id,
+            num: latest.num,
+            yanked: latest.yanked,
+            license: latest.license,
+            readme_path: latest.readme_path,
+            links: latest.links,
+            author_names: vec![],
+            dependencies,
+        }],
+    };
 
+    *crate_info.lock().unwrap() = Some(crate_data);
     *full_crate_info.lock().unwrap() = Some(full_crate_data);
     Ok(())
 }
 
+/// Fetches whether the documentation of a crate version was built on docs.rs.
+///
+/// Returns `None` if the build status could not be fetched.
+async fn request_docs_status(crate_name: &str, version: &str) -> Option<bool> {
+    #[derive(Deserialize)]
+    struct DocsStatus {
+        doc_status: bool,
+    }
+    let client = reqwest::Client::builder()
+        .user_agent("crates-tui (crates-tui@kdheepak.com)")
+        .build()
+        .ok()?;
+    let body = client
+        .get(format!(
+            "https://docs.rs/crate/{crate_name}/{version}/status.json"
+        ))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .ok()?
+        .text()
+        .await
+        .ok()?;
+    serde_json::from_str::<DocsStatus>(&body)
+        .ok()
+        .map(|status| status.doc_status)
+}
+
+/// Fetches the remaining data needed to evaluate the health of a crate.
+async fn evaluate_health(
+    client: &crates_io_api::AsyncClient,
+    crate_response: &crates_io_api::CrateResponse,
+) -> Result<HealthReport, String> {
+    let name = &crate_response.crate_data.name;
+    let (owners, reverse_dependencies) = tokio::try_join!(
+        client.crate_owners(name),
+        client.crate_reverse_dependency_count(name),
+    )
+    .map_err(|err| format!("Error fetching crate health: {err:#?}"))?;
+    let docs_built = request_docs_status(name, &crate_response.crate_data.max_version).await;
+    let data = HealthData::new(
+        crate_response,
+        owners.len(),
+        reverse_dependencies,
+        docs_built,
+    );
+    Ok(HealthReport::evaluate(
+        &data,
+        &config::get().health,
+        chrono::Utc::now(),
+    ))
+}
+
+/// A version of a crate with everything `crates-tui info` prints about it.
+pub struct CrateOverview {
+    pub crate_response: crates_io_api::CrateResponse,
+    pub version: crates_io_api::Version,
+    pub owners: Vec<crates_io_api::User>,
+    pub dependencies: Vec<crates_io_api::Dependency>,
+    pub health: HealthReport,
+}
+
+/// Fetches a version of a crate, the newest one if `version` is `None`, with
+/// its owners, dependencies and health, for use outside the TUI.
+pub async fn request_crate_overview(
+    crate_name: &str,
+    version: Option<&str>,
+) -> Result<CrateOverview, String> {
+    let client = create_client()?;
+    let crate_response = client
+        .get_crate(crate_name)
+        .await
+        .map_err(|err| format!("Error fetching crate details: {err:#?}"))?;
+    let num = version.unwrap_or(&crate_response.crate_data.max_version);
+    let version = crate_response
+        .versions
+        .iter()
+        .find(|v| v.num == num)
+        .cloned()
+        .ok_or_else(|| format!("`{crate_name}` has no version `{num}`"))?;
+    let (owners, dependencies, reverse_dependencies) = tokio::try_join!(
+        client.crate_owners(crate_name),
+        client.crate_dependencies(crate_name, &version.num),
+        client.crate_reverse_dependency_count(crate_name),
+    )
+    .map_err(|err| format!("Error fetching crate details: {err:#?}"))?;
+    let docs_built = request_docs_status(crate_name, &crate_response.crate_data.max_version).await;
+    let data = HealthData::new(
+        &crate_response,
+        owners.len(),
+        reverse_dependencies,
+        docs_built,
+    );
+    let health = HealthReport::evaluate(&data, &config::get().health, chrono::Utc::now());
+    Ok(CrateOverview {
+        crate_response,
+        version,
+        owners,
+        dependencies,
+        health,
+    })
+}
+
+/// Evaluates the health of a crate whose details were already fetched.
+pub async fn request_crate_health(
+    crate_response: crates_io_api::CrateResponse,
+    health: Arc<Mutex<HashMap<String, HealthReport>>>,
+) -> Result<(), String> {
+    let client = create_client()?;
+    let report = evaluate_health(&client, &crate_response).await?;
+    health
+        .lock()
+        .unwrap()
+        .insert(crate_response.crate_data.name, report);
+    Ok(())
+}
+
+/// Evaluates the health of each crate in turn, skipping the ones that were
+/// already evaluated.
+///
+/// This takes a few seconds per crate because of the crates.io rate limit, so
+/// results are stored as soon as they are available. A crate that fails is
+/// skipped, and the failures are returned once every crate was tried.
+pub async fn request_crates_health(
+    crate_names: Vec<String>,
+    health: Arc<Mutex<HashMap<String, HealthReport>>>,
+) -> Result<(), String> {
+    let client = create_client()?;
+    let mut failures = vec![];
+    for name in crate_names {
+        if health.lock().unwrap().contains_key(&name) {
+            continue;
+        }
+        let report = match client.get_crate(&name).await {
+            Ok(crate_response) => evaluate_health(&client, &crate_response).await,
+            Err(err) => Err(format!("Error fetching crate details: {err:#?}")),
+        };
+        match report {
+            Ok(report) => {
+                health.lock().unwrap().insert(name, report);
+            }
+            Err(err) => failures.push(format!("`{name}`: {err}")),
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+/// Fetches the license of every version of each crate in turn, skipping the
+/// ones that were already fetched.
+pub async fn request_crates_licenses(
+    crate_names: Vec<String>,
+    licenses: Arc<Mutex<LicenseCache>>,
+) -> Result<(), String> {
+    let client = create_client()?;
+    for name in crate_names {
+        if licenses.lock().unwrap().contains_key(&name) {
+            continue;
+        }
+        let crate_response = client
+            .get_crate(&name)
+            .await
+            .map_err(|err| format!("Error fetching crate details: {err:#?}"))?;
+        licenses
+            .lock()
+            .unwrap()
+            .insert(name, license::versions_licenses(&crate_response));
+    }
+    Ok(())
+}
+
+/// Fetches the versions and owners of each watched crate in turn.
+///
+/// Results are stored as soon as they are available, replacing the ones of
+/// a previous check. A crate that fails is recorded in `failures` and
+/// skipped, and the failures are returned once every crate was tried.
+pub async fn request_watchlist(
+    crate_names: Vec<String>,
+    snapshots: Arc<Mutex<HashMap<String, CrateSnapshot>>>,
+    failures: Arc<Mutex<HashMap<String, String>>>,
+) -> Result<(), String> {
+    let client = create_client()?;
+    let mut errors = vec![];
+    for name in crate_names {
+        match tokio::try_join!(client.get_crate(&name), client.crate_owners(&name)) {
+            Ok((crate_response, owners)) => {
+                failures.lock().unwrap().remove(&name);
+                snapshots
+                    .lock()
+                    .unwrap()
+                    .insert(name, CrateSnapshot::new(&crate_response, &owners));
+            }
+            Err(err) => {
+                errors.push(format!("Error fetching watched crate `{name}`: {err:#?}"));
+                failures.lock().unwrap().insert(name, err.to_string());
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+/// Fetches what the dependency justification report of the newest version of
+/// a crate is written from.
+pub async fn request_justification(
+    crate_name: &str,
+    advisories: Option<Vec<Advisory>>,
+) -> Result<Justification, String> {
+    let client = create_client()?;
+    let (crate_response, owners) = tokio::try_join!(
+        client.get_crate(crate_name),
+        client.crate_owners(crate_name)
+    )
+    .map_err(|err| format!("Error fetching crate details: {err:#?}"))?;
+    let dependencies = client
+        .crate_dependencies(crate_name, &crate_response.crate_data.max_version)
+        .await
+        .map_err(|err| format!("Error fetching crate dependencies: {err:#?}"))?;
+    Ok(Justification::new(
+        &crate_response,
+        &owners,
+        &dependencies,
+        advisories,
+    ))
+}
+
 pub async fn request_summary(
     summary: Arc<Mutex<Option<crates_io_api::Summary>>>,
 ) -> Result<(), String> {
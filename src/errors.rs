@@ -41,7 +41,7 @@ fn install_better_panic() {
         .install()
 }
 
-#[allow(dead_code)]
+#[allow(dead_code, deprecated)]
 fn install_human_panic() {
     human_panic::setup_panic!(Metadata {
         name: env!("CARGO_PKG_NAME").into(),
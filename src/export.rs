@@ -0,0 +1,309 @@
+use std::{collections::HashMap, path::Path};
+
+use color_eyre::eyre::{eyre, Result};
+use itertools::Itertools;
+use serde_json::json;
+
+use crate::{
+    cli::ExportArgs, crates_index_helper, crates_io_api_helper, favorites::Collection, project,
+    project::LockfileEntry,
+};
+
+/// A file format views are exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    /// The format of a file, from its extension.
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") => Ok(Self::Csv),
+            Some("json") => Ok(Self::Json),
+            Some("md" | "markdown") => Ok(Self::Markdown),
+            _ => Err(format!(
+                "Unable to tell the format of `{}`, use a .csv, .json or .md file",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// What is exported from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportSource {
+    /// A page of search results.
+    Search,
+    /// The favorites, from every collection or the one given by `--collection`.
+    Favorites,
+    /// The packages locked in the project of the current directory.
+    Lockfile,
+}
+
+/// The rows of a view, with the full data of each row for JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Export {
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+    json: serde_json::Value,
+}
+
+impl Export {
+    pub fn search_results(crates: &[crates_io_api::Crate]) -> Self {
+        Self {
+            headers: vec![
+                "Name",
+                "Version",
+                "Description",
+                "Downloads",
+                "Recent Downloads",
+                "Updated",
+                "Repository",
+            ],
+            rows: crates
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.name.clone(),
+                        c.max_version.clone(),
+                        c.description.clone().unwrap_or_default(),
+                        c.downloads.to_string(),
+                        c.recent_downloads
+                            .map(|d| d.to_string())
+                            .unwrap_or_default(),
+                        c.updated_at.format("%Y-%m-%d").to_string(),
+                        c.repository.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect(),
+            json: json!(crates),
+        }
+    }
+
+    pub fn favorites(collections: &[&Collection]) -> Self {
+        Self {
+            headers: vec!["Collection", "Name", "Note"],
+            rows: collections
+                .iter()
+                .flat_map(|collection| {
+                    collection
+                        .crates
+                        .iter()
+                        .map(|c| vec![collection.name.clone(), c.name.clone(), c.note.clone()])
+                })
+                .collect(),
+            json: json!(collections),
+        }
+    }
+
+    pub fn lockfile(entries: &[&LockfileEntry]) -> Self {
+        let optional = |v: Option<String>| v.unwrap_or_default();
+        Self {
+            headers: vec![
+                "Name",
+                "Version",
+                "Source",
+                "Latest",
+                "Rust Version",
+                "Yanked",
+                "Parents",
+            ],
+            rows: entries
+                .iter()
+                .map(|e| {
+                    vec![
+                        e.package.name.clone(),
+                        e.package.version.to_string(),
+                        optional(e.package.source.clone()),
+                        optional(e.latest.as_ref().map(ToString::to_string)),
+                        optional(e.rust_version.clone()),
+                        optional(e.yanked.map(|y| y.to_string())),
+                        e.parents.join(", "),
+                    ]
+                })
+                .collect(),
+            json: entries
+                .iter()
+                .map(|e| {
+                    json!({
+                        "name": e.package.name,
+                        "version": e.package.version,
+                        "source": e.package.source,
+                        "latest": e.latest,
+                        "rust_version": e.rust_version,
+                        "yanked": e.yanked,
+                        "locked_versions": e.versions,
+                        "parents": e.parents,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Csv => {
+                std::iter::once(self.headers.iter().map(|h| csv_field(h)).join(","))
+                    .chain(
+                        self.rows
+                            .iter()
+                            .map(|row| row.iter().map(|c| csv_field(c)).join(",")),
+                    )
+                    .map(|line| line + "\n")
+                    .collect()
+            }
+            ExportFormat::Json => {
+                serde_json::to_string_pretty(&self.json).unwrap_or_default() + "\n"
+            }
+            ExportFormat::Markdown => {
+                let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+                std::iter::once(line(self.headers.iter().map(|h| h.to_string()).collect()))
+                    .chain(std::iter::once(line(
+                        self.headers.iter().map(|_| "---".to_string()).collect(),
+                    )))
+                    .chain(
+                        self.rows
+                            .iter()
+                            .map(|row| line(row.iter().map(|c| markdown_cell(c)).collect())),
+                    )
+                    .collect()
+            }
+        }
+    }
+
+    /// Writes the rows to `path`, in `format` or the one its extension
+    /// stands for.
+    pub fn write(&self, path: &Path, format: Option<ExportFormat>) -> Result<(), String> {
+        let format = match format {
+            Some(format) => format,
+            None => ExportFormat::from_path(path)?,
+        };
+        std::fs::write(path, self.render(format))
+            .map_err(|err| format!("Unable to write `{}`: {err}", path.display()))
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.into()
+    }
+}
+
+/// Escapes pipes and joins lines so that the cell doesn't break the table.
+fn markdown_cell(cell: &str) -> String {
+    cell.split_whitespace().join(" ").replace('|', "\\|")
+}
+
+/// Runs `crates-tui export`, writing the rows without starting the TUI.
+pub async fn run(args: &ExportArgs) -> Result<()> {
+    let format = match args.format {
+        Some(format) => format,
+        None => ExportFormat::from_path(&args.output).map_err(|err| eyre!(err))?,
+    };
+    let export = match args.source {
+        ExportSource::Search => {
+            let (crates, _) = crates_io_api_helper::search_crates(
+                &args.query,
+                args.page.page,
+                args.page.per_page,
+                args.page.sort.clone(),
+            )
+            .await
+            .map_err(|err| eyre!(err))?;
+            Export::search_results(&crates)
+        }
+        ExportSource::Favorites => {
+            let favorites = crate::favorites::Favorites::load().map_err(|err| eyre!(err))?;
+            let collections = favorites
+                .collections
+                .iter()
+                .filter(|c| args.collection.as_ref().is_none_or(|name| c.name == *name))
+                .collect_vec();
+            if collections.is_empty() {
+                return Err(eyre!(
+                    "There is no collection named `{}`",
+                    args.collection.clone().unwrap_or_default()
+                ));
+            }
+            Export::favorites(&collections)
+        }
+        ExportSource::Lockfile => {
+            let manifest_path = std::env::current_dir()
+                .ok()
+                .and_then(|dir| project::find_manifest(&dir))
+                .ok_or_else(|| eyre!("crates-tui was not started inside a Cargo project"))?;
+            let project = project::Project::load(&manifest_path).map_err(|err| eyre!(err))?;
+            let names = project
+                .lockfile
+                .packages
+                .iter()
+                .filter(|p| p.is_from_registry())
+                .map(|p| p.name.clone())
+                .unique()
+                .collect();
+            let (index, failures): (HashMap<_, _>, _) =
+                crates_index_helper::request_index_entries(names)
+                    .await
+                    .map_err(|err| eyre!(err))?;
+            for failure in failures {
+                eprintln!("{failure}");
+            }
+            let entries = project.lockfile.entries(&index);
+            Export::lockfile(&entries.iter().collect_vec())
+        }
+    };
+    export
+        .write(&args.output, Some(format))
+        .map_err(|err| eyre!(err))?;
+    eprintln!(
+        "Exported {} rows to {}",
+        export.len(),
+        args.output.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::favorites::FavoriteCrate;
+
+    #[test]
+    fn renders_rows() {
+        let collection = Collection {
+            name: "cli stack".into(),
+            crates: vec![FavoriteCrate {
+                name: "clap".into(),
+                note: "derive, \"builder\" | both".into(),
+            }],
+        };
+        let export = Export::favorites(&[&collection]);
+        assert_eq!(
+            export.render(ExportFormat::Csv),
+            "Collection,Name,Note\ncli stack,clap,\"derive, \"\"builder\"\" | both\"\n"
+        );
+        assert_eq!(
+            export.render(ExportFormat::Markdown),
+            "| Collection | Name | Note |\n| --- | --- | --- |\n\
+             | cli stack | clap | derive, \"builder\" \\| both |\n"
+        );
+        assert!(export
+            .render(ExportFormat::Json)
+            .contains("\"name\": \"clap\""));
+        assert_eq!(
+            ExportFormat::from_path(Path::new("crates.md")),
+            Ok(ExportFormat::Markdown)
+        );
+        assert!(ExportFormat::from_path(Path::new("crates.txt")).is_err());
+    }
+}
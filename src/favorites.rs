@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const FAVORITES_FILE: &str = "favorites.toml";
+
+/// The collection bookmarks go into until other collections are created.
+const DEFAULT_COLLECTION: &str = "Favorites";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FavoriteCrate {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+/// A named, ordered list of crates, e.g. "web stack".
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collection {
+    pub name: String,
+    #[serde(default)]
+    pub crates: Vec<FavoriteCrate>,
+}
+
+impl Collection {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            crates: vec![],
+        }
+    }
+
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.crates.iter().position(|c| c.name == name)
+    }
+}
+
+/// Bookmarked crates, persisted in the data directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Favorites {
+    #[serde(default)]
+    pub collections: Vec<Collection>,
+}
+
+impl Default for Favorites {
+    fn default() -> Self {
+        Self {
+            collections: vec![Collection::new(DEFAULT_COLLECTION)],
+        }
+    }
+}
+
+impl Favorites {
+    pub fn load() -> Result<Self, String> {
+        let favorites: Self = storage::load(FAVORITES_FILE)?;
+        if favorites.collections.is_empty() {
+            Ok(Self::default())
+        } else {
+            Ok(favorites)
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save(FAVORITES_FILE, self)
+    }
+
+    /// Whether the crate is in any collection.
+    pub fn contains(&self, name: &str) -> bool {
+        self.collections.iter().any(|c| c.position(name).is_some())
+    }
+
+    /// Adds the crate to the end of a collection, or removes it if it is
+    /// already there. Returns whether it was added.
+    pub fn toggle(&mut self, collection: usize, name: &str) -> bool {
+        let collection = &mut self.collections[collection];
+        match collection.position(name) {
+            Some(i) => {
+                collection.crates.remove(i);
+                false
+            }
+            None => {
+                collection.crates.push(FavoriteCrate {
+                    name: name.into(),
+                    note: String::new(),
+                });
+                true
+            }
+        }
+    }
+
+    fn check_name(&self, name: &str) -> Result<(), String> {
+        if name.is_empty() {
+            Err("The collection name can't be empty".into())
+        } else if self.collections.iter().any(|c| c.name == name) {
+            Err(format!("There already is a collection named `{name}`"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Adds an empty collection, returning its index.
+    pub fn add_collection(&mut self, name: &str) -> Result<usize, String> {
+        let name = name.trim();
+        self.check_name(name)?;
+        self.collections.push(Collection::new(name));
+        Ok(self.collections.len() - 1)
+    }
+
+    pub fn rename_collection(&mut self, collection: usize, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if self.collections[collection].name != name {
+            self.check_name(name)?;
+            self.collections[collection].name = name.into();
+        }
+        Ok(())
+    }
+
+    /// Removes a collection, which must be empty so that no bookmarks are
+    /// lost by accident. There is always at least one collection.
+    pub fn remove_collection(&mut self, collection: usize) -> Result<(), String> {
+        let removed = &self.collections[collection];
+        if !removed.crates.is_empty() {
+            return Err(format!(
+                "Remove the crates of `{}` before deleting it",
+                removed.name
+            ));
+        }
+        if self.collections.len() == 1 {
+            return Err("The last collection can't be deleted".into());
+        }
+        self.collections.remove(collection);
+        Ok(())
+    }
+
+    /// Moves a crate up (`-1`) or down (`1`) in its collection, returning its
+    /// new index.
+    pub fn move_crate(&mut self, collection: usize, index: usize, offset: isize) -> Option<usize> {
+        let crates = &mut self.collections[collection].crates;
+        let target = index.checked_add_signed(offset)?;
+        if target >= crates.len() || index >= crates.len() {
+            return None;
+        }
+        crates.swap(index, target);
+        Some(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggles_and_reorders_crates() {
+        let mut favorites = Favorites::default();
+        assert!(favorites.toggle(0, "serde"));
+        assert!(favorites.toggle(0, "tokio"));
+        assert!(favorites.toggle(0, "axum"));
+        assert_eq!(favorites.move_crate(0, 2, -1), Some(1));
+        assert_eq!(favorites.move_crate(0, 0, -1), None);
+        assert_eq!(favorites.move_crate(0, 2, 1), None);
+        let names = |f: &Favorites| {
+            f.collections[0]
+                .crates
+                .iter()
+                .map(|c| c.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&favorites), ["serde", "axum", "tokio"]);
+        assert!(!favorites.toggle(0, "axum"));
+        assert_eq!(names(&favorites), ["serde", "tokio"]);
+        assert!(favorites.contains("tokio"));
+        assert!(!favorites.contains("axum"));
+    }
+
+    #[test]
+    fn manages_collections() {
+        let mut favorites = Favorites::default();
+        assert_eq!(favorites.add_collection(" web stack "), Ok(1));
+        assert!(favorites.add_collection("web stack").is_err());
+        assert!(favorites.add_collection("").is_err());
+        assert!(favorites.rename_collection(1, DEFAULT_COLLECTION).is_err());
+        assert_eq!(favorites.rename_collection(1, "cli stack"), Ok(()));
+        favorites.toggle(1, "clap");
+        assert!(favorites.remove_collection(1).is_err());
+        favorites.toggle(1, "clap");
+        assert_eq!(favorites.remove_collection(1), Ok(()));
+        assert!(favorites.remove_collection(0).is_err());
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let mut favorites = Favorites::default();
+        favorites.toggle(0, "serde");
+        favorites.collections[0].crates[0].note = "derive everything".into();
+        favorites.add_collection("empty").unwrap();
+        let toml = toml::to_string(&favorites).unwrap();
+        assert_eq!(toml::from_str::<Favorites>(&toml).unwrap(), favorites);
+    }
+}
@@ -0,0 +1,204 @@
+use chrono::{DateTime, Utc};
+use strum::Display;
+
+use crate::{config::HealthConfig, rust_version};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum HealthCheckKind {
+    #[strum(to_string = "Recent release")]
+    RecentRelease,
+    #[strum(to_string = "Multiple owners")]
+    MultipleOwners,
+    #[strum(to_string = "Repository")]
+    Repository,
+    #[strum(to_string = "Docs build")]
+    DocsBuild,
+    #[strum(to_string = "Not yanked")]
+    NotYanked,
+    #[strum(to_string = "Reasonable MSRV")]
+    RustVersion,
+    #[strum(to_string = "Reverse dependencies")]
+    ReverseDependencies,
+}
+
+#[derive(Debug, Clone)]
+pub struct HealthCheck {
+    pub kind: HealthCheckKind,
+    /// `None` if there was not enough information to run the check.
+    pub passed: Option<bool>,
+    pub detail: String,
+}
+
+/// Everything needed to evaluate the health of a crate, gathered from several
+/// endpoints.
+#[derive(Debug, Clone)]
+pub struct HealthData {
+    pub latest_release: DateTime<Utc>,
+    pub latest_version: String,
+    pub yanked: bool,
+    pub rust_version: Option<String>,
+    pub repository: Option<String>,
+    pub owners: usize,
+    pub reverse_dependencies: u64,
+    pub docs_built: Option<bool>,
+}
+
+impl HealthData {
+    pub fn new(
+        crate_response: &crates_io_api::CrateResponse,
+        owners: usize,
+        reverse_dependencies: u64,
+        docs_built: Option<bool>,
+    ) -> Self {
+        let max_version = &crate_response.crate_data.max_version;
+        let latest = crate_response
+            .versions
+            .iter()
+            .find(|v| v.num == *max_version)
+            .or(crate_response.versions.first());
+        Self {
+            latest_release: latest
+                .map(|v| v.created_at)
+                .unwrap_or(crate_response.crate_data.updated_at),
+            latest_version: max_version.clone(),
+            yanked: latest.is_some_and(|v| v.yanked),
+            rust_version: latest.and_then(|v| v.rust_version.clone()),
+            repository: crate_response.crate_data.repository.clone(),
+            owners,
+            reverse_dependencies,
+            docs_built,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HealthReport {
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    pub fn evaluate(data: &HealthData, config: &HealthConfig, now: DateTime<Utc>) -> Self {
+        let days = (now - data.latest_release).num_days();
+        let max_rust_version = rust_version::parse(&config.max_rust_version);
+        let rust_version = data.rust_version.as_deref().and_then(rust_version::parse);
+        let checks = vec![
+            HealthCheck {
+                kind: HealthCheckKind::RecentRelease,
+                passed: Some(days <= config.max_days_since_release),
+                detail: format!("last release {days} days ago"),
+            },
+            HealthCheck {
+                kind: HealthCheckKind::MultipleOwners,
+                passed: Some(data.owners >= config.min_owners),
+                detail: format!("{} owner(s)", data.owners),
+            },
+            HealthCheck {
+                kind: HealthCheckKind::Repository,
+                passed: Some(data.repository.is_some()),
+                detail: data
+                    .repository
+                    .clone()
+                    .unwrap_or_else(|| "no repository".into()),
+            },
+            HealthCheck {
+                kind: HealthCheckKind::DocsBuild,
+                passed: data.docs_built,
+                detail: match data.docs_built {
+                    Some(true) => "docs.rs build succeeded".into(),
+                    Some(false) => "docs.rs build failed".into(),
+                    None => "docs.rs build status unknown".into(),
+                },
+            },
+            HealthCheck {
+                kind: HealthCheckKind::NotYanked,
+                passed: Some(!data.yanked),
+                detail: if data.yanked {
+                    format!("v{} is yanked", data.latest_version)
+                } else {
+                    format!("v{} is not yanked", data.latest_version)
+                },
+            },
+            HealthCheck {
+                kind: HealthCheckKind::RustVersion,
+                passed: rust_version
+                    .as_ref()
+                    .zip(max_rust_version)
+                    .map(|(v, max)| *v <= max),
+                detail: match data.rust_version {
+                    Some(ref v) => {
+                        format!("rust-version {v} (at most {})", config.max_rust_version)
+                    }
+                    None => "no rust-version declared".into(),
+                },
+            },
+            HealthCheck {
+                kind: HealthCheckKind::ReverseDependencies,
+                passed: Some(data.reverse_dependencies >= config.min_reverse_dependencies),
+                detail: format!("{} reverse dependencies", data.reverse_dependencies),
+            },
+        ];
+        Self { checks }
+    }
+
+    /// Percentage of the checks that could be run and passed.
+    pub fn score(&self) -> u8 {
+        let (passed, total) = self
+            .checks
+            .iter()
+            .filter_map(|c| c.passed)
+            .fold((0, 0), |(passed, total), p| {
+                (passed + p as usize, total + 1)
+            });
+        (passed * 100).checked_div(total).unwrap_or_default() as u8
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &HealthCheck> {
+        self.checks.iter().filter(|c| c.passed == Some(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn data(now: DateTime<Utc>) -> HealthData {
+        HealthData {
+            latest_release: now - Duration::days(30),
+            latest_version: "1.0.0".into(),
+            yanked: false,
+            rust_version: Some("1.60".into()),
+            repository: Some("https://github.com/ratatui-org/crates-tui".into()),
+            owners: 2,
+            reverse_dependencies: 10,
+            docs_built: Some(true),
+        }
+    }
+
+    #[test]
+    fn healthy_crate_passes_every_check() {
+        let now = Utc::now();
+        let report = HealthReport::evaluate(&data(now), &HealthConfig::default(), now);
+        assert_eq!(report.score(), 100);
+        assert_eq!(report.failed().count(), 0);
+    }
+
+    #[test]
+    fn unknown_checks_are_not_counted() {
+        let now = Utc::now();
+        let data = HealthData {
+            owners: 1,
+            docs_built: None,
+            rust_version: None,
+            ..data(now)
+        };
+        let report = HealthReport::evaluate(&data, &HealthConfig::default(), now);
+        assert_eq!(report.score(), 80);
+        assert_eq!(
+            report.failed().map(|c| c.kind).collect_vec(),
+            vec![HealthCheckKind::MultipleOwners]
+        );
+    }
+}
@@ -0,0 +1,421 @@
+use std::{collections::HashMap, fmt, path::Path};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::LicensePolicyConfig;
+
+/// Licenses of every version of a crate, keyed by crate name and then by
+/// version.
+pub type LicenseCache = HashMap<String, HashMap<String, Option<String>>>;
+
+/// Returns the license of each version of a crate.
+pub fn versions_licenses(
+    crate_response: &crates_io_api::CrateResponse,
+) -> HashMap<String, Option<String>> {
+    crate_response
+        .versions
+        .iter()
+        .map(|v| (v.num.clone(), v.license.clone()))
+        .collect()
+}
+
+/// A single license of an SPDX expression, e.g. `GPL-2.0+` or
+/// `Apache-2.0 WITH LLVM-exception`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseReq {
+    /// The license identifier without `+`, `-only` or `-or-later`, which
+    /// are not told apart when matching the policy.
+    id: String,
+    exception: Option<String>,
+    /// The requirement as it was written.
+    text: String,
+}
+
+impl LicenseReq {
+    fn new(id: &str, exception: Option<&str>) -> Self {
+        let base = ["+", "-or-later", "-only"]
+            .iter()
+            .find_map(|suffix| id.strip_suffix(suffix))
+            .unwrap_or(id);
+        let text = match exception {
+            Some(exception) => format!("{id} WITH {exception}"),
+            None => id.to_string(),
+        };
+        Self {
+            id: base.to_string(),
+            exception: exception.map(String::from),
+            text,
+        }
+    }
+
+    /// Parses a policy entry, which must be a single license.
+    pub fn parse(entry: &str) -> Result<Self, String> {
+        match LicenseExpr::parse(entry)? {
+            LicenseExpr::License(req) => Ok(req),
+            _ => Err(format!("`{entry}` is not a single license")),
+        }
+    }
+
+    /// Whether this policy entry covers `license`. An entry without an
+    /// exception covers the license with any exception.
+    fn covers(&self, license: &LicenseReq) -> bool {
+        self.id.eq_ignore_ascii_case(&license.id)
+            && self.exception.as_ref().is_none_or(|exception| {
+                license
+                    .exception
+                    .as_ref()
+                    .is_some_and(|e| e.eq_ignore_ascii_case(exception))
+            })
+    }
+}
+
+impl fmt::Display for LicenseReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// A parsed SPDX license expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseExpr {
+    License(LicenseReq),
+    And(Vec<LicenseExpr>),
+    Or(Vec<LicenseExpr>),
+}
+
+impl LicenseExpr {
+    /// Parses an SPDX expression. The `/` separator of older crates is read
+    /// as `OR`.
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let tokens = expression
+            .replace('(', " ( ")
+            .replace(')', " ) ")
+            .replace('/', " OR ");
+        let mut parser = Parser {
+            tokens: tokens.split_whitespace().peekable(),
+        };
+        let expr = parser
+            .or()
+            .map_err(|err| format!("Invalid license `{expression}`: {err}"))?;
+        match parser.tokens.next() {
+            Some(token) => Err(format!(
+                "Invalid license `{expression}`: unexpected `{token}`"
+            )),
+            None => Ok(expr),
+        }
+    }
+
+    /// Checks the expression against a policy, explaining which clause failed
+    /// when it is not satisfied.
+    fn evaluate(&self, policy: &LicensePolicy) -> Result<(), String> {
+        match self {
+            Self::License(req) => policy.check(req),
+            Self::And(exprs) => {
+                let failures = exprs
+                    .iter()
+                    .filter_map(|e| e.evaluate(policy).err())
+                    .collect_vec();
+                if failures.is_empty() {
+                    Ok(())
+                } else {
+                    Err(failures.join("; "))
+                }
+            }
+            Self::Or(exprs) => {
+                let mut failures = vec![];
+                for expr in exprs {
+                    match expr.evaluate(policy) {
+                        Ok(()) => return Ok(()),
+                        Err(failure) => failures.push(failure),
+                    }
+                }
+                Err(format!(
+                    "none of `{self}` is allowed: {}",
+                    failures.join("; ")
+                ))
+            }
+        }
+    }
+}
+
+impl fmt::Display for LicenseExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::License(req) => req.fmt(f),
+            Self::And(exprs) => {
+                let exprs = exprs
+                    .iter()
+                    .map(|e| match e {
+                        Self::Or(_) => format!("({e})"),
+                        _ => e.to_string(),
+                    })
+                    .join(" AND ");
+                f.write_str(&exprs)
+            }
+            Self::Or(exprs) => f.write_str(&exprs.iter().join(" OR ")),
+        }
+    }
+}
+
+/// A recursive descent parser where `AND` binds tighter than `OR`.
+struct Parser<'a, I: Iterator<Item = &'a str>> {
+    tokens: std::iter::Peekable<I>,
+}
+
+impl<'a, I: Iterator<Item = &'a str>> Parser<'a, I> {
+    fn next_is(&mut self, keyword: &str) -> bool {
+        self.tokens
+            .next_if(|token| token.eq_ignore_ascii_case(keyword))
+            .is_some()
+    }
+
+    fn or(&mut self) -> Result<LicenseExpr, String> {
+        let mut exprs = vec![self.and()?];
+        while self.next_is("OR") {
+            exprs.push(self.and()?);
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            LicenseExpr::Or(exprs)
+        })
+    }
+
+    fn and(&mut self) -> Result<LicenseExpr, String> {
+        let mut exprs = vec![self.primary()?];
+        while self.next_is("AND") {
+            exprs.push(self.primary()?);
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            LicenseExpr::And(exprs)
+        })
+    }
+
+    fn primary(&mut self) -> Result<LicenseExpr, String> {
+        match self.tokens.next() {
+            Some("(") => {
+                let expr = self.or()?;
+                match self.tokens.next() {
+                    Some(")") => Ok(expr),
+                    _ => Err("missing `)`".into()),
+                }
+            }
+            Some(token) if [")", "AND", "OR", "WITH"].contains(&token.to_uppercase().as_str()) => {
+                Err(format!("expected a license, found `{token}`"))
+            }
+            Some(id) => {
+                let exception = if self.next_is("WITH") {
+                    Some(self.tokens.next().ok_or("missing exception after `WITH`")?)
+                } else {
+                    None
+                };
+                Ok(LicenseExpr::License(LicenseReq::new(id, exception)))
+            }
+            None => Err("expected a license".into()),
+        }
+    }
+}
+
+/// The outcome of checking the license of a crate against the policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "verdict", content = "explanation", rename_all = "snake_case")]
+pub enum LicenseVerdict {
+    Allowed,
+    /// The explanation of the clause that failed.
+    Violation(String),
+}
+
+impl LicenseVerdict {
+    pub fn violation(&self) -> Option<&str> {
+        match self {
+            Self::Allowed => None,
+            Self::Violation(explanation) => Some(explanation),
+        }
+    }
+}
+
+/// Licenses that crates may or may not use.
+///
+/// A license is accepted when it is not denied and either the allow list is
+/// empty or it is allowed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LicensePolicy {
+    pub allow: Vec<LicenseReq>,
+    pub deny: Vec<LicenseReq>,
+}
+
+/// The part of a `deny.toml` used by cargo-deny that lists licenses.
+#[derive(Debug, Default, Deserialize)]
+struct DenyToml {
+    #[serde(default)]
+    licenses: DenyLicenses,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DenyLicenses {
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    deny: Vec<String>,
+}
+
+impl LicensePolicy {
+    fn new(allow: &[String], deny: &[String]) -> Self {
+        let parse = |entries: &[String]| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    LicenseReq::parse(entry)
+                        .inspect_err(|err| warn!("Ignoring license policy entry: {err}"))
+                        .ok()
+                })
+                .collect_vec()
+        };
+        Self {
+            allow: parse(allow),
+            deny: parse(deny),
+        }
+    }
+
+    /// Reads the `[licenses]` section of a `deny.toml`.
+    pub fn from_deny_toml(contents: &str) -> Result<Self, String> {
+        let deny = toml::from_str::<DenyToml>(contents).map_err(|err| err.to_string())?;
+        Ok(Self::new(&deny.licenses.allow, &deny.licenses.deny))
+    }
+
+    /// Loads the policy from the `[policy.licenses]` config, falling back to
+    /// the nearest `deny.toml`. Returns `None` when no license is allowed or
+    /// denied anywhere.
+    pub fn load(config: &LicensePolicyConfig, start: &Path) -> Option<Self> {
+        let policy = if config.allow.is_empty() && config.deny.is_empty() {
+            let path = match config.deny_file {
+                Some(ref path) => path.into(),
+                None => start
+                    .ancestors()
+                    .flat_map(|dir| [dir.join("deny.toml"), dir.join(".deny.toml")])
+                    .find(|path| path.is_file())?,
+            };
+            std::fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|contents| Self::from_deny_toml(&contents))
+                .inspect_err(|err| warn!("Unable to read `{}`: {err}", path.display()))
+                .ok()?
+        } else {
+            Self::new(&config.allow, &config.deny)
+        };
+        (!policy.allow.is_empty() || !policy.deny.is_empty()).then_some(policy)
+    }
+
+    fn check(&self, license: &LicenseReq) -> Result<(), String> {
+        if self.deny.iter().any(|entry| entry.covers(license)) {
+            Err(format!("`{license}` is denied"))
+        } else if !self.allow.is_empty() && !self.allow.iter().any(|entry| entry.covers(license)) {
+            Err(format!("`{license}` is not in the allow list"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks the license expression of a crate, which may be missing when
+    /// the crate uses a license file instead.
+    pub fn evaluate(&self, license: Option<&str>) -> LicenseVerdict {
+        let Some(license) = license else {
+            return LicenseVerdict::Violation("no SPDX license expression".into());
+        };
+        match LicenseExpr::parse(license).and_then(|expr| expr.evaluate(self)) {
+            Ok(()) => LicenseVerdict::Allowed,
+            Err(explanation) => LicenseVerdict::Violation(explanation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allow: &[&str], deny: &[&str]) -> LicensePolicy {
+        let entries = |e: &[&str]| e.iter().map(|s| s.to_string()).collect_vec();
+        LicensePolicy::new(&entries(allow), &entries(deny))
+    }
+
+    #[test]
+    fn parses_spdx_expressions() {
+        for (expression, expected) in [
+            ("MIT OR Apache-2.0", "MIT OR Apache-2.0"),
+            ("MIT/Apache-2.0", "MIT OR Apache-2.0"),
+            (
+                "(MIT OR Apache-2.0) AND Unicode-DFS-2016",
+                "(MIT OR Apache-2.0) AND Unicode-DFS-2016",
+            ),
+            (
+                "Apache-2.0 WITH LLVM-exception OR MIT",
+                "Apache-2.0 WITH LLVM-exception OR MIT",
+            ),
+            ("((GPL-2.0+))", "GPL-2.0+"),
+        ] {
+            assert_eq!(
+                LicenseExpr::parse(expression).unwrap().to_string(),
+                expected
+            );
+        }
+        assert!(LicenseExpr::parse("MIT OR").is_err());
+        assert!(LicenseExpr::parse("(MIT").is_err());
+        assert!(LicenseExpr::parse("MIT Apache-2.0").is_err());
+    }
+
+    #[test]
+    fn explains_the_failing_clause() {
+        let policy = policy(&["MIT", "Apache-2.0"], &["GPL-3.0"]);
+        assert_eq!(
+            policy.evaluate(Some("MIT OR GPL-3.0")),
+            LicenseVerdict::Allowed
+        );
+        assert_eq!(
+            policy.evaluate(Some("MIT AND GPL-3.0-or-later")),
+            LicenseVerdict::Violation("`GPL-3.0-or-later` is denied".into())
+        );
+        assert_eq!(
+            policy.evaluate(Some("Apache-2.0 AND (GPL-3.0 OR MPL-2.0)")),
+            LicenseVerdict::Violation(
+                "none of `GPL-3.0 OR MPL-2.0` is allowed: `GPL-3.0` is denied; `MPL-2.0` is not in the allow list"
+                    .into()
+            )
+        );
+        assert!(policy.evaluate(None).violation().is_some());
+    }
+
+    #[test]
+    fn matches_exceptions() {
+        let policy = policy(&["Apache-2.0"], &["GPL-2.0 WITH Classpath-exception-2.0"]);
+        assert_eq!(
+            policy.evaluate(Some("Apache-2.0 WITH LLVM-exception")),
+            LicenseVerdict::Allowed
+        );
+        assert_eq!(
+            policy.evaluate(Some(
+                "GPL-2.0-only WITH Classpath-exception-2.0 OR Apache-2.0"
+            )),
+            LicenseVerdict::Allowed
+        );
+        let deny_only = self::policy(&[], &["GPL-2.0 WITH Classpath-exception-2.0"]);
+        assert_eq!(deny_only.evaluate(Some("GPL-2.0")), LicenseVerdict::Allowed);
+    }
+
+    #[test]
+    fn reads_deny_toml() {
+        let policy = LicensePolicy::from_deny_toml(
+            r#"
+            [licenses]
+            allow = ["MIT", "Apache-2.0"]
+            confidence-threshold = 0.8
+            "#,
+        )
+        .unwrap();
+        assert_eq!(policy.allow.len(), 2);
+        assert!(policy.deny.is_empty());
+    }
+}
@@ -1,15 +1,34 @@
 mod action;
+mod advisory;
+mod annotations;
 mod app;
 mod cli;
 mod command;
 mod config;
+mod crates_index_helper;
 mod crates_io_api_helper;
 mod errors;
 mod events;
+mod export;
+mod favorites;
+mod health;
+mod license;
 mod logging;
+mod pick;
+mod project;
+mod recently_viewed;
+mod report;
+mod resolver;
+mod rust_version;
+mod search_history;
 mod serde_helper;
+mod session;
+mod storage;
+mod subcommands;
 mod tui;
+mod watchlist;
 mod widgets;
+mod workspace_graph;
 
 use app::App;
 use color_eyre::eyre::Result;
@@ -19,16 +38,35 @@ async fn main() -> Result<()> {
     let cli = cli::parse();
     config::init(&cli)?;
     logging::init()?;
+
+    // commands that don't start the TUI leave the terminal alone
+    match cli.command {
+        Some(cli::Commands::Export(ref args)) => return export::run(args).await,
+        Some(cli::Commands::Search(ref args)) => return subcommands::search(args).await,
+        Some(cli::Commands::Info(ref args)) => return subcommands::info(args).await,
+        Some(cli::Commands::Pick(ref args)) => match pick::run(args).await? {
+            Some(line) => return subcommands::write_stdout(&format!("{line}\n")),
+            None => std::process::exit(1),
+        },
+        None => {}
+    }
+
     errors::install_hooks()?;
 
     if cli.print_default_config {
-        println!("{}", toml::to_string_pretty(config::get())?);
-        return Ok(());
+        return subcommands::write_stdout(&format!("{}\n", toml::to_string_pretty(config::get())?));
     }
 
     let tui = tui::Tui::init()?;
     let events = events::Events::new();
-    App::new().run(tui, events).await?;
+    let mut app = App::new();
+    if !cli.fresh {
+        match session::Session::load() {
+            Ok(session) => app.restore_session(session),
+            Err(error_message) => app.show_error_popup(error_message),
+        }
+    }
+    app.run(tui, events).await?;
 
     Ok(())
 }
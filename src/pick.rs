@@ -0,0 +1,120 @@
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::Result;
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyModifiers};
+use tokio::{sync::mpsc, task::JoinHandle};
+use tui_input::backend::crossterm::EventHandler;
+
+use crate::{
+    cli::PickArgs,
+    crates_io_api_helper,
+    events::{Event, Events},
+    tui::InlineTui,
+    widgets::crate_picker::{CratePicker, CratePickerWidget},
+};
+
+/// How long typing has to pause before the query is searched.
+const SEARCH_DELAY: Duration = Duration::from_millis(300);
+
+/// What `crates-tui pick` prints for the picked crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PickOutput {
+    /// The name of the crate.
+    Name,
+    /// `name@version`, with the newest version of the crate.
+    Version,
+    /// A `cargo add` command line adding the crate.
+    CargoAdd,
+}
+
+impl PickOutput {
+    pub fn line(self, crate_data: &crates_io_api::Crate) -> String {
+        match self {
+            Self::Name => crate_data.name.clone(),
+            Self::Version => format!("{}@{}", crate_data.name, crate_data.max_version),
+            Self::CargoAdd => format!("cargo add {}", crate_data.name),
+        }
+    }
+}
+
+type SearchResult = (String, Result<(Vec<crates_io_api::Crate>, u64), String>);
+
+/// Runs `crates-tui pick`, returning the line to print for the picked crate,
+/// or `None` when the picker was cancelled.
+pub async fn run(args: &PickArgs) -> Result<Option<String>> {
+    let mut tui = InlineTui::init(args.height)?;
+    let mut events = Events::new();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut picker = CratePicker::new(args.query.clone());
+    let mut search_task = Some(search(args, picker.input.value(), tx.clone()));
+    // when the query was last edited, until it is searched
+    let mut edited_at: Option<Instant> = None;
+
+    loop {
+        tokio::select! {
+            Some((query, result)) = rx.recv() => {
+                // results of a query that was edited since are dropped
+                if query == picker.input.value() {
+                    match result {
+                        Ok((crates, total)) => picker.set_results(crates, total),
+                        Err(err) => {
+                            picker.loading = false;
+                            picker.error = Some(err);
+                        }
+                    }
+                }
+            }
+            Some(event) = events.next() => match event {
+                Event::Key(key) => match (key.code, key.modifiers) {
+                    (KeyCode::Esc, _) | (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+                        return Ok(None)
+                    }
+                    (KeyCode::Enter, _) => {
+                        if let Some(selected) = picker.selected() {
+                            return Ok(Some(args.print.line(selected)));
+                        }
+                    }
+                    (KeyCode::Up, _)
+                    | (KeyCode::Char('p' | 'k'), KeyModifiers::CONTROL) => picker.scroll_previous(),
+                    (KeyCode::Down, _)
+                    | (KeyCode::Char('n' | 'j'), KeyModifiers::CONTROL) => picker.scroll_next(),
+                    _ => {
+                        let changed = picker.input.handle_event(&CrosstermEvent::Key(key));
+                        if changed.is_some_and(|changed| changed.value) {
+                            picker.loading = true;
+                            edited_at = Some(Instant::now());
+                        }
+                    }
+                },
+                Event::Render => {
+                    tui.draw(|frame| {
+                        frame.render_stateful_widget(CratePickerWidget, frame.size(), &mut picker);
+                        if let Some(cursor) = picker.cursor_position() {
+                            frame.set_cursor(cursor.x, cursor.y);
+                        }
+                    })?;
+                }
+                _ => {}
+            }
+        }
+        if edited_at.is_some_and(|at| at.elapsed() >= SEARCH_DELAY) {
+            edited_at = None;
+            if let Some(task) = search_task.take() {
+                task.abort();
+            }
+            search_task = Some(search(args, picker.input.value(), tx.clone()));
+        }
+    }
+}
+
+/// Searches crates.io in the background, sending the results along with the
+/// query they are for.
+fn search(args: &PickArgs, query: &str, tx: mpsc::UnboundedSender<SearchResult>) -> JoinHandle<()> {
+    let query = query.to_string();
+    let sort = args.page.sort.clone();
+    let (page, per_page) = (args.page.page, args.page.per_page);
+    tokio::spawn(async move {
+        let result = crates_io_api_helper::search_crates(&query, page, per_page, sort).await;
+        let _ = tx.send((query, result));
+    })
+}
@@ -0,0 +1,931 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use itertools::Itertools;
+use serde::Deserialize;
+
+use crate::{
+    crates_index_helper::{DependencyKind, IndexVersion},
+    storage,
+};
+
+/// Returns the nearest `Cargo.toml`, starting at `start` and walking up the
+/// directory tree.
+pub fn find_manifest(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|path| path.is_file())
+}
+
+/// Returns the root manifest of the workspace that `manifest` belongs to,
+/// which is `manifest` itself for single crate projects.
+pub fn find_workspace_manifest(manifest: &Path) -> Option<PathBuf> {
+    let dir = manifest.parent()?;
+    dir.ancestors()
+        .map(|dir| dir.join("Cargo.toml"))
+        .filter(|path| path.is_file())
+        .find(|path| {
+            read_manifest(path).is_some_and(|manifest| manifest.get("workspace").is_some())
+        })
+        .or_else(|| Some(manifest.to_path_buf()))
+}
+
+/// The manifests and lockfile to watch for a project that could not be
+/// loaded: `manifest`, the root manifest of its workspace and its
+/// `Cargo.lock`.
+pub fn manifest_files(manifest: &Path) -> Vec<PathBuf> {
+    let workspace_manifest =
+        find_workspace_manifest(manifest).unwrap_or_else(|| manifest.to_path_buf());
+    let lockfile = workspace_manifest.with_file_name("Cargo.lock");
+    [manifest.to_path_buf(), workspace_manifest, lockfile]
+        .into_iter()
+        .unique()
+        .collect()
+}
+
+fn read_manifest(path: &Path) -> Option<toml::Table> {
+    std::fs::read_to_string(path).ok()?.parse().ok()
+}
+
+/// Returns the `rust-version` of the project in the current directory.
+///
+/// This follows `rust-version.workspace = true` to the workspace root, and
+/// falls back to `workspace.package.rust-version` for virtual manifests.
+pub fn rust_version() -> Option<String> {
+    let manifest_path = find_manifest(&std::env::current_dir().ok()?)?;
+    let manifest = read_manifest(&manifest_path)?;
+    let workspace_rust_version = || {
+        let workspace_manifest = read_manifest(&find_workspace_manifest(&manifest_path)?)?;
+        workspace_manifest
+            .get("workspace")?
+            .get("package")?
+            .get("rust-version")?
+            .as_str()
+            .map(String::from)
+    };
+    match manifest.get("package").and_then(|p| p.get("rust-version")) {
+        Some(toml::Value::String(rust_version)) => Some(rust_version.clone()),
+        Some(toml::Value::Table(_)) => workspace_rust_version(),
+        _ if manifest.get("package").is_none() => workspace_rust_version(),
+        _ => None,
+    }
+}
+
+/// A package recorded in `Cargo.lock`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: semver::Version,
+    pub source: Option<String>,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+impl LockedPackage {
+    pub fn is_from_registry(&self) -> bool {
+        self.source.as_deref().is_some_and(is_registry_source)
+    }
+
+    /// The name and version of the packages this one depends on.
+    ///
+    /// Entries are written as `name`, `name version` or
+    /// `name version (source)`, and the version is only given when several
+    /// versions of the crate are locked.
+    pub fn dependency_ids(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.dependencies.iter().filter_map(|dependency| {
+            let mut parts = dependency.split_whitespace();
+            Some((parts.next()?, parts.next()))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Lockfile {
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockedPackage>,
+}
+
+impl Lockfile {
+    /// Locked versions of a crate from crates.io.
+    pub fn versions<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a semver::Version> {
+        self.packages
+            .iter()
+            .filter(move |p| p.name == name && p.is_from_registry())
+            .map(|p| &p.version)
+    }
+
+    /// Every locked package, sorted by name and version, with its parents and
+    /// the index metadata of its version.
+    ///
+    /// Index metadata is only filled in for crates with entries in `index`.
+    pub fn entries(&self, index: &HashMap<String, Vec<IndexVersion>>) -> Vec<LockfileEntry> {
+        let counts = self.packages.iter().counts_by(|p| p.name.as_str());
+        let parents = self
+            .packages
+            .iter()
+            .flat_map(|parent| parent.dependency_ids().map(move |id| (id, parent)))
+            .into_group_map();
+        let version_strings = self
+            .packages
+            .iter()
+            .map(|p| p.version.to_string())
+            .collect_vec();
+        self.packages
+            .iter()
+            .zip(&version_strings)
+            .map(|(package, version)| {
+                let name = package.name.as_str();
+                let versions = index
+                    .get(&package.name)
+                    .filter(|_| package.is_from_registry())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let locked = versions.iter().find(|v| v.vers == package.version);
+                LockfileEntry {
+                    package: package.clone(),
+                    parents: [(name, None), (name, Some(version.as_str()))]
+                        .iter()
+                        .flat_map(|id| parents.get(id).into_iter().flatten())
+                        .map(|p| format!("{} {}", p.name, p.version))
+                        .sorted()
+                        .collect(),
+                    versions: counts[name],
+                    yanked: locked.map(|v| v.yanked),
+                    rust_version: locked.and_then(|v| v.rust_version.clone()),
+                    latest: versions
+                        .iter()
+                        .filter(|v| !v.yanked && v.vers.pre.is_empty())
+                        .map(|v| v.vers.clone())
+                        .max(),
+                }
+            })
+            .sorted_by(|a, b| {
+                (&a.package.name, &a.package.version).cmp(&(&b.package.name, &b.package.version))
+            })
+            .collect()
+    }
+}
+
+/// A package from `Cargo.lock` with the packages that pull it in.
+#[derive(Debug, Clone)]
+pub struct LockfileEntry {
+    pub package: LockedPackage,
+    /// The packages depending on this one, as `name version`.
+    pub parents: Vec<String>,
+    /// Number of locked versions of the crate.
+    pub versions: usize,
+    /// Whether the locked version is yanked, if it is known.
+    pub yanked: Option<bool>,
+    pub rust_version: Option<String>,
+    pub latest: Option<semver::Version>,
+}
+
+impl LockfileEntry {
+    /// Several versions of the crate are locked.
+    pub fn is_duplicate(&self) -> bool {
+        self.versions > 1
+    }
+
+    pub fn is_yanked(&self) -> bool {
+        self.yanked == Some(true)
+    }
+}
+
+/// Whether a package comes from crates.io, whose index is the only one that
+/// is looked up. Packages of alternate registries are left alone.
+fn is_registry_source(source: &str) -> bool {
+    matches!(
+        source,
+        "registry+https://github.com/rust-lang/crates.io-index" | "sparse+https://index.crates.io/"
+    )
+}
+
+/// A crates.io crate the workspace depends on directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsedCrate {
+    pub requirements: Vec<String>,
+    pub locked: Vec<semver::Version>,
+}
+
+/// The Cargo workspace crates-tui was started in.
+#[derive(Debug, Clone)]
+pub struct Project {
+    pub metadata: cargo_metadata::Metadata,
+    pub lockfile: Lockfile,
+}
+
+impl Project {
+    /// Loads the workspace that `manifest_path` belongs to.
+    ///
+    /// This runs `cargo metadata --no-deps`, which is quick and doesn't need
+    /// network access, and reads `Cargo.lock` if there is one.
+    pub fn load(manifest_path: &Path) -> Result<Self, String> {
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(manifest_path)
+            .no_deps()
+            .exec()
+            .map_err(|err| format!("Unable to load `{}`: {err}", manifest_path.display()))?;
+        let lockfile_path = metadata.workspace_root.join("Cargo.lock");
+        let lockfile = match std::fs::read_to_string(&lockfile_path) {
+            Ok(contents) => toml::from_str(&contents)
+                .map_err(|err| format!("Unable to parse `{lockfile_path}`: {err}"))?,
+            Err(_) => Lockfile::default(),
+        };
+        Ok(Self { metadata, lockfile })
+    }
+
+    /// Files that require the project to be loaded again when they change.
+    pub fn watched_files(&self) -> Vec<PathBuf> {
+        let root = self.metadata.workspace_root.as_std_path();
+        std::iter::once(root.join("Cargo.toml"))
+            .chain(std::iter::once(root.join("Cargo.lock")))
+            .chain(
+                self.metadata
+                    .workspace_packages()
+                    .into_iter()
+                    .map(|p| p.manifest_path.clone().into_std_path_buf()),
+            )
+            .unique()
+            .collect()
+    }
+
+    /// Crates.io dependencies of every workspace member, keyed by crate name.
+    ///
+    /// Only locked versions that match one of the requirements are listed,
+    /// since other versions are pulled in by some other dependency.
+    pub fn used_crates(&self) -> HashMap<String, UsedCrate> {
+        self.metadata
+            .workspace_packages()
+            .into_iter()
+            .flat_map(|p| &p.dependencies)
+            .filter(|d| d.source.as_deref().is_some_and(is_registry_source))
+            .map(|d| (d.name.clone(), d.req.clone()))
+            .into_group_map()
+            .into_iter()
+            .map(|(name, requirements)| {
+                let requirements = requirements.into_iter().unique().collect_vec();
+                let locked = self
+                    .lockfile
+                    .versions(&name)
+                    .filter(|v| requirements.iter().any(|req| req.matches(v)))
+                    .cloned()
+                    .sorted()
+                    .collect();
+                let used = UsedCrate {
+                    requirements: requirements.iter().map(|r| r.to_string()).collect(),
+                    locked,
+                };
+                (name, used)
+            })
+            .collect()
+    }
+}
+
+/// A direct crates.io dependency of a workspace member, with the versions it
+/// could be upgraded to.
+#[derive(Debug, Clone)]
+pub struct ProjectDependency {
+    pub member: String,
+    pub manifest_path: PathBuf,
+    /// The crate name.
+    pub name: String,
+    /// The key of the dependency in the manifest, which differs from the
+    /// crate name when the dependency is renamed.
+    pub key: String,
+    pub kind: DependencyKind,
+    pub target: Option<String>,
+    pub req: semver::VersionReq,
+    pub locked: Option<semver::Version>,
+    /// The newest version matching the requirement.
+    pub compatible: Option<semver::Version>,
+    pub latest: Option<semver::Version>,
+}
+
+impl ProjectDependency {
+    /// The version in use, or the one that would be used once the lockfile
+    /// is updated.
+    pub fn version(&self) -> Option<&semver::Version> {
+        self.locked.as_ref().or(self.compatible.as_ref())
+    }
+
+    /// Upgrading to the latest version requires changing the requirement.
+    pub fn is_breaking(&self) -> bool {
+        self.latest.as_ref().is_some_and(|v| !self.req.matches(v))
+    }
+
+    /// A newer version matching the requirement is available, so running
+    /// `cargo update` would pick it up.
+    pub fn is_updatable(&self) -> bool {
+        matches!((&self.locked, &self.compatible), (Some(l), Some(c)) if c > l)
+    }
+
+    /// The manifest table the dependency is declared in, e.g.
+    /// `["target", "cfg(unix)", "dependencies"]`.
+    pub fn table(&self) -> Vec<String> {
+        let spec = DependencySpec {
+            kind: self.kind,
+            ..Default::default()
+        };
+        match self.target {
+            Some(ref target) => vec!["target".into(), target.clone(), spec.kind_table().into()],
+            None => vec![spec.kind_table().into()],
+        }
+    }
+}
+
+impl Project {
+    /// Direct crates.io dependencies of every workspace member.
+    ///
+    /// Compatible and latest versions are only filled in for crates with
+    /// entries in `index`.
+    pub fn dependencies(
+        &self,
+        index: &HashMap<String, Vec<IndexVersion>>,
+    ) -> Vec<ProjectDependency> {
+        self.metadata
+            .workspace_packages()
+            .into_iter()
+            .flat_map(|package| {
+                package
+                    .dependencies
+                    .iter()
+                    .filter(|d| d.source.as_deref().is_some_and(is_registry_source))
+                    .map(move |d| (package, d))
+            })
+            .map(|(package, d)| {
+                let locked = self
+                    .lockfile
+                    .versions(&d.name)
+                    .filter(|v| d.req.matches(v))
+                    .max()
+                    .cloned();
+                let versions = index.get(&d.name).map(Vec::as_slice).unwrap_or_default();
+                let newest = |matches: &dyn Fn(&semver::Version) -> bool| {
+                    versions
+                        .iter()
+                        .filter(|v| !v.yanked && matches(&v.vers))
+                        .map(|v| v.vers.clone())
+                        .max()
+                };
+                let prerelease = locked.as_ref().is_some_and(|v| !v.pre.is_empty());
+                ProjectDependency {
+                    member: package.name.clone(),
+                    manifest_path: package.manifest_path.clone().into_std_path_buf(),
+                    name: d.name.clone(),
+                    key: d.rename.clone().unwrap_or_else(|| d.name.clone()),
+                    kind: match d.kind {
+                        cargo_metadata::DependencyKind::Development => DependencyKind::Dev,
+                        cargo_metadata::DependencyKind::Build => DependencyKind::Build,
+                        _ => DependencyKind::Normal,
+                    },
+                    target: d.target.as_ref().map(|t| t.to_string()),
+                    req: d.req.clone(),
+                    locked,
+                    compatible: newest(&|v| d.req.matches(v)),
+                    latest: newest(&|v| prerelease || v.pre.is_empty()),
+                }
+            })
+            .sorted_by(|a, b| {
+                (&a.member, a.kind, &a.name, &a.target)
+                    .cmp(&(&b.member, b.kind, &b.name, &b.target))
+            })
+            .collect()
+    }
+}
+
+/// Tracks modification times of files to notice when they change.
+#[derive(Debug, Default)]
+pub struct FileWatcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl FileWatcher {
+    pub fn new(files: Vec<PathBuf>) -> Self {
+        Self {
+            files: files
+                .into_iter()
+                .map(|path| {
+                    let modified = Self::modified(&path);
+                    (path, modified)
+                })
+                .collect(),
+        }
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    /// Returns `true` if any of the files changed since the last call.
+    pub fn changed(&mut self) -> bool {
+        let mut changed = false;
+        for (path, modified) in self.files.iter_mut() {
+            let current = Self::modified(path);
+            if current != *modified {
+                *modified = current;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Loads the workspace that `manifest_path` belongs to in the background.
+pub async fn request_project(
+    manifest_path: PathBuf,
+    project: Arc<Mutex<Option<Project>>>,
+) -> Result<(), String> {
+    let loaded = tokio::task::spawn_blocking(move || Project::load(&manifest_path))
+        .await
+        .map_err(|err| format!("Unable to load project: {err}"))??;
+    *project.lock().unwrap() = Some(loaded);
+    Ok(())
+}
+
+/// A dependency to add to a manifest.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencySpec {
+    pub name: String,
+    pub version: String,
+    pub features: Vec<String>,
+    pub default_features: bool,
+    pub optional: bool,
+    pub kind: DependencyKind,
+    /// A target triple or `cfg(..)` expression for target-specific
+    /// dependencies.
+    pub target: Option<String>,
+    /// Inherit the dependency from `[workspace.dependencies]` instead of
+    /// giving a version.
+    pub workspace: bool,
+}
+
+impl DependencySpec {
+    pub fn kind_table(&self) -> &'static str {
+        match self.kind {
+            DependencyKind::Normal => "dependencies",
+            DependencyKind::Dev => "dev-dependencies",
+            DependencyKind::Build => "build-dependencies",
+        }
+    }
+
+    /// The manifest table this dependency goes in.
+    pub fn table_name(&self) -> String {
+        let table = self.kind_table();
+        match self.target {
+            Some(ref target) => format!("target.\"{target}\".{table}"),
+            None => table.into(),
+        }
+    }
+
+    /// Writes the fields of this dependency into an existing entry, keeping
+    /// keys such as `path` or `package` that aren't managed here.
+    fn update(&self, entry: &mut dyn toml_edit::TableLike) {
+        if self.workspace {
+            entry.insert("workspace", toml_edit::value(true));
+            for key in ["version", "path", "git", "branch", "tag", "rev", "registry"] {
+                entry.remove(key);
+            }
+        } else {
+            entry.remove("workspace");
+            entry.insert("version", toml_edit::value(&self.version));
+        }
+        if self.features.is_empty() {
+            entry.remove("features");
+        } else {
+            entry.insert(
+                "features",
+                toml_edit::value(self.features.iter().collect::<toml_edit::Array>()),
+            );
+        }
+        // inherited dependencies take `default-features` from the workspace
+        if self.default_features || self.workspace {
+            entry.remove("default-features");
+            entry.remove("default_features");
+        } else {
+            entry.insert("default-features", toml_edit::value(false));
+        }
+        if self.optional {
+            entry.insert("optional", toml_edit::value(true));
+        } else {
+            entry.remove("optional");
+        }
+    }
+
+    fn to_item(&self) -> toml_edit::Item {
+        if self.features.is_empty() && self.default_features && !self.optional && !self.workspace {
+            return toml_edit::value(&self.version);
+        }
+        let mut table = toml_edit::InlineTable::new();
+        self.update(&mut table);
+        toml_edit::value(table)
+    }
+
+    /// The entry for `[workspace.dependencies]` that this dependency
+    /// inherits from.
+    pub fn workspace_entry(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            version: self.version.clone(),
+            default_features: self.default_features,
+            ..Default::default()
+        }
+    }
+}
+
+fn implicit_table() -> toml_edit::Item {
+    let mut table = toml_edit::Table::new();
+    table.set_implicit(true);
+    toml_edit::Item::Table(table)
+}
+
+/// Adds a dependency to the contents of a manifest, preserving its
+/// formatting and comments.
+///
+/// An existing entry for the same crate is updated in place. If the
+/// dependency table was sorted before, it is kept sorted.
+pub fn add_dependency(manifest: &str, spec: &DependencySpec) -> Result<String, String> {
+    if spec.optional && spec.kind == DependencyKind::Dev {
+        return Err("Dev-dependencies cannot be optional".into());
+    }
+    let mut document = manifest
+        .parse::<toml_edit::Document>()
+        .map_err(|err| format!("Unable to parse manifest: {err}"))?;
+    let table_name = spec.table_name();
+    let not_a_table = || format!("`{table_name}` is not a table");
+
+    let mut parent = document.as_table_mut() as &mut dyn toml_edit::TableLike;
+    if let Some(ref target) = spec.target {
+        parent = parent
+            .entry("target")
+            .or_insert(implicit_table())
+            .as_table_like_mut()
+            .ok_or_else(not_a_table)?
+            .entry(target)
+            .or_insert(implicit_table())
+            .as_table_like_mut()
+            .ok_or_else(not_a_table)?;
+    }
+    let dependencies = parent
+        .entry(spec.kind_table())
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or_else(not_a_table)?;
+    insert_dependency(dependencies, spec);
+    Ok(document.to_string())
+}
+
+/// Adds a dependency to `[workspace.dependencies]` of a workspace root
+/// manifest, the same way as [`add_dependency`].
+pub fn add_workspace_dependency(manifest: &str, spec: &DependencySpec) -> Result<String, String> {
+    let mut document = manifest
+        .parse::<toml_edit::Document>()
+        .map_err(|err| format!("Unable to parse manifest: {err}"))?;
+    let dependencies = document
+        .get_mut("workspace")
+        .and_then(|w| w.as_table_like_mut())
+        .ok_or("The manifest has no `[workspace]` table")?
+        .entry("dependencies")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or("`workspace.dependencies` is not a table")?;
+    insert_dependency(dependencies, spec);
+    Ok(document.to_string())
+}
+
+/// Inserts or updates the entry of a dependency, keeping the table sorted if
+/// it was sorted before.
+fn insert_dependency(dependencies: &mut dyn toml_edit::TableLike, spec: &DependencySpec) {
+    let sorted = dependencies
+        .iter()
+        .map(|(name, _)| name)
+        .tuple_windows()
+        .all(|(a, b)| a <= b);
+    match dependencies.get_mut(&spec.name) {
+        Some(toml_edit::Item::Value(toml_edit::Value::InlineTable(entry))) => {
+            spec.update(entry);
+            entry.fmt();
+        }
+        Some(toml_edit::Item::Table(entry)) => spec.update(entry),
+        _ => {
+            dependencies.insert(&spec.name, spec.to_item());
+        }
+    }
+    if sorted {
+        dependencies.sort_values();
+    }
+}
+
+/// Names of the dependencies in `[workspace.dependencies]`, or `None` if
+/// `manifest` is not a workspace root.
+pub fn workspace_dependencies(manifest: &Path) -> Option<Vec<String>> {
+    let workspace = read_manifest(manifest)?.remove("workspace")?;
+    Some(
+        workspace
+            .get("dependencies")
+            .and_then(|d| d.as_table())
+            .map(|d| d.keys().cloned().collect())
+            .unwrap_or_default(),
+    )
+}
+
+/// File in the data directory with the last member a dependency was added
+/// to, for each workspace.
+const LAST_MEMBERS_FILE: &str = "workspace_members.toml";
+
+/// The member a dependency was last added to in the workspace at `root`.
+pub fn last_member(root: &Path) -> Option<String> {
+    let members: HashMap<String, String> = storage::load(LAST_MEMBERS_FILE).ok()?;
+    members.get(&root.to_string_lossy().to_string()).cloned()
+}
+
+pub fn remember_member(root: &Path, member: &str) -> Result<(), String> {
+    let mut members: HashMap<String, String> = storage::load(LAST_MEMBERS_FILE)?;
+    members.insert(root.to_string_lossy().into(), member.into());
+    storage::save(LAST_MEMBERS_FILE, &members)
+}
+
+/// A changed version requirement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequirementBump {
+    pub old: String,
+    pub new: String,
+    /// The contents of the edited manifest.
+    pub manifest: String,
+}
+
+/// Returns a requirement for `version` in the style of `old`, keeping an
+/// explicit operator such as `=` or `~`.
+fn bumped_requirement(old: &str, version: &semver::Version) -> String {
+    let old = old.trim();
+    let operator = old
+        .find(|c: char| c.is_ascii_digit())
+        .filter(|_| !old.contains(','))
+        .map_or("", |i| old[..i].trim());
+    format!("{operator}{version}")
+}
+
+/// Changes the version requirement of the dependency `key` in `table` to
+/// `version`, preserving the formatting of the manifest.
+///
+/// Returns `None` if the dependency is inherited with `workspace = true`, in
+/// which case the workspace manifest has to be edited instead.
+pub fn bump_requirement(
+    manifest: &str,
+    table: &[String],
+    key: &str,
+    version: &semver::Version,
+) -> Result<Option<RequirementBump>, String> {
+    let mut document = manifest
+        .parse::<toml_edit::Document>()
+        .map_err(|err| format!("Unable to parse manifest: {err}"))?;
+    let mut item = document.as_item_mut();
+    for name in table {
+        item = item
+            .as_table_like_mut()
+            .and_then(|t| t.get_mut(name))
+            .ok_or_else(|| format!("`{}` not found in manifest", table.join(".")))?;
+    }
+    let entry = item
+        .as_table_like_mut()
+        .and_then(|t| t.get_mut(key))
+        .ok_or_else(|| format!("`{key}` not found in `[{}]`", table.join(".")))?;
+    if entry.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+        return Ok(None);
+    }
+    let requirement = if entry.is_str() {
+        entry.as_value_mut()
+    } else {
+        entry
+            .as_table_like_mut()
+            .and_then(|t| t.get_mut("version"))
+            .and_then(|v| v.as_value_mut())
+    }
+    .filter(|v| v.is_str())
+    .ok_or_else(|| format!("`{key}` has no version requirement"))?;
+
+    let old = requirement.as_str().unwrap_or_default().to_string();
+    let new = bumped_requirement(&old, version);
+    let decor = requirement.decor().clone();
+    *requirement = new.as_str().into();
+    *requirement.decor_mut() = decor;
+    Ok(Some(RequirementBump {
+        old,
+        new,
+        manifest: document.to_string(),
+    }))
+}
+
+/// A line of a diff between two versions of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Context(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Number of unchanged lines shown around a change.
+const DIFF_CONTEXT: usize = 3;
+
+/// Returns the changed lines between `old` and `new`, with a few lines of
+/// context.
+///
+/// Edits made by this app touch a single region of a file, so everything
+/// between the first and the last changed line is treated as one hunk.
+pub fn diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let old = old.lines().collect_vec();
+    let new = new.lines().collect_vec();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if prefix == old.len() && prefix == new.len() {
+        return vec![];
+    }
+    let context = |lines: &[&str]| {
+        lines
+            .iter()
+            .map(|line| DiffLine::Context(line.to_string()))
+            .collect_vec()
+    };
+    let mut lines = context(&old[prefix.saturating_sub(DIFF_CONTEXT)..prefix]);
+    lines.extend(
+        old[prefix..old.len() - suffix]
+            .iter()
+            .map(|line| DiffLine::Removed(line.to_string())),
+    );
+    lines.extend(
+        new[prefix..new.len() - suffix]
+            .iter()
+            .map(|line| DiffLine::Added(line.to_string())),
+    );
+    let end = old.len() - suffix;
+    lines.extend(context(&old[end..(end + DIFF_CONTEXT).min(old.len())]));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const MANIFEST: &str = r#"[package]
+name = "app"
+
+[dependencies]
+# logging
+log = "0.4"
+serde = { version = "1.0", path = "../serde" }
+"#;
+
+    fn spec(name: &str) -> DependencySpec {
+        DependencySpec {
+            name: name.into(),
+            version: "1.2.3".into(),
+            default_features: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reads_locked_registry_versions() {
+        let lockfile: Lockfile = toml::from_str(
+            r#"
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["log", "syn 1.0.109", "syn 2.0.48"]
+
+[[package]]
+name = "syn"
+version = "2.0.48"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "syn"
+version = "1.0.109"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            lockfile.versions("syn").sorted().collect_vec(),
+            vec![
+                &semver::Version::new(1, 0, 109),
+                &semver::Version::new(2, 0, 48)
+            ]
+        );
+        assert_eq!(lockfile.versions("app").count(), 0);
+
+        let entries = lockfile.entries(&HashMap::new());
+        let summary = entries
+            .iter()
+            .map(|e| (e.package.name.as_str(), e.is_duplicate(), e.parents.clone()))
+            .collect_vec();
+        assert_eq!(
+            summary,
+            vec![
+                ("app", false, vec![]),
+                ("syn", true, vec!["app 0.1.0".to_string()]),
+                ("syn", true, vec!["app 0.1.0".to_string()]),
+            ]
+        );
+        assert_eq!(entries[1].package.version, semver::Version::new(1, 0, 109));
+        assert!(entries[1].package.is_from_registry());
+        assert!(!LockedPackage {
+            name: "corp-log".into(),
+            version: semver::Version::new(0, 1, 0),
+            source: Some("sparse+https://registry.example.com/index/".into()),
+            dependencies: vec![],
+        }
+        .is_from_registry());
+    }
+
+    #[test]
+    fn adds_dependency_in_sorted_position() {
+        let manifest = add_dependency(MANIFEST, &spec("rand")).unwrap();
+        assert_eq!(
+            diff(MANIFEST, &manifest),
+            vec![
+                DiffLine::Context("[dependencies]".into()),
+                DiffLine::Context("# logging".into()),
+                DiffLine::Context("log = \"0.4\"".into()),
+                DiffLine::Added("rand = \"1.2.3\"".into()),
+                DiffLine::Context("serde = { version = \"1.0\", path = \"../serde\" }".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn updates_existing_dependency() {
+        let spec = DependencySpec {
+            features: vec!["derive".into()],
+            default_features: false,
+            ..spec("serde")
+        };
+        let manifest = add_dependency(MANIFEST, &spec).unwrap();
+        assert!(manifest.contains(
+            r#"serde = { version = "1.2.3", path = "../serde", features = ["derive"], default-features = false }"#
+        ));
+    }
+
+    #[test]
+    fn bumps_requirement_keeping_style() {
+        let version = semver::Version::new(1, 2, 3);
+        let bump = bump_requirement(MANIFEST, &["dependencies".into()], "log", &version)
+            .unwrap()
+            .unwrap();
+        assert_eq!((bump.old.as_str(), bump.new.as_str()), ("0.4", "1.2.3"));
+        assert!(bump.manifest.contains("# logging\nlog = \"1.2.3\"\n"));
+
+        let manifest = "[dependencies]\nfoo = { version = \"=0.1\" }\nbar = { workspace = true }\n";
+        let bump = bump_requirement(manifest, &["dependencies".into()], "foo", &version)
+            .unwrap()
+            .unwrap();
+        assert_eq!(bump.new, "=1.2.3");
+        assert_eq!(
+            bump_requirement(manifest, &["dependencies".into()], "bar", &version),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn adds_target_specific_dev_dependency() {
+        let spec = DependencySpec {
+            kind: DependencyKind::Dev,
+            target: Some("cfg(unix)".into()),
+            ..spec("libc")
+        };
+        let manifest = add_dependency(MANIFEST, &spec).unwrap();
+        assert!(manifest.ends_with("\n[target.\"cfg(unix)\".dev-dependencies]\nlibc = \"1.2.3\"\n"));
+    }
+
+    #[test]
+    fn adds_workspace_dependency() {
+        let spec = DependencySpec {
+            features: vec!["derive".into()],
+            default_features: false,
+            workspace: true,
+            ..spec("serde")
+        };
+        let manifest = add_dependency(MANIFEST, &spec).unwrap();
+        assert!(manifest.contains(r#"serde = { workspace = true, features = ["derive"] }"#));
+
+        let root = "[workspace]\nmembers = [\"app\"]\n";
+        let root = add_workspace_dependency(root, &spec.workspace_entry()).unwrap();
+        assert!(root.ends_with(
+            "[workspace.dependencies]\nserde = { version = \"1.2.3\", default-features = false }\n"
+        ));
+        assert!(add_workspace_dependency(MANIFEST, &spec).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const RECENTLY_VIEWED_FILE: &str = "recently_viewed.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewedCrate {
+    pub name: String,
+    pub viewed_at: DateTime<Utc>,
+}
+
+/// The crates whose details were opened, newest first, persisted in the data
+/// directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentlyViewed {
+    #[serde(default)]
+    pub crates: Vec<ViewedCrate>,
+}
+
+impl RecentlyViewed {
+    pub fn load() -> Result<Self, String> {
+        storage::load(RECENTLY_VIEWED_FILE)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save(RECENTLY_VIEWED_FILE, self)
+    }
+
+    /// Moves the crate to the top of the list, keeping at most `max_entries`
+    /// crates.
+    pub fn record(&mut self, name: &str, viewed_at: DateTime<Utc>, max_entries: usize) {
+        self.crates.retain(|c| c.name != name);
+        self.crates.insert(
+            0,
+            ViewedCrate {
+                name: name.into(),
+                viewed_at,
+            },
+        );
+        self.crates.truncate(max_entries);
+    }
+}
+
+/// How long ago a crate was viewed, e.g. `3 hours ago`, or the date for
+/// crates viewed more than a month ago.
+pub fn viewed_ago(viewed_at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now - viewed_at;
+    let plural = |n: i64, unit: &str| {
+        if n == 1 {
+            format!("1 {unit} ago")
+        } else {
+            format!("{n} {unit}s ago")
+        }
+    };
+    match elapsed {
+        e if e.num_minutes() < 1 => "just now".into(),
+        e if e.num_hours() < 1 => plural(e.num_minutes(), "minute"),
+        e if e.num_days() < 1 => plural(e.num_hours(), "hour"),
+        e if e.num_days() == 1 => "yesterday".into(),
+        e if e.num_days() <= 30 => plural(e.num_days(), "day"),
+        _ => viewed_at.format("%Y-%m-%d").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn records_newest_first() {
+        let now = Utc::now();
+        let mut recent = RecentlyViewed::default();
+        recent.record("serde", now - Duration::hours(2), 3);
+        recent.record("tokio", now - Duration::hours(1), 3);
+        recent.record("serde", now, 3);
+        recent.record("axum", now, 3);
+        recent.record("clap", now, 3);
+        let names = recent.crates.iter().map(|c| c.name.as_str()).collect_vec();
+        assert_eq!(names, ["clap", "axum", "serde"]);
+        assert_eq!(recent.crates[2].viewed_at, now);
+        let toml = toml::to_string(&recent).unwrap();
+        assert_eq!(toml::from_str::<RecentlyViewed>(&toml).unwrap(), recent);
+    }
+
+    #[test]
+    fn describes_when_crates_were_viewed() {
+        let now = "2024-03-10T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let ago = |d: Duration| viewed_ago(now - d, now);
+        assert_eq!(ago(Duration::seconds(30)), "just now");
+        assert_eq!(ago(Duration::minutes(1)), "1 minute ago");
+        assert_eq!(ago(Duration::minutes(59)), "59 minutes ago");
+        assert_eq!(ago(Duration::hours(5)), "5 hours ago");
+        assert_eq!(ago(Duration::hours(30)), "yesterday");
+        assert_eq!(ago(Duration::days(12)), "12 days ago");
+        assert_eq!(ago(Duration::days(60)), "2024-01-10");
+    }
+}
@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use num_format::{Locale, ToFormattedString};
+
+use crate::advisory::Advisory;
+
+/// The number of dependencies of a crate version, by kind.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyCounts {
+    pub normal: usize,
+    /// Normal dependencies that are only pulled in by a feature.
+    pub optional: usize,
+    pub build: usize,
+    pub dev: usize,
+}
+
+impl DependencyCounts {
+    pub fn new(dependencies: &[crates_io_api::Dependency]) -> Self {
+        let mut counts = Self::default();
+        for dependency in dependencies {
+            match dependency.kind.as_str() {
+                "build" => counts.build += 1,
+                "dev" => counts.dev += 1,
+                _ if dependency.optional => counts.optional += 1,
+                _ => counts.normal += 1,
+            }
+        }
+        counts
+    }
+}
+
+/// What the dependency justification report of the newest version of a
+/// crate is written from.
+#[derive(Debug, Clone, Default)]
+pub struct Justification {
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub license: Option<String>,
+    pub owners: Vec<String>,
+    pub downloads: u64,
+    pub recent_downloads: Option<u64>,
+    pub released: Option<DateTime<Utc>>,
+    pub rust_version: Option<String>,
+    pub dependencies: DependencyCounts,
+    /// The advisories affecting the version, `None` without an advisory
+    /// database.
+    pub advisories: Option<Vec<Advisory>>,
+    pub documentation: Option<String>,
+    pub homepage: Option<String>,
+    pub repository: Option<String>,
+}
+
+impl Justification {
+    /// Gathers the report of the newest version of a crate, keeping the
+    /// advisories of the crate that affect it.
+    pub fn new(
+        crate_response: &crates_io_api::CrateResponse,
+        owners: &[crates_io_api::User],
+        dependencies: &[crates_io_api::Dependency],
+        advisories: Option<Vec<Advisory>>,
+    ) -> Self {
+        let crate_data = &crate_response.crate_data;
+        let version = crate_response
+            .versions
+            .iter()
+            .find(|v| v.num == crate_data.max_version);
+        let parsed = semver::Version::parse(&crate_data.max_version).ok();
+        Self {
+            name: crate_data.name.clone(),
+            version: crate_data.max_version.clone(),
+            description: crate_data.description.clone(),
+            license: version.and_then(|v| v.license.clone()),
+            owners: owners.iter().map(|o| o.login.clone()).collect(),
+            downloads: crate_data.downloads,
+            recent_downloads: crate_data.recent_downloads,
+            released: version.map(|v| v.created_at),
+            rust_version: version.and_then(|v| v.rust_version.clone()),
+            dependencies: DependencyCounts::new(dependencies),
+            advisories: advisories.map(|advisories| {
+                advisories
+                    .into_iter()
+                    .filter(|a| parsed.as_ref().is_some_and(|v| a.affects(v)))
+                    .collect()
+            }),
+            documentation: crate_data.documentation.clone(),
+            homepage: crate_data.homepage.clone(),
+            repository: crate_data.repository.clone(),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let unknown = || "unknown".to_string();
+        let downloads = match self.recent_downloads {
+            Some(recent) => format!(
+                "{} ({} in the last 90 days)",
+                self.downloads.to_formatted_string(&Locale::en),
+                recent.to_formatted_string(&Locale::en)
+            ),
+            None => self.downloads.to_formatted_string(&Locale::en),
+        };
+        let deps = &self.dependencies;
+        let dependencies = format!(
+            "{} normal, {} optional, {} build, {} dev",
+            deps.normal, deps.optional, deps.build, deps.dev
+        );
+        let advisories = match self.advisories {
+            None => "not checked, no advisory database is configured".to_string(),
+            Some(ref advisories) if advisories.is_empty() => "none known".to_string(),
+            Some(ref advisories) => advisories
+                .iter()
+                .map(|a| {
+                    format!(
+                        "[{}]({}) {} ({}, patched in {})",
+                        a.id,
+                        a.link(),
+                        a.title,
+                        a.severity_label(),
+                        a.patched_versions()
+                    )
+                })
+                .join("<br>"),
+        };
+        let rows = [
+            ("License", self.license.clone().unwrap_or_else(unknown)),
+            ("Owners", self.owners.join(", ")),
+            ("Downloads", downloads),
+            (
+                "Last release",
+                self.released
+                    .map_or_else(unknown, |d| d.format("%Y-%m-%d").to_string()),
+            ),
+            (
+                "MSRV",
+                self.rust_version
+                    .clone()
+                    .unwrap_or_else(|| "not specified".into()),
+            ),
+            ("Dependencies", dependencies),
+            ("Advisories", advisories),
+        ];
+        let links = std::iter::once(format!(
+            "[crates.io](https://crates.io/crates/{})",
+            self.name
+        ))
+        .chain(
+            [
+                ("documentation", &self.documentation),
+                ("homepage", &self.homepage),
+                ("repository", &self.repository),
+            ]
+            .into_iter()
+            .filter_map(|(label, url)| url.as_ref().map(|url| format!("[{label}]({url})"))),
+        )
+        .join(" · ");
+
+        let mut report = format!("## Dependency: `{}` {}\n\n", self.name, self.version);
+        if let Some(ref description) = self.description {
+            report += &format!("> {}\n\n", description.split_whitespace().join(" "));
+        }
+        report += "| | |\n| --- | --- |\n";
+        for (label, value) in rows {
+            report += &format!("| {label} | {} |\n", value.replace('|', "\\|"));
+        }
+        report += &format!("\n{links}\n\n");
+        report += "### Why this dependency\n\n";
+        report += "<!-- What it is needed for, and why the alternatives were not chosen. -->\n\n";
+        report += "TODO\n";
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_markdown_report() {
+        let justification = Justification {
+            name: "serde".into(),
+            version: "1.0.200".into(),
+            description: Some("A generic serialization\nframework".into()),
+            license: Some("MIT OR Apache-2.0".into()),
+            owners: vec!["dtolnay".into()],
+            downloads: 1_234_567,
+            recent_downloads: Some(1000),
+            dependencies: DependencyCounts {
+                normal: 1,
+                optional: 1,
+                build: 0,
+                dev: 2,
+            },
+            advisories: Some(vec![]),
+            repository: Some("https://github.com/serde-rs/serde".into()),
+            ..Default::default()
+        };
+        let report = justification.to_markdown();
+        assert!(report.starts_with(
+            "## Dependency: `serde` 1.0.200\n\n> A generic serialization framework\n"
+        ));
+        assert!(report.contains("| Downloads | 1,234,567 (1,000 in the last 90 days) |\n"));
+        assert!(report.contains("| Last release | unknown |\n"));
+        assert!(report.contains("| MSRV | not specified |\n"));
+        assert!(report.contains("| Dependencies | 1 normal, 1 optional, 0 build, 2 dev |\n"));
+        assert!(report.contains("| Advisories | none known |\n"));
+        assert!(report.contains(
+            "[crates.io](https://crates.io/crates/serde) · [repository](https://github.com/serde-rs/serde)"
+        ));
+        assert!(report.ends_with("### Why this dependency\n\n<!-- What it is needed for, and why the alternatives were not chosen. -->\n\nTODO\n"));
+    }
+}
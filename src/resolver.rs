@@ -0,0 +1,392 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use itertools::Itertools;
+use semver::{Version, VersionReq};
+
+use crate::crates_index_helper::{DependencyKind, IndexVersion};
+
+/// A resolved crate, identified by its name and version.
+pub type NodeId = (String, Version);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Edge {
+    pub child: NodeId,
+    pub kind: DependencyKind,
+    pub optional: bool,
+    pub target: Option<String>,
+}
+
+/// The transitive dependency graph of a crate version.
+///
+/// Dev-dependencies are never followed since they don't affect the build of
+/// dependents. Target-specific dependencies are followed for every target.
+#[derive(Debug, Clone, Default)]
+pub struct Resolution {
+    pub root: Option<NodeId>,
+    /// Every resolved crate with its activated features.
+    pub nodes: BTreeMap<NodeId, BTreeSet<String>>,
+    pub edges: BTreeMap<NodeId, BTreeSet<Edge>>,
+    /// Features of the root crate that can be toggled.
+    pub root_features: Vec<String>,
+    /// Dependencies for which no version matches the requirement.
+    pub unresolved: BTreeSet<(String, String)>,
+}
+
+impl Resolution {
+    pub fn unique_crates(&self) -> usize {
+        self.nodes.keys().map(|(name, _)| name).unique().count()
+    }
+
+    /// Crates that appear with more than one version in the graph.
+    pub fn duplicates(&self) -> BTreeMap<String, Vec<Version>> {
+        self.nodes
+            .keys()
+            .cloned()
+            .into_group_map()
+            .into_iter()
+            .filter(|(_, versions)| versions.len() > 1)
+            .collect()
+    }
+
+    pub fn children(&self, id: &NodeId) -> impl Iterator<Item = &Edge> {
+        self.edges.get(id).into_iter().flatten()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    /// The index entries of these crates are needed to continue resolving.
+    MissingIndexEntries(Vec<String>),
+    VersionNotFound(String, String),
+}
+
+/// Features and optional dependencies activated by a set of requested
+/// features.
+#[derive(Debug, Default)]
+struct Activation {
+    features: BTreeSet<String>,
+    dependencies: BTreeSet<String>,
+    dependency_features: BTreeMap<String, BTreeSet<String>>,
+}
+
+fn activate(version: &IndexVersion, requested: &BTreeSet<String>) -> Activation {
+    let all_features = version.all_features();
+    let optional = version
+        .deps
+        .iter()
+        .filter(|d| d.optional)
+        .map(|d| d.name.as_str())
+        .collect::<BTreeSet<_>>();
+
+    let mut activation = Activation::default();
+    let mut weak = BTreeMap::<String, BTreeSet<String>>::new();
+    let mut queue = requested.iter().cloned().collect::<VecDeque<_>>();
+    while let Some(feature) = queue.pop_front() {
+        if !activation.features.insert(feature.clone()) {
+            continue;
+        }
+        let Some(values) = all_features.get(&feature) else {
+            // optional dependencies implicitly define a feature of the same name
+            if optional.contains(feature.as_str()) {
+                activation.dependencies.insert(feature);
+            }
+            continue;
+        };
+        for value in values {
+            if let Some(dep) = value.strip_prefix("dep:") {
+                activation.dependencies.insert(dep.into());
+            } else if let Some((dep, dep_feature)) = value.split_once('/') {
+                if let Some(dep) = dep.strip_suffix('?') {
+                    weak.entry(dep.into())
+                        .or_default()
+                        .insert(dep_feature.into());
+                } else {
+                    activation.dependencies.insert(dep.into());
+                    activation
+                        .dependency_features
+                        .entry(dep.into())
+                        .or_default()
+                        .insert(dep_feature.into());
+                }
+            } else {
+                queue.push_back(value.clone());
+            }
+        }
+    }
+    // weak features only apply to dependencies that are enabled anyway
+    for (dep, features) in weak {
+        let enabled = activation.dependencies.contains(&dep)
+            || version.deps.iter().any(|d| d.name == dep && !d.optional);
+        if enabled {
+            activation
+                .dependency_features
+                .entry(dep)
+                .or_default()
+                .extend(features);
+        }
+    }
+    activation
+        .features
+        .retain(|f| f != "default" || all_features.contains_key(f));
+    activation
+}
+
+/// Picks the version used for a dependency requirement.
+///
+/// A version already in the graph is reused when it matches, which mirrors
+/// how cargo unifies semver compatible requirements. Otherwise the newest
+/// version that isn't yanked is used.
+fn select_version(
+    entries: &[IndexVersion],
+    req: &VersionReq,
+    selected: &BTreeMap<NodeId, BTreeSet<String>>,
+    crate_name: &str,
+) -> Option<Version> {
+    selected
+        .keys()
+        .filter(|(name, version)| name == crate_name && req.matches(version))
+        .map(|(_, version)| version.clone())
+        .max()
+        .or_else(|| {
+            entries
+                .iter()
+                .filter(|v| !v.yanked && req.matches(&v.vers))
+                .map(|v| v.vers.clone())
+                .max()
+        })
+}
+
+fn find_version<'a>(
+    index: &'a HashMap<String, Vec<IndexVersion>>,
+    (name, version): &NodeId,
+) -> Option<&'a IndexVersion> {
+    index
+        .get(name)
+        .and_then(|entries| entries.iter().find(|v| v.vers == *version))
+}
+
+/// Resolves the transitive dependencies of a crate version against index
+/// data.
+///
+/// When the index entries of some crates have not been fetched yet, this
+/// returns [`ResolveError::MissingIndexEntries`], and resolving should be
+/// retried once they are available.
+pub fn resolve(
+    index: &HashMap<String, Vec<IndexVersion>>,
+    root: &NodeId,
+    features: &BTreeSet<String>,
+    default_features: bool,
+) -> Result<Resolution, ResolveError> {
+    let Some(root_entries) = index.get(&root.0) else {
+        return Err(ResolveError::MissingIndexEntries(vec![root.0.clone()]));
+    };
+    let Some(root_version) = root_entries.iter().find(|v| v.vers == root.1) else {
+        return Err(ResolveError::VersionNotFound(
+            root.0.clone(),
+            root.1.to_string(),
+        ));
+    };
+
+    let mut requested = features.clone();
+    if default_features {
+        requested.insert("default".into());
+    }
+
+    let mut resolution = Resolution {
+        root: Some(root.clone()),
+        root_features: root_version
+            .all_features()
+            .into_keys()
+            .chain(
+                root_version
+                    .deps
+                    .iter()
+                    .filter(|d| d.optional)
+                    .map(|d| d.name.clone()),
+            )
+            .sorted()
+            .dedup()
+            .collect(),
+        ..Default::default()
+    };
+    let mut missing = BTreeSet::new();
+    resolution.nodes.insert(root.clone(), requested);
+    let mut queue = VecDeque::from([root.clone()]);
+
+    while let Some(id) = queue.pop_front() {
+        let Some(version) = find_version(index, &id) else {
+            continue;
+        };
+        let activation = activate(version, &resolution.nodes[&id]);
+        for dep in &version.deps {
+            if dep.kind == DependencyKind::Dev
+                || (dep.optional && !activation.dependencies.contains(&dep.name))
+            {
+                continue;
+            }
+            let crate_name = dep.crate_name();
+            let Some(entries) = index.get(crate_name) else {
+                missing.insert(crate_name.to_string());
+                continue;
+            };
+            let Some(child_version) =
+                select_version(entries, &dep.req, &resolution.nodes, crate_name)
+            else {
+                resolution
+                    .unresolved
+                    .insert((crate_name.to_string(), dep.req.to_string()));
+                continue;
+            };
+            let child = (crate_name.to_string(), child_version);
+
+            let mut child_features = dep.features.iter().cloned().collect::<BTreeSet<_>>();
+            child_features.extend(
+                activation
+                    .dependency_features
+                    .get(&dep.name)
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+            if dep.default_features {
+                child_features.insert("default".into());
+            }
+
+            let entry = resolution.nodes.entry(child.clone()).or_default();
+            let before = entry.len();
+            entry.extend(child_features);
+            if entry.len() > before || before == 0 {
+                queue.push_back(child.clone());
+            }
+
+            resolution
+                .edges
+                .entry(id.clone())
+                .or_default()
+                .insert(Edge {
+                    child,
+                    kind: dep.kind,
+                    optional: dep.optional,
+                    target: dep.target.clone(),
+                });
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(ResolveError::MissingIndexEntries(
+            missing.into_iter().collect(),
+        ));
+    }
+
+    // replace requested features with the features they actually activate
+    for (id, features) in resolution.nodes.iter_mut() {
+        if let Some(version) = find_version(index, id) {
+            *features = activate(version, features).features;
+            features.remove("default");
+        }
+    }
+    Ok(resolution)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn version(
+        name: &str,
+        vers: &str,
+        deps: &[(&str, &str, bool)],
+        features: &[(&str, &[&str])],
+    ) -> IndexVersion {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "vers": vers,
+            "deps": deps.iter().map(|(name, req, optional)| serde_json::json!({
+                "name": name,
+                "req": req,
+                "features": [],
+                "optional": optional,
+                "default_features": true,
+                "target": null,
+                "kind": "normal",
+            })).collect_vec(),
+            "features": features.iter().map(|(k, v)| (k.to_string(), v.to_vec())).collect::<BTreeMap<_, _>>(),
+            "yanked": false,
+        }))
+        .unwrap()
+    }
+
+    fn index() -> HashMap<String, Vec<IndexVersion>> {
+        HashMap::from([
+            (
+                "app".into(),
+                vec![version(
+                    "app",
+                    "1.0.0",
+                    &[("a", "^1", false), ("b", "^0.2", true)],
+                    &[("extra", &["dep:b"])],
+                )],
+            ),
+            (
+                "a".into(),
+                vec![
+                    version("a", "1.0.0", &[], &[]),
+                    version("a", "1.2.0", &[("c", "^1", false)], &[]),
+                ],
+            ),
+            (
+                "b".into(),
+                vec![version("b", "0.2.1", &[("c", "^2", false)], &[])],
+            ),
+            (
+                "c".into(),
+                vec![
+                    version("c", "1.5.0", &[], &[]),
+                    version("c", "2.0.0", &[], &[]),
+                ],
+            ),
+        ])
+    }
+
+    fn root() -> NodeId {
+        ("app".into(), Version::new(1, 0, 0))
+    }
+
+    #[test]
+    fn resolves_newest_matching_versions() {
+        let resolution = resolve(&index(), &root(), &BTreeSet::new(), true).unwrap();
+        let nodes = resolution
+            .nodes
+            .keys()
+            .map(|(n, v)| format!("{n}@{v}"))
+            .collect_vec();
+        assert_eq!(nodes, vec!["a@1.2.0", "app@1.0.0", "c@1.5.0"]);
+        assert!(resolution.duplicates().is_empty());
+        assert_eq!(resolution.root_features, vec!["b", "extra"]);
+    }
+
+    #[test]
+    fn features_enable_optional_dependencies() {
+        let features = BTreeSet::from(["extra".to_string()]);
+        let resolution = resolve(&index(), &root(), &features, true).unwrap();
+        assert_eq!(resolution.unique_crates(), 4);
+        assert_eq!(
+            resolution.duplicates(),
+            BTreeMap::from([(
+                "c".to_string(),
+                vec![Version::new(1, 5, 0), Version::new(2, 0, 0)]
+            )])
+        );
+    }
+
+    #[test]
+    fn reports_missing_index_entries() {
+        let mut index = index();
+        index.remove("c");
+        assert_eq!(
+            resolve(&index, &root(), &BTreeSet::new(), true).unwrap_err(),
+            ResolveError::MissingIndexEntries(vec!["c".into()])
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use semver::Version;
+
+use crate::{config, crates_index_helper::IndexVersion, project};
+
+/// Parses a `rust-version` value such as `1.70` or `1.70.0`.
+///
+/// `rust-version` allows omitting the minor and patch components, which
+/// `semver::Version::parse` does not.
+pub fn parse(rust_version: &str) -> Option<semver::Version> {
+    let mut parts = rust_version.trim().split('.').map(|p| p.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    parts
+        .next()
+        .is_none()
+        .then(|| semver::Version::new(major, minor, patch))
+}
+
+/// Returns the Rust version crates are checked against.
+///
+/// This is the configured toolchain if there is one, and otherwise the
+/// `rust-version` of the project in the current directory.
+pub fn target_toolchain() -> Option<Version> {
+    let msrv = &config::get().msrv;
+    match msrv.toolchain {
+        Some(ref toolchain) => parse(toolchain),
+        None if msrv.use_project_rust_version => project::rust_version().as_deref().and_then(parse),
+        None => None,
+    }
+}
+
+/// How well the versions of a crate work with a Rust toolchain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Compatibility {
+    /// The newest version can be used.
+    Latest,
+    /// Only versions up to this one can be used.
+    Older(Version),
+    /// No version can be used.
+    Incompatible,
+}
+
+/// Checks which versions of a crate can be used with `toolchain`.
+///
+/// Yanked versions are ignored, and versions that don't declare a
+/// `rust-version` are assumed to be compatible.
+pub fn compatibility(versions: &[IndexVersion], toolchain: &Version) -> Compatibility {
+    let available = versions.iter().filter(|v| !v.yanked);
+    let newest = available.clone().map(|v| &v.vers).max();
+    let newest_compatible = available
+        .filter(|v| {
+            v.rust_version
+                .as_deref()
+                .and_then(parse)
+                .is_none_or(|rust_version| rust_version <= *toolchain)
+        })
+        .map(|v| &v.vers)
+        .max();
+    match newest_compatible {
+        Some(version) if Some(version) == newest => Compatibility::Latest,
+        Some(version) => Compatibility::Older(version.clone()),
+        None => Compatibility::Incompatible,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn version(vers: &str, rust_version: Option<&str>, yanked: bool) -> IndexVersion {
+        serde_json::from_value(serde_json::json!({
+            "name": "foo",
+            "vers": vers,
+            "deps": [],
+            "features": {},
+            "yanked": yanked,
+            "rust_version": rust_version,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_short_rust_versions() {
+        assert_eq!(parse("1.70"), Some(Version::new(1, 70, 0)));
+        assert_eq!(parse("1.70.1"), Some(Version::new(1, 70, 1)));
+        assert_eq!(parse("1.70.0.1"), None);
+        assert_eq!(parse("stable"), None);
+    }
+
+    #[test]
+    fn finds_newest_compatible_version() {
+        let versions = vec![
+            version("1.0.0", None, false),
+            version("1.1.0", Some("1.60"), false),
+            version("1.2.0", Some("1.74"), false),
+            version("1.3.0", Some("1.56"), true),
+        ];
+        assert_eq!(
+            compatibility(&versions, &Version::new(1, 75, 0)),
+            Compatibility::Latest
+        );
+        assert_eq!(
+            compatibility(&versions, &Version::new(1, 70, 0)),
+            Compatibility::Older(Version::new(1, 1, 0))
+        );
+        assert_eq!(
+            compatibility(&versions[1..], &Version::new(1, 56, 0)),
+            Compatibility::Incompatible
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{serde_helper::sort::SortDef, storage};
+
+const SEARCH_HISTORY_FILE: &str = "search_history.toml";
+
+/// A submitted search.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchHistoryEntry {
+    pub query: String,
+    #[serde(with = "SortDef")]
+    pub sort: crates_io_api::Sort,
+}
+
+/// Every submitted search, oldest first, persisted in the data directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchHistory {
+    #[serde(default)]
+    pub entries: Vec<SearchHistoryEntry>,
+}
+
+impl SearchHistory {
+    pub fn load() -> Result<Self, String> {
+        storage::load(SEARCH_HISTORY_FILE)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save(SEARCH_HISTORY_FILE, self)
+    }
+
+    /// Adds a search as the newest entry, dropping an older copy of it and
+    /// the oldest entries beyond `max_entries`. Empty queries are not
+    /// remembered.
+    pub fn push(&mut self, query: &str, sort: crates_io_api::Sort, max_entries: usize) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+        let entry = SearchHistoryEntry {
+            query: query.into(),
+            sort,
+        };
+        self.entries.retain(|e| *e != entry);
+        self.entries.push(entry);
+        let excess = self.entries.len().saturating_sub(max_entries);
+        self.entries.drain(..excess);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Indices of the entries matching `pattern`, best match first and the
+    /// newest first among equal matches.
+    pub fn matches(&self, pattern: &str) -> Vec<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .rev()
+            .filter_map(|(i, e)| Some((i, fuzzy_score(pattern, &e.query)?)))
+            .sorted_by_key(|(_, score)| std::cmp::Reverse(*score))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// Scores how well `text` matches `pattern` when the characters of the
+/// pattern appear in order, ignoring case. Consecutive characters and
+/// characters at the start of a word score higher, gaps score lower.
+pub fn fuzzy_score(pattern: &str, text: &str) -> Option<i64> {
+    let text = text.to_lowercase().chars().collect_vec();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for c in pattern
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+    {
+        let found = next + text[next..].iter().position(|t| *t == c)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - next) as i64;
+        previous = Some(found);
+        next = found + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use crates_io_api::Sort;
+
+    use super::*;
+
+    #[test]
+    fn keeps_newest_unique_entries() {
+        let mut history = SearchHistory::default();
+        history.push("serde", Sort::Relevance, 3);
+        history.push("tokio", Sort::Downloads, 3);
+        history.push("  ", Sort::Relevance, 3);
+        history.push("serde", Sort::Relevance, 3);
+        history.push("serde", Sort::Downloads, 3);
+        history.push("axum", Sort::Relevance, 3);
+        let queries = history
+            .entries
+            .iter()
+            .map(|e| e.query.as_str())
+            .collect_vec();
+        assert_eq!(queries, ["serde", "serde", "axum"]);
+        assert_eq!(history.entries[1].sort, Sort::Downloads);
+        let toml = toml::to_string(&history).unwrap();
+        assert!(toml.contains("sort = \"downloads\""));
+        assert_eq!(toml::from_str::<SearchHistory>(&toml).unwrap(), history);
+    }
+
+    #[test]
+    fn ranks_fuzzy_matches() {
+        assert_eq!(fuzzy_score("tk", "serde"), None);
+        assert!(fuzzy_score("tok", "tokio").unwrap() > fuzzy_score("tok", "toml kit").unwrap());
+        assert!(
+            fuzzy_score("sj", "serde json").unwrap() > fuzzy_score("sj", "seahash jit").unwrap()
+        );
+        let mut history = SearchHistory::default();
+        for query in ["async runtime", "tokio", "toml", "axum"] {
+            history.push(query, Sort::Relevance, 10);
+        }
+        assert_eq!(history.matches("to"), [2, 1]);
+        assert_eq!(history.matches(""), [3, 2, 1, 0]);
+    }
+}
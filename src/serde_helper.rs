@@ -26,6 +26,8 @@ pub mod keybindings {
                 Command::DecrementPage => Action::DecrementPage,
                 Command::NextSummaryMode => Action::NextSummaryMode,
                 Command::PreviousSummaryMode => Action::PreviousSummaryMode,
+                Command::NextFullCrateInfoTab => Action::NextFullCrateInfoTab,
+                Command::PreviousFullCrateInfoTab => Action::PreviousFullCrateInfoTab,
                 Command::ToggleSortBy { reload, forward } => {
                     Action::ToggleSortBy { reload, forward }
                 }
@@ -43,6 +45,63 @@ pub mod keybindings {
                 Command::CopyCargoAddCommandToClipboard => Action::CopyCargoAddCommandToClipboard,
                 Command::OpenDocsUrlInBrowser => Action::OpenDocsUrlInBrowser,
                 Command::OpenCratesIOUrlInBrowser => Action::OpenCratesIOUrlInBrowser,
+                Command::ShowFullCrateInfo => Action::ShowFullCrateInfo,
+                Command::CloseFullCrateInfo => Action::CloseFullCrateInfo,
+                Command::ShowDependencyTree => Action::ShowDependencyTree,
+                Command::CloseDependencyTree => Action::CloseDependencyTree,
+                Command::ToggleDependencyTreeFocus => Action::ToggleDependencyTreeFocus,
+                Command::ToggleDependencyTreeSelection => Action::ToggleDependencyTreeSelection,
+                Command::ToggleHealthColumn => Action::ToggleHealthColumn,
+                Command::ToggleHideIncompatible => Action::ToggleHideIncompatible,
+                Command::AddDependency => Action::AddDependency,
+                Command::SubmitAddDependency => Action::SubmitAddDependency,
+                Command::CancelAddDependency => Action::CancelAddDependency,
+                Command::OpenVersionDiffInBrowser => Action::OpenVersionDiffInBrowser,
+                Command::BumpDependencyRequirement => Action::BumpDependencyRequirement,
+                Command::ShowLockfile => Action::ShowLockfile,
+                Command::ToggleDuplicatesOnly => Action::ToggleDuplicatesOnly,
+                Command::ShowInverseDependencies => Action::ShowInverseDependencies,
+                Command::CloseInverseDependencies => Action::CloseInverseDependencies,
+                Command::ShowFeatureUnification => Action::ShowFeatureUnification,
+                Command::CloseFeatureUnification => Action::CloseFeatureUnification,
+                Command::ToggleFavorite => Action::ToggleFavorite,
+                Command::NextCollection => Action::NextCollection,
+                Command::PreviousCollection => Action::PreviousCollection,
+                Command::MoveFavoriteUp => Action::MoveFavoriteUp,
+                Command::MoveFavoriteDown => Action::MoveFavoriteDown,
+                Command::EditFavoriteNote => Action::EditFavoriteNote,
+                Command::NewCollection => Action::NewCollection,
+                Command::RenameCollection => Action::RenameCollection,
+                Command::DeleteCollection => Action::DeleteCollection,
+                Command::SubmitFavoritesPrompt => Action::SubmitFavoritesPrompt,
+                Command::CancelFavoritesPrompt => Action::CancelFavoritesPrompt,
+                Command::PreviousSearchQuery => Action::PreviousSearchQuery,
+                Command::NextSearchQuery => Action::NextSearchQuery,
+                Command::ShowSearchHistory => Action::ShowSearchHistory,
+                Command::SelectSearchHistory => Action::SelectSearchHistory,
+                Command::CloseSearchHistory => Action::CloseSearchHistory,
+                Command::ClearSearchHistory => Action::ClearSearchHistory,
+                Command::ShowRecentlyViewed => Action::ShowRecentlyViewed,
+                Command::CloseRecentlyViewed => Action::CloseRecentlyViewed,
+                Command::ToggleWatch => Action::ToggleWatch,
+                Command::ShowWatchlist => Action::ShowWatchlist,
+                Command::CloseWatchlist => Action::CloseWatchlist,
+                Command::AcknowledgeWatched => Action::AcknowledgeWatched,
+                Command::AcknowledgeAllWatched => Action::AcknowledgeAllWatched,
+                Command::CheckWatchlist => Action::CheckWatchlist,
+                Command::EditCrateNote => Action::EditCrateNote,
+                Command::EditCrateTags => Action::EditCrateTags,
+                Command::SubmitAnnotationPrompt => Action::SubmitAnnotationPrompt,
+                Command::CancelAnnotationPrompt => Action::CancelAnnotationPrompt,
+                Command::ShowSavedSearches => Action::ShowSavedSearches,
+                Command::CloseSavedSearches => Action::CloseSavedSearches,
+                Command::SelectSavedSearch => Action::SelectSavedSearch,
+                Command::RunSavedSearch(name) => Action::RunSavedSearch(name),
+                Command::Export => Action::Export,
+                Command::SubmitExportPrompt => Action::SubmitExportPrompt,
+                Command::CancelExportPrompt => Action::CancelExportPrompt,
+                Command::CopyJustificationReport => Action::CopyJustificationReport,
+                Command::WriteJustificationReport => Action::WriteJustificationReport,
             }
         }
 
@@ -316,3 +375,19 @@ pub mod keybindings {
         sequences.into_iter().map(parse_key_event).collect()
     }
 }
+
+pub mod sort {
+    use serde::{Deserialize, Serialize};
+
+    /// Serializes the sort of a search, which `crates_io_api` doesn't.
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "crates_io_api::Sort", rename_all = "snake_case")]
+    pub enum SortDef {
+        Alphabetical,
+        Relevance,
+        Downloads,
+        RecentDownloads,
+        RecentUpdates,
+        NewlyAdded,
+    }
+}
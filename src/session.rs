@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{Mode, DEFAULT_PAGE_SIZE},
+    serde_helper::sort::SortDef,
+    storage,
+    widgets::summary::SummaryMode,
+};
+
+const SESSION_FILE: &str = "session.toml";
+
+/// What was shown when crates-tui was last quit, persisted in the data
+/// directory so that the next launch can pick up from there.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub mode: Mode,
+    pub search: String,
+    #[serde(with = "SortDef")]
+    pub sort: crates_io_api::Sort,
+    pub page: u64,
+    pub page_size: u64,
+    pub filter: String,
+    /// The selected row of the search results.
+    pub selected: Option<usize>,
+    pub summary_mode: SummaryMode,
+    /// The selected row of each summary list.
+    pub summary_selection: [usize; 6],
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Summary,
+            search: String::new(),
+            sort: crates_io_api::Sort::Relevance,
+            page: 1,
+            page_size: DEFAULT_PAGE_SIZE,
+            filter: String::new(),
+            selected: None,
+            summary_mode: SummaryMode::default(),
+            summary_selection: [0; 6],
+        }
+    }
+}
+
+impl Session {
+    pub fn load() -> Result<Self, String> {
+        storage::load(SESSION_FILE)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save(SESSION_FILE, self)
+    }
+}
+
+/// The mode to start in for a session that was quit in `mode`. Prompts,
+/// popups and views that depend on what was loaded before fall back to the
+/// view they were opened from.
+pub fn restored_mode(mode: Mode) -> Mode {
+    match mode {
+        Mode::Summary
+        | Mode::Search
+        | Mode::PickerShowCrateInfo
+        | Mode::PickerHideCrateInfo
+        | Mode::Project
+        | Mode::Lockfile
+        | Mode::Favorites
+        | Mode::RecentlyViewed
+        | Mode::Watchlist => mode,
+        Mode::SearchHistory => Mode::Search,
+        Mode::SavedSearches => Mode::PickerHideCrateInfo,
+        Mode::Filter
+        | Mode::FullCrateDetails
+        | Mode::DependencyTree
+        | Mode::InverseDependencies
+        | Mode::AddDependency
+        | Mode::AnnotationPrompt
+        | Mode::ExportPrompt => Mode::PickerHideCrateInfo,
+        Mode::FeatureUnification => Mode::Project,
+        Mode::FavoritesPrompt => Mode::Favorites,
+        Mode::Common | Mode::Popup | Mode::Help | Mode::Quit => Mode::Summary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_toml() {
+        let session = Session {
+            mode: Mode::PickerShowCrateInfo,
+            search: "serde".into(),
+            sort: crates_io_api::Sort::Downloads,
+            page: 3,
+            page_size: 50,
+            filter: "json".into(),
+            selected: Some(4),
+            summary_mode: SummaryMode::JustUpdated,
+            summary_selection: [1, 2, 3, 0, 0, 5],
+        };
+        let toml = toml::to_string(&session).unwrap();
+        assert_eq!(toml::from_str::<Session>(&toml).unwrap(), session);
+        assert_eq!(
+            toml::from_str::<Session>("search = \"tokio\"").unwrap(),
+            Session {
+                search: "tokio".into(),
+                ..Default::default()
+            }
+        );
+        assert_eq!(restored_mode(Mode::FavoritesPrompt), Mode::Favorites);
+        assert_eq!(restored_mode(Mode::Help), Mode::Summary);
+    }
+}
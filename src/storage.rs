@@ -0,0 +1,72 @@
+use std::path::{Path, PathBuf};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::config;
+
+/// Reads a TOML file from the data directory, returning the default value
+/// if it doesn't exist.
+///
+/// A file that can't be parsed is moved to `<file name>.bak`, so that saving
+/// the default value in its place doesn't lose what it held.
+pub fn load<T: DeserializeOwned + Default>(file_name: &str) -> Result<T, String> {
+    read(&config::get().data_home.join(file_name))
+}
+
+fn read<T: DeserializeOwned + Default>(path: &Path) -> Result<T, String> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(T::default()),
+        Err(err) => return Err(format!("Unable to read `{}`: {err}", path.display())),
+    };
+    toml::from_str(&contents).map_err(|err| {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(".bak");
+        let backup = PathBuf::from(backup);
+        match std::fs::rename(path, &backup) {
+            Ok(()) => format!(
+                "Unable to parse `{}`, it was moved to `{}`: {err}",
+                path.display(),
+                backup.display()
+            ),
+            Err(_) => format!("Unable to parse `{}`: {err}", path.display()),
+        }
+    })
+}
+
+/// Writes a value as a TOML file in the data directory.
+pub fn save<T: Serialize>(file_name: &str, value: &T) -> Result<(), String> {
+    let path = config::get().data_home.join(file_name);
+    let contents = toml::to_string(value)
+        .map_err(|err| format!("Unable to serialize `{}`: {err}", path.display()))?;
+    std::fs::create_dir_all(&config::get().data_home)
+        .and_then(|_| std::fs::write(&path, contents))
+        .map_err(|err| format!("Unable to write `{}`: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn moves_unparsable_file_aside() {
+        let dir = std::env::temp_dir().join(format!("crates-tui-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("favorites.toml");
+
+        let missing: Result<HashMap<String, String>, _> = read(&path);
+        assert_eq!(missing, Ok(HashMap::new()));
+
+        std::fs::write(&path, "serde = \"derive\"\n[broken").unwrap();
+        let broken: Result<HashMap<String, String>, _> = read(&path);
+        assert!(broken.unwrap_err().contains("it was moved to"));
+        assert!(!path.exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("favorites.toml.bak")).unwrap(),
+            "serde = \"derive\"\n[broken"
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,581 @@
+use std::{
+    collections::BTreeMap,
+    io::{ErrorKind, Write},
+};
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{eyre, Result};
+use itertools::Itertools;
+use num_format::{Locale, ToFormattedString};
+use serde::Serialize;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    advisory::AdvisoryDb,
+    annotations::Annotations,
+    cli::{InfoArgs, SearchArgs},
+    config,
+    crates_io_api_helper::{self, CrateOverview},
+    export::{Export, ExportFormat},
+    license::{LicensePolicy, LicenseVerdict},
+};
+
+/// How the commands that don't start the TUI print their results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns for reading in a terminal.
+    Table,
+    Json,
+    Csv,
+}
+
+/// Runs `crates-tui search`, printing a page of search results to stdout.
+pub async fn search(args: &SearchArgs) -> Result<()> {
+    let (crates, total) = crates_io_api_helper::search_crates(
+        &args.query,
+        args.page.page,
+        args.page.per_page,
+        args.page.sort.clone(),
+    )
+    .await
+    .map_err(|err| eyre!(err))?;
+    match args.format {
+        OutputFormat::Table => {
+            let rows = crates
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.name.clone(),
+                        c.max_version.clone(),
+                        c.downloads.to_formatted_string(&Locale::en),
+                        c.updated_at.format("%Y-%m-%d").to_string(),
+                        c.description
+                            .as_deref()
+                            .unwrap_or_default()
+                            .split_whitespace()
+                            .join(" "),
+                    ]
+                })
+                .collect_vec();
+            write_stdout(&text_table(
+                &["NAME", "VERSION", "DOWNLOADS", "UPDATED", "DESCRIPTION"],
+                &rows,
+            ))?;
+            let first = (args.page.page - 1) * args.page.per_page;
+            eprintln!(
+                "Showing {}-{} of {total} crates",
+                (first + 1).min(total),
+                first + crates.len() as u64
+            );
+        }
+        OutputFormat::Json => {
+            write_stdout(&Export::search_results(&crates).render(ExportFormat::Json))?
+        }
+        OutputFormat::Csv => {
+            write_stdout(&Export::search_results(&crates).render(ExportFormat::Csv))?
+        }
+    }
+    Ok(())
+}
+
+/// Writes `text` to stdout. A reader that went away, like `head` at the end of
+/// a pipeline, is not an error.
+pub fn write_stdout(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    match write!(stdout, "{text}").and_then(|()| stdout.flush()) {
+        Err(err) if err.kind() != ErrorKind::BrokenPipe => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct AdvisoryInfo {
+    id: String,
+    title: String,
+    severity: String,
+    patched: String,
+    url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct DependencyInfo {
+    name: String,
+    req: String,
+    kind: String,
+    optional: bool,
+    default_features: bool,
+    features: Vec<String>,
+    target: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct VersionInfo {
+    num: String,
+    created_at: DateTime<Utc>,
+    yanked: bool,
+    rust_version: Option<String>,
+    license: Option<String>,
+    downloads: u64,
+}
+
+/// What `crates-tui info` prints about a crate version: the fields of the
+/// crate info panel followed by the versions, dependencies, features and
+/// owners.
+#[derive(Debug, Serialize)]
+struct CrateInfoOutput {
+    name: String,
+    version: String,
+    description: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    max_version: String,
+    max_stable_version: Option<String>,
+    yanked: bool,
+    rust_version: Option<String>,
+    license: Option<String>,
+    /// Whether the license is allowed by the license policy, if there is one.
+    license_policy: Option<LicenseVerdict>,
+    /// The advisories affecting the version, if an advisory database is
+    /// configured.
+    advisories: Option<Vec<AdvisoryInfo>>,
+    health_score: u8,
+    failed_health_checks: Vec<String>,
+    downloads: u64,
+    recent_downloads: Option<u64>,
+    keywords: Vec<String>,
+    categories: Vec<String>,
+    homepage: Option<String>,
+    documentation: Option<String>,
+    repository: Option<String>,
+    tags: Vec<String>,
+    note: String,
+    owners: Vec<String>,
+    features: BTreeMap<String, Vec<String>>,
+    dependencies: Vec<DependencyInfo>,
+    versions: Vec<VersionInfo>,
+}
+
+impl CrateInfoOutput {
+    fn new(overview: CrateOverview, annotations: &Annotations) -> Result<Self> {
+        let CrateOverview {
+            crate_response,
+            version,
+            owners,
+            dependencies,
+            health,
+        } = overview;
+        let crate_data = crate_response.crate_data;
+        let license_policy = LicensePolicy::load(
+            &config::get().policy.licenses,
+            &std::env::current_dir().unwrap_or_default(),
+        )
+        .map(|policy| policy.evaluate(version.license.as_deref()));
+        // an advisory database that can't be read must not pass for one
+        // without advisories
+        let advisories = match config::get().advisories.db_path {
+            Some(ref path) => {
+                let db = AdvisoryDb::load(path.as_ref())
+                    .map_err(|err| eyre!("Unable to load the advisory database: {err}"))?;
+                let affecting = semver::Version::parse(&version.num)
+                    .map(|v| db.affecting(&crate_data.name, &v))
+                    .unwrap_or_default();
+                Some(
+                    affecting
+                        .iter()
+                        .map(|a| AdvisoryInfo {
+                            id: a.id.clone(),
+                            title: a.title.clone(),
+                            severity: a.severity_label(),
+                            patched: a.patched_versions(),
+                            url: a.link(),
+                        })
+                        .collect(),
+                )
+            }
+            None => None,
+        };
+        let annotation = annotations
+            .get(&crate_data.name)
+            .cloned()
+            .unwrap_or_default();
+        Ok(Self {
+            version: version.num.clone(),
+            description: crate_data.description,
+            created_at: crate_data.created_at,
+            updated_at: crate_data.updated_at,
+            max_version: crate_data.max_version,
+            max_stable_version: crate_data.max_stable_version,
+            yanked: version.yanked,
+            rust_version: version.rust_version.clone(),
+            license: version.license.clone(),
+            license_policy,
+            advisories,
+            health_score: health.score(),
+            failed_health_checks: health
+                .failed()
+                .map(|c| format!("{}: {}", c.kind, c.detail))
+                .collect(),
+            downloads: crate_data.downloads,
+            recent_downloads: crate_data.recent_downloads,
+            keywords: crate_response
+                .keywords
+                .into_iter()
+                .map(|k| k.keyword)
+                .collect(),
+            categories: crate_response
+                .categories
+                .into_iter()
+                .map(|c| c.category)
+                .collect(),
+            homepage: crate_data.homepage,
+            documentation: crate_data.documentation,
+            repository: crate_data.repository,
+            tags: annotation.tags,
+            note: annotation.note,
+            owners: owners.into_iter().map(|o| o.login).collect(),
+            features: version.features.into_iter().collect(),
+            dependencies: dependencies
+                .into_iter()
+                .map(|d| DependencyInfo {
+                    name: d.crate_id,
+                    req: d.req,
+                    kind: d.kind,
+                    optional: d.optional,
+                    default_features: d.default_features,
+                    features: d.features,
+                    target: d.target,
+                })
+                .sorted_by(|a, b| (&a.kind, &a.name).cmp(&(&b.kind, &b.name)))
+                .collect(),
+            versions: crate_response
+                .versions
+                .into_iter()
+                .map(|v| VersionInfo {
+                    num: v.num,
+                    created_at: v.created_at,
+                    yanked: v.yanked,
+                    rust_version: v.rust_version,
+                    license: v.license,
+                    downloads: v.downloads,
+                })
+                .collect(),
+            name: crate_data.name,
+        })
+    }
+
+    fn to_text(&self) -> String {
+        let unspecified = || "unspecified".to_string();
+        let date = |d: &DateTime<Utc>| d.format("%Y-%m-%d %H:%M:%S").to_string();
+        let mut fields = vec![
+            ("Name", self.name.clone()),
+            (
+                "Version",
+                if self.yanked {
+                    format!("{} (yanked)", self.version)
+                } else {
+                    self.version.clone()
+                },
+            ),
+            ("Created At", date(&self.created_at)),
+            ("Updated At", date(&self.updated_at)),
+            ("Max Version", self.max_version.clone()),
+        ];
+        if let Some(ref max_stable_version) = self.max_stable_version {
+            fields.push(("Max Stable Version", max_stable_version.clone()));
+        }
+        if !self.tags.is_empty() {
+            fields.push(("Tags", self.tags.join(", ")));
+        }
+        if !self.note.is_empty() {
+            fields.push(("Note", self.note.clone()));
+        }
+        fields.push((
+            "Rust Version",
+            self.rust_version.clone().unwrap_or_else(unspecified),
+        ));
+        let license = self.license.clone().unwrap_or_else(unspecified);
+        fields.push((
+            "License",
+            match self.license_policy {
+                Some(LicenseVerdict::Allowed) => format!("{license} (allowed by policy)"),
+                Some(LicenseVerdict::Violation(ref explanation)) => {
+                    format!("{license} ({explanation})")
+                }
+                None => license,
+            },
+        ));
+        if let Some(ref advisories) = self.advisories {
+            fields.push((
+                "Advisories",
+                if advisories.is_empty() {
+                    "none".into()
+                } else {
+                    advisories
+                        .iter()
+                        .map(|a| {
+                            format!(
+                                "{} {} ({}, patched: {})",
+                                a.id, a.title, a.severity, a.patched
+                            )
+                        })
+                        .join("; ")
+                },
+            ));
+        }
+        fields.push(("Health", format!("{}%", self.health_score)));
+        if !self.failed_health_checks.is_empty() {
+            fields.push(("Failed Checks", self.failed_health_checks.join("; ")));
+        }
+        fields.push(("Keywords", self.keywords.join(", ")));
+        fields.push(("Categories", self.categories.join(", ")));
+        if let Some(ref description) = self.description {
+            fields.push(("Description", description.split_whitespace().join(" ")));
+        }
+        for (label, url) in [
+            ("Homepage", &self.homepage),
+            ("Documentation", &self.documentation),
+            ("Repository", &self.repository),
+        ] {
+            if let Some(url) = url {
+                fields.push((label, url.clone()));
+            }
+        }
+        fields.push(("Downloads", self.downloads.to_formatted_string(&Locale::en)));
+        if let Some(recent_downloads) = self.recent_downloads {
+            fields.push((
+                "Recent Downloads",
+                recent_downloads.to_formatted_string(&Locale::en),
+            ));
+        }
+        fields.push(("Owners", self.owners.join(", ")));
+
+        let width = fields
+            .iter()
+            .map(|(label, _)| label.width())
+            .max()
+            .unwrap_or_default();
+        let mut text: String = fields
+            .iter()
+            .map(|(label, value)| format!("{label:width$}  {value}\n"))
+            .collect();
+
+        text += "\nFeatures\n";
+        text += &self
+            .features
+            .iter()
+            .map(|(name, enables)| format!("  {name} = [{}]\n", enables.iter().join(", ")))
+            .collect::<String>();
+
+        text += "\nDependencies\n";
+        text += &text_table(
+            &["NAME", "REQ", "KIND", "OPTIONAL", "TARGET"],
+            &self
+                .dependencies
+                .iter()
+                .map(|d| {
+                    vec![
+                        d.name.clone(),
+                        d.req.clone(),
+                        d.kind.clone(),
+                        if d.optional { "yes" } else { "" }.into(),
+                        d.target.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect_vec(),
+        );
+
+        text += "\nVersions\n";
+        text += &text_table(
+            &[
+                "VERSION",
+                "RELEASED",
+                "RUST VERSION",
+                "LICENSE",
+                "DOWNLOADS",
+                "YANKED",
+            ],
+            &self
+                .versions
+                .iter()
+                .map(|v| {
+                    vec![
+                        v.num.clone(),
+                        v.created_at.format("%Y-%m-%d").to_string(),
+                        v.rust_version.clone().unwrap_or_default(),
+                        v.license.clone().unwrap_or_default(),
+                        v.downloads.to_formatted_string(&Locale::en),
+                        if v.yanked { "yes" } else { "" }.into(),
+                    ]
+                })
+                .collect_vec(),
+        );
+        text
+    }
+}
+
+/// Runs `crates-tui info`, printing a crate version to stdout.
+pub async fn info(args: &InfoArgs) -> Result<()> {
+    let (name, version) = match args.spec.split_once('@') {
+        Some((name, version)) => (name, Some(version)),
+        None => (args.spec.as_str(), None),
+    };
+    let overview = crates_io_api_helper::request_crate_overview(name, version)
+        .await
+        .map_err(|err| eyre!(err))?;
+    let annotations = Annotations::load().map_err(|err| eyre!(err))?;
+    let output = CrateInfoOutput::new(overview, &annotations)?;
+    if args.json {
+        write_stdout(&format!("{}\n", serde_json::to_string_pretty(&output)?))
+    } else {
+        write_stdout(&output.to_text())
+    }
+}
+
+/// Lays out rows in columns separated by two spaces. The last column is not
+/// padded so that long values don't leave trailing whitespace.
+fn text_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let widths = (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].width())
+                .chain(std::iter::once(headers[i].width()))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect_vec();
+    let line = |cells: Vec<&str>| {
+        let last = cells.len() - 1;
+        cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                if i == last {
+                    cell.to_string()
+                } else {
+                    format!("{cell}{}", " ".repeat(widths[i] - cell.width()))
+                }
+            })
+            .join("  ")
+            .trim_end()
+            .to_string()
+            + "\n"
+    };
+    std::iter::once(line(headers.to_vec()))
+        .chain(
+            rows.iter()
+                .map(|row| line(row.iter().map(String::as_str).collect())),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aligns_columns() {
+        let rows = vec![
+            vec!["serde".into(), "1.0.200".into(), "Serialization".into()],
+            vec!["clap".into(), "4.5.4".into(), "".into()],
+        ];
+        assert_eq!(
+            text_table(&["NAME", "VERSION", "DESCRIPTION"], &rows),
+            "NAME   VERSION  DESCRIPTION\n\
+             serde  1.0.200  Serialization\n\
+             clap   4.5.4\n"
+        );
+    }
+
+    #[test]
+    fn writes_crate_info_as_text() {
+        let date = "2024-05-01T12:30:00Z".parse().unwrap();
+        let output = CrateInfoOutput {
+            name: "serde".into(),
+            version: "1.0.200".into(),
+            description: Some("A generic serialization/deserialization\n framework".into()),
+            created_at: date,
+            updated_at: date,
+            max_version: "1.0.200".into(),
+            max_stable_version: None,
+            yanked: false,
+            rust_version: None,
+            license: Some("MIT OR Apache-2.0".into()),
+            license_policy: Some(LicenseVerdict::Allowed),
+            advisories: Some(vec![]),
+            health_score: 80,
+            failed_health_checks: vec!["Documentation: missing".into()],
+            downloads: 1234567,
+            recent_downloads: None,
+            keywords: vec!["serde".into(), "serialization".into()],
+            categories: vec!["encoding".into()],
+            homepage: None,
+            documentation: None,
+            repository: Some("https://github.com/serde-rs/serde".into()),
+            tags: vec!["core".into()],
+            note: String::new(),
+            owners: vec!["dtolnay".into()],
+            features: BTreeMap::from([("derive".into(), vec!["serde_derive".into()])]),
+            dependencies: vec![DependencyInfo {
+                name: "serde_derive".into(),
+                req: "=1.0.200".into(),
+                kind: "normal".into(),
+                optional: true,
+                default_features: true,
+                features: vec![],
+                target: None,
+            }],
+            versions: vec![VersionInfo {
+                num: "1.0.200".into(),
+                created_at: date,
+                yanked: false,
+                rust_version: Some("1.31".into()),
+                license: Some("MIT OR Apache-2.0".into()),
+                downloads: 42,
+            }],
+        };
+        assert_eq!(
+            output.to_text(),
+            "Name           serde\n\
+             Version        1.0.200\n\
+             Created At     2024-05-01 12:30:00\n\
+             Updated At     2024-05-01 12:30:00\n\
+             Max Version    1.0.200\n\
+             Tags           core\n\
+             Rust Version   unspecified\n\
+             License        MIT OR Apache-2.0 (allowed by policy)\n\
+             Advisories     none\n\
+             Health         80%\n\
+             Failed Checks  Documentation: missing\n\
+             Keywords       serde, serialization\n\
+             Categories     encoding\n\
+             Description    A generic serialization/deserialization framework\n\
+             Repository     https://github.com/serde-rs/serde\n\
+             Downloads      1,234,567\n\
+             Owners         dtolnay\n\
+             \n\
+             Features\n  \
+             derive = [serde_derive]\n\
+             \n\
+             Dependencies\n\
+             NAME          REQ       KIND    OPTIONAL  TARGET\n\
+             serde_derive  =1.0.200  normal  yes\n\
+             \n\
+             Versions\n\
+             VERSION  RELEASED    RUST VERSION  LICENSE            DOWNLOADS  YANKED\n\
+             1.0.200  2024-05-01  1.31          MIT OR Apache-2.0  42\n"
+        );
+
+        let output = CrateInfoOutput {
+            license_policy: Some(LicenseVerdict::Violation("GPL-3.0 is denied".into())),
+            ..output
+        };
+        assert!(output
+            .to_text()
+            .contains("License        MIT OR Apache-2.0 (GPL-3.0 is denied)\n"));
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(
+            json["license_policy"],
+            serde_json::json!({ "verdict": "violation", "explanation": "GPL-3.0 is denied" })
+        );
+    }
+}
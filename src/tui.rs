@@ -1,5 +1,5 @@
 use std::{
-    io::{stdout, Stdout},
+    io::{stdout, IsTerminal, Stdout},
     ops::{Deref, DerefMut},
 };
 
@@ -67,3 +67,118 @@ impl Drop for Tui {
         restore_backend().unwrap();
     }
 }
+
+/// A terminal drawing in a few lines below the cursor instead of the
+/// alternate screen.
+///
+/// When stdout is redirected, as in `$(crates-tui pick)`, it is pointed at
+/// the terminal until the `InlineTui` is dropped, so that only what is printed
+/// afterwards ends up in the redirect.
+pub struct InlineTui {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    redirected_stdout: Option<RedirectedStdout>,
+}
+
+impl InlineTui {
+    pub fn init(height: u16) -> Result<Self> {
+        let redirected_stdout = if stdout().is_terminal() {
+            None
+        } else {
+            Some(RedirectedStdout::to_terminal()?)
+        };
+        enable_raw_mode()?;
+        // on failure, stdout is pointed back at the redirect as
+        // `redirected_stdout` is dropped
+        let terminal = Terminal::with_options(
+            CrosstermBackend::new(stdout()),
+            TerminalOptions {
+                viewport: Viewport::Inline(height),
+            },
+        )
+        .inspect_err(|_| {
+            let _ = disable_raw_mode();
+        })?;
+        Ok(Self {
+            terminal,
+            redirected_stdout,
+        })
+    }
+}
+
+impl Deref for InlineTui {
+    type Target = Terminal<CrosstermBackend<Stdout>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.terminal
+    }
+}
+
+impl DerefMut for InlineTui {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.terminal
+    }
+}
+
+impl Drop for InlineTui {
+    fn drop(&mut self) {
+        // leaves the cursor where the viewport started, like fzf does
+        let _ = self.terminal.clear();
+        let _ = self.terminal.show_cursor();
+        let _ = Backend::flush(self.terminal.backend_mut());
+        let _ = disable_raw_mode();
+        drop(self.redirected_stdout.take());
+    }
+}
+
+/// The file descriptor stdout pointed at before it was pointed at the
+/// terminal, which it points at again when this is dropped.
+#[cfg(unix)]
+struct RedirectedStdout(libc::c_int);
+
+#[cfg(unix)]
+impl RedirectedStdout {
+    fn to_terminal() -> Result<Self> {
+        use std::os::fd::AsRawFd;
+
+        let tty = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("/dev/tty")?;
+        // SAFETY: both file descriptors are open, `dup2` leaves `tty` open
+        unsafe {
+            let saved = libc::dup(libc::STDOUT_FILENO);
+            if saved < 0 {
+                return Err(std::io::Error::last_os_error().into());
+            }
+            if libc::dup2(tty.as_raw_fd(), libc::STDOUT_FILENO) < 0 {
+                let err = std::io::Error::last_os_error();
+                libc::close(saved);
+                return Err(err.into());
+            }
+            Ok(Self(saved))
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RedirectedStdout {
+    fn drop(&mut self) {
+        // SAFETY: the saved file descriptor is only closed here
+        unsafe {
+            libc::dup2(self.0, libc::STDOUT_FILENO);
+            libc::close(self.0);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+struct RedirectedStdout;
+
+#[cfg(not(unix))]
+impl RedirectedStdout {
+    fn to_terminal() -> Result<Self> {
+        Err(color_eyre::eyre::eyre!(
+            "Drawing inline needs stdout to be a terminal on this platform"
+        ))
+    }
+}
@@ -0,0 +1,220 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const WATCHLIST_FILE: &str = "watchlist.toml";
+
+/// The state of a crate on crates.io, as far as the watchlist cares.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrateSnapshot {
+    /// The newest version.
+    #[serde(default)]
+    pub version: String,
+    /// Every published version, yanked or not.
+    #[serde(default, skip_serializing)]
+    pub versions: Vec<String>,
+    #[serde(default)]
+    pub yanked: Vec<String>,
+    #[serde(default)]
+    pub owners: Vec<String>,
+}
+
+impl CrateSnapshot {
+    pub fn new(
+        crate_response: &crates_io_api::CrateResponse,
+        owners: &[crates_io_api::User],
+    ) -> Self {
+        Self {
+            version: crate_response.crate_data.max_version.clone(),
+            versions: crate_response
+                .versions
+                .iter()
+                .map(|v| v.num.clone())
+                .collect(),
+            yanked: crate_response
+                .versions
+                .iter()
+                .filter(|v| v.yanked)
+                .map(|v| v.num.clone())
+                .sorted()
+                .collect(),
+            owners: owners.iter().map(|o| o.login.clone()).sorted().collect(),
+        }
+    }
+}
+
+/// A watched crate and its state when it was last acknowledged.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchedCrate {
+    pub name: String,
+    /// `None` until the crate was fetched for the first time.
+    #[serde(default)]
+    pub acknowledged: Option<CrateSnapshot>,
+}
+
+/// What happened to a watched crate since it was last acknowledged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Changes {
+    /// The acknowledged and the newest version, if a newer one was published.
+    pub version: Option<(String, String)>,
+    /// The number of versions published since.
+    pub new_versions: usize,
+    pub yanked: Vec<String>,
+    pub owners_added: Vec<String>,
+    pub owners_removed: Vec<String>,
+}
+
+impl Changes {
+    pub fn between(acknowledged: &CrateSnapshot, current: &CrateSnapshot) -> Self {
+        let newer = |v: &str| match (
+            semver::Version::parse(v),
+            semver::Version::parse(&acknowledged.version),
+        ) {
+            (Ok(v), Ok(acknowledged)) => v > acknowledged,
+            _ => false,
+        };
+        let version = (current.version != acknowledged.version)
+            .then(|| (acknowledged.version.clone(), current.version.clone()));
+        Self {
+            version,
+            new_versions: current.versions.iter().filter(|v| newer(v)).count(),
+            yanked: current
+                .yanked
+                .iter()
+                .filter(|v| !acknowledged.yanked.contains(v))
+                .cloned()
+                .collect(),
+            owners_added: current
+                .owners
+                .iter()
+                .filter(|o| !acknowledged.owners.contains(o))
+                .cloned()
+                .collect(),
+            owners_removed: acknowledged
+                .owners
+                .iter()
+                .filter(|o| !current.owners.contains(o))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Crates whose new releases, yanks and owner changes are reported,
+/// persisted in the data directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Watchlist {
+    #[serde(default)]
+    pub crates: Vec<WatchedCrate>,
+}
+
+impl Watchlist {
+    pub fn load() -> Result<Self, String> {
+        storage::load(WATCHLIST_FILE)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save(WATCHLIST_FILE, self)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.crates.iter().any(|c| c.name == name)
+    }
+
+    /// Starts or stops watching a crate. Returns whether it is watched.
+    pub fn toggle(&mut self, name: &str) -> bool {
+        if self.contains(name) {
+            self.crates.retain(|c| c.name != name);
+            false
+        } else {
+            self.crates.push(WatchedCrate {
+                name: name.into(),
+                acknowledged: None,
+            });
+            true
+        }
+    }
+
+    /// Marks the current state of a crate as seen.
+    pub fn acknowledge(&mut self, name: &str, current: &CrateSnapshot) {
+        if let Some(watched) = self.crates.iter_mut().find(|c| c.name == name) {
+            watched.acknowledged = Some(current.clone());
+        }
+    }
+
+    /// Acknowledges the crates that were never fetched before, so that
+    /// changes are reported from the moment a crate is watched. Returns
+    /// whether any crate was acknowledged.
+    pub fn acknowledge_new<'a>(
+        &mut self,
+        current: impl Fn(&str) -> Option<&'a CrateSnapshot>,
+    ) -> bool {
+        let mut acknowledged = false;
+        for watched in self.crates.iter_mut().filter(|c| c.acknowledged.is_none()) {
+            if let Some(snapshot) = current(&watched.name) {
+                watched.acknowledged = Some(snapshot.clone());
+                acknowledged = true;
+            }
+        }
+        acknowledged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(
+        version: &str,
+        versions: &[&str],
+        yanked: &[&str],
+        owners: &[&str],
+    ) -> CrateSnapshot {
+        let strings = |s: &[&str]| s.iter().map(|s| s.to_string()).collect();
+        CrateSnapshot {
+            version: version.into(),
+            versions: strings(versions),
+            yanked: strings(yanked),
+            owners: strings(owners),
+        }
+    }
+
+    #[test]
+    fn reports_changes_since_acknowledged() {
+        let acknowledged = snapshot("1.0.1", &["1.0.1", "1.0.0"], &[], &["alice", "bob"]);
+        let current = snapshot(
+            "1.2.0",
+            &["1.2.0", "1.1.0", "1.0.1", "1.0.0"],
+            &["1.0.0"],
+            &["alice", "carol"],
+        );
+        let changes = Changes::between(&acknowledged, &current);
+        assert_eq!(changes.version, Some(("1.0.1".into(), "1.2.0".into())));
+        assert_eq!(changes.new_versions, 2);
+        assert_eq!(changes.yanked, ["1.0.0"]);
+        assert_eq!(changes.owners_added, ["carol"]);
+        assert_eq!(changes.owners_removed, ["bob"]);
+        assert!(Changes::between(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn acknowledges_newly_watched_crates() {
+        let mut watchlist = Watchlist::default();
+        assert!(watchlist.toggle("serde"));
+        assert!(watchlist.toggle("tokio"));
+        let current = snapshot("1.0.0", &["1.0.0"], &[], &["dtolnay"]);
+        assert!(watchlist.acknowledge_new(|name| (name == "serde").then_some(&current)));
+        assert!(!watchlist.acknowledge_new(|name| (name == "serde").then_some(&current)));
+        assert_eq!(watchlist.crates[0].acknowledged, Some(current));
+        assert_eq!(watchlist.crates[1].acknowledged, None);
+        let toml = toml::to_string(&watchlist).unwrap();
+        assert!(!toml.contains("versions"));
+        assert!(!watchlist.toggle("tokio"));
+        assert!(!watchlist.contains("tokio"));
+    }
+}
@@ -1,7 +1,22 @@
+pub mod add_dependency;
+pub mod annotation_editor;
 pub mod crate_info_table;
+pub mod crate_picker;
+pub mod dependency_tree;
+pub mod export_prompt;
+pub mod favorites;
+pub mod feature_unification;
+pub mod full_crate_info;
 pub mod help;
+pub mod inverse_dependencies;
+pub mod lockfile;
 pub mod popup_message;
+pub mod project_dependencies;
+pub mod recently_viewed;
+pub mod saved_searches;
 pub mod search_filter_prompt;
+pub mod search_history;
 pub mod search_results_table;
 pub mod summary;
 pub mod tabs;
+pub mod watchlist;
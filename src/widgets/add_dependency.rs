@@ -0,0 +1,434 @@
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use itertools::Itertools;
+use ratatui::{
+    layout::{Flex, Position},
+    prelude::*,
+    widgets::{block::Title, *},
+};
+use strum::{Display, EnumCount, EnumIter, FromRepr};
+use tui_input::backend::crossterm::EventHandler;
+
+use crate::{
+    app::Mode,
+    command::Command,
+    config,
+    crates_index_helper::DependencyKind,
+    project::{self, DependencySpec, DiffLine},
+};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter, EnumCount, FromRepr)]
+pub enum AddDependencyField {
+    Member,
+    Workspace,
+    #[default]
+    Version,
+    Features,
+    #[strum(to_string = "Default Features")]
+    DefaultFeatures,
+    Optional,
+    Kind,
+    Target,
+}
+
+/// Dependency kinds in the order they are cycled through.
+const KINDS: [DependencyKind; 3] = [
+    DependencyKind::Normal,
+    DependencyKind::Dev,
+    DependencyKind::Build,
+];
+
+/// An edited manifest, shown as a diff before it is written.
+#[derive(Debug, Clone)]
+pub struct ManifestPreview {
+    pub path: PathBuf,
+    pub original: String,
+    pub updated: String,
+}
+
+/// A workspace member a dependency can be added to.
+#[derive(Debug, Clone, Default)]
+pub struct Member {
+    pub name: String,
+    pub manifest_path: PathBuf,
+}
+
+#[derive(Debug, Default)]
+pub struct AddDependencyForm {
+    pub crate_name: String,
+    pub members: Vec<Member>,
+    member: usize,
+    /// The root manifest of the workspace, if the project is a workspace.
+    pub workspace_manifest: Option<PathBuf>,
+    workspace: bool,
+    version: tui_input::Input,
+    features: tui_input::Input,
+    default_features: bool,
+    optional: bool,
+    kind: DependencyKind,
+    target: tui_input::Input,
+    focus: AddDependencyField,
+    pub preview: Option<Vec<ManifestPreview>>,
+    scroll: usize,
+    /// The mode to return to when the form is closed.
+    pub return_mode: Mode,
+    cursor_position: Option<Position>,
+}
+
+impl AddDependencyForm {
+    pub fn new(
+        crate_name: String,
+        version: String,
+        members: Vec<Member>,
+        return_mode: Mode,
+    ) -> Self {
+        Self {
+            crate_name,
+            members,
+            version: tui_input::Input::new(version),
+            default_features: true,
+            return_mode,
+            ..Default::default()
+        }
+    }
+
+    /// Selects the member with the given name, if there is one.
+    pub fn select_member(&mut self, name: &str) {
+        if let Some(i) = self.members.iter().position(|m| m.name == name) {
+            self.member = i;
+        }
+    }
+
+    /// Lets the dependency be added to `[workspace.dependencies]` of
+    /// `manifest`, which is done by default if `inherit` is set.
+    pub fn set_workspace(&mut self, manifest: Option<PathBuf>, inherit: bool) {
+        self.workspace = inherit && manifest.is_some();
+        self.workspace_manifest = manifest;
+    }
+
+    pub fn member(&self) -> Option<&Member> {
+        self.members.get(self.member)
+    }
+
+    pub fn cursor_position(&self) -> Option<Position> {
+        self.cursor_position
+    }
+
+    pub fn spec(&self) -> DependencySpec {
+        DependencySpec {
+            name: self.crate_name.clone(),
+            version: self.version.value().trim().into(),
+            features: self
+                .features
+                .value()
+                .split([',', ' '])
+                .filter(|f| !f.is_empty())
+                .map(String::from)
+                .collect(),
+            default_features: self.default_features,
+            optional: self.optional,
+            kind: self.kind,
+            target: Some(self.target.value().trim())
+                .filter(|t| !t.is_empty())
+                .map(String::from),
+            workspace: self.workspace,
+        }
+    }
+
+    /// Moves to the previous field, or scrolls the diff up.
+    pub fn previous(&mut self) {
+        if self.preview.is_some() {
+            self.scroll = self.scroll.saturating_sub(1);
+        } else {
+            let i =
+                (self.focus as usize + AddDependencyField::COUNT - 1) % AddDependencyField::COUNT;
+            self.focus = AddDependencyField::from_repr(i).unwrap_or_default();
+        }
+    }
+
+    /// Moves to the next field, or scrolls the diff down.
+    pub fn next(&mut self) {
+        if self.preview.is_some() {
+            self.scroll = self.scroll.saturating_add(1);
+        } else {
+            let i = (self.focus as usize + 1) % AddDependencyField::COUNT;
+            self.focus = AddDependencyField::from_repr(i).unwrap_or_default();
+        }
+    }
+
+    pub fn set_preview(&mut self, preview: Option<Vec<ManifestPreview>>) {
+        self.preview = preview;
+        self.scroll = 0;
+    }
+
+    /// Edits the focused field.
+    ///
+    /// Text fields take any input, while the other fields are toggled with
+    /// `Space`, `Left` or `Right`.
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if self.preview.is_some() {
+            return;
+        }
+        let toggle = matches!(
+            key.code,
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right
+        );
+        let forward = key.code != KeyCode::Left;
+        match self.focus {
+            AddDependencyField::Member if toggle && !self.members.is_empty() => {
+                let n = self.members.len();
+                let i = if forward {
+                    self.member + 1
+                } else {
+                    self.member + n - 1
+                };
+                self.member = i % n;
+            }
+            AddDependencyField::Workspace if toggle && self.workspace_manifest.is_some() => {
+                self.workspace = !self.workspace
+            }
+            AddDependencyField::Version => {
+                self.version
+                    .handle_event(&crossterm::event::Event::Key(key));
+            }
+            AddDependencyField::Features => {
+                self.features
+                    .handle_event(&crossterm::event::Event::Key(key));
+            }
+            AddDependencyField::Target => {
+                self.target.handle_event(&crossterm::event::Event::Key(key));
+            }
+            AddDependencyField::DefaultFeatures if toggle => {
+                self.default_features = !self.default_features
+            }
+            AddDependencyField::Optional if toggle => self.optional = !self.optional,
+            AddDependencyField::Kind if toggle => {
+                let i = KINDS
+                    .iter()
+                    .position(|k| *k == self.kind)
+                    .unwrap_or_default();
+                let i = if forward { i + 1 } else { i + KINDS.len() - 1 };
+                self.kind = KINDS[i % KINDS.len()];
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct AddDependencyFormWidget;
+
+impl AddDependencyFormWidget {
+    fn field_value(state: &AddDependencyForm, field: AddDependencyField) -> Line<'static> {
+        let checkbox = |checked: bool| if checked { "[x]" } else { "[ ]" };
+        match field {
+            AddDependencyField::Member => match state.member() {
+                Some(member) if state.members.len() > 1 => format!("< {} >", member.name).into(),
+                Some(member) => member.name.clone().into(),
+                None => "-".into(),
+            },
+            AddDependencyField::Workspace => match state.workspace_manifest {
+                Some(_) => Line::from(vec![
+                    checkbox(state.workspace).into(),
+                    " inherit from [workspace.dependencies]".fg(config::get().color.base03),
+                ]),
+                None => "not a workspace".fg(config::get().color.base03).into(),
+            },
+            AddDependencyField::Version => state.version.value().to_string().into(),
+            AddDependencyField::Features => state.features.value().to_string().into(),
+            AddDependencyField::DefaultFeatures => checkbox(state.default_features).into(),
+            AddDependencyField::Optional => checkbox(state.optional).into(),
+            AddDependencyField::Kind => format!("< {:?} >", state.kind).to_lowercase().into(),
+            AddDependencyField::Target => {
+                if state.target.value().is_empty() {
+                    "all targets".fg(config::get().color.base03).into()
+                } else {
+                    state.target.value().to_string().into()
+                }
+            }
+        }
+    }
+
+    fn input(state: &AddDependencyForm, field: AddDependencyField) -> Option<&tui_input::Input> {
+        match field {
+            AddDependencyField::Version => Some(&state.version),
+            AddDependencyField::Features => Some(&state.features),
+            AddDependencyField::Target => Some(&state.target),
+            _ => None,
+        }
+    }
+
+    fn render_form(&self, area: Rect, buf: &mut Buffer, state: &mut AddDependencyForm) {
+        use strum::IntoEnumIterator;
+        let label_width = AddDependencyField::iter()
+            .map(|field| field.to_string().len())
+            .max()
+            .unwrap_or_default() as u16
+            + 2;
+        let rows = Layout::vertical(
+            AddDependencyField::iter()
+                .map(|_| Constraint::Length(1))
+                .chain([Constraint::Length(1), Constraint::Fill(1)]),
+        )
+        .split(area);
+        state.cursor_position = None;
+        for (field, row) in AddDependencyField::iter().zip(rows.iter()) {
+            let focused = field == state.focus;
+            let [label, value] =
+                Layout::horizontal([Constraint::Length(label_width), Constraint::Fill(1)])
+                    .areas(*row);
+            Line::from(field.to_string())
+                .style(if focused {
+                    Style::default().fg(config::get().color.base0a).bold()
+                } else {
+                    Style::default().fg(config::get().color.base05)
+                })
+                .render(label, buf);
+            Self::field_value(state, field).render(value, buf);
+            if focused {
+                if let Some(input) = Self::input(state, field) {
+                    let cursor = (input.visual_cursor() as u16).min(value.width.saturating_sub(1));
+                    state.cursor_position = Some(Position::new(value.x + cursor, value.y));
+                }
+            }
+        }
+        let spec = state.spec();
+        let mut adds_to = vec![
+            "Adds to ".into(),
+            format!("[{}]", spec.table_name()).fg(config::get().color.base0d),
+        ];
+        if let Some(member) = state.member() {
+            adds_to.push(format!(" of {}", member.name).into());
+        }
+        if spec.workspace {
+            adds_to.push(" and ".into());
+            adds_to.push("[workspace.dependencies]".fg(config::get().color.base0d));
+        }
+        Paragraph::new(vec![
+            Line::from(adds_to),
+            Line::styled(
+                "Features are separated by commas or spaces, and target is a triple or cfg(..)",
+                config::get().color.base03,
+            ),
+        ])
+        .wrap(Wrap { trim: true })
+        .render(rows[rows.len() - 1], buf);
+    }
+
+    fn render_preview(&self, area: Rect, buf: &mut Buffer, state: &mut AddDependencyForm) {
+        let Some(ref previews) = state.preview else {
+            return;
+        };
+        let lines = previews
+            .iter()
+            .map(|preview| (preview, project::diff(&preview.original, &preview.updated)))
+            .filter(|(_, diff)| !diff.is_empty())
+            .flat_map(|(preview, diff)| {
+                let header = Line::styled(
+                    preview.path.display().to_string(),
+                    Style::default().fg(config::get().color.base0d).bold(),
+                );
+                std::iter::once(header).chain(diff.into_iter().map(Self::diff_line))
+            })
+            .collect_vec();
+        state.cursor_position = None;
+        if lines.is_empty() {
+            Line::styled(
+                "Cargo.toml already contains this dependency",
+                config::get().color.base03,
+            )
+            .render(area, buf);
+            return;
+        }
+        state.scroll = state.scroll.min(lines.len().saturating_sub(1));
+        Paragraph::new(lines)
+            .scroll((state.scroll as u16, 0))
+            .render(area, buf);
+    }
+
+    fn diff_line(line: DiffLine) -> Line<'static> {
+        match line {
+            DiffLine::Context(line) => {
+                Line::styled(format!("  {line}"), config::get().color.base03)
+            }
+            DiffLine::Removed(line) => {
+                Line::styled(format!("- {line}"), config::get().color.base08)
+            }
+            DiffLine::Added(line) => Line::styled(format!("+ {line}"), config::get().color.base0b),
+        }
+    }
+}
+
+impl StatefulWidget for AddDependencyFormWidget {
+    type State = AddDependencyForm;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let [center] = Layout::horizontal([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [center] = Layout::vertical([Constraint::Length(18)])
+            .flex(Flex::Center)
+            .areas(center);
+
+        let key = |command| {
+            config::get()
+                .key_bindings
+                .get_config_for_command(Mode::AddDependency, command)
+                .into_iter()
+                .next()
+                .unwrap_or_default()
+        };
+        let submit = key(Command::SubmitAddDependency);
+        let cancel = key(Command::CancelAddDependency);
+        let (title, instruction) = if state.preview.is_some() {
+            (
+                format!(
+                    " Add {} to {} ",
+                    state.crate_name,
+                    state.member().map(|m| m.name.as_str()).unwrap_or_default()
+                ),
+                vec![
+                    submit.bold(),
+                    " to write, ".into(),
+                    cancel.bold(),
+                    " to go back ".into(),
+                ],
+            )
+        } else {
+            (
+                format!(" Add {} ", state.crate_name),
+                vec![
+                    key(Command::ScrollDown).bold(),
+                    " next field, ".into(),
+                    "Space".bold(),
+                    " toggle, ".into(),
+                    submit.bold(),
+                    " preview, ".into(),
+                    cancel.bold(),
+                    " cancel ".into(),
+                ],
+            )
+        };
+        let block = Block::bordered()
+            .border_style(config::get().color.base0a)
+            .title(title)
+            .title_style(Style::default().fg(config::get().color.base0d).bold())
+            .title(
+                Title::from(instruction)
+                    .position(block::Position::Bottom)
+                    .alignment(Alignment::Right),
+            );
+        let inner = block.inner(center).inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        Clear.render(center, buf);
+        block.bg(config::get().color.base00).render(center, buf);
+
+        if state.preview.is_some() {
+            self.render_preview(inner, buf, state);
+        } else {
+            self.render_form(inner, buf, state);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use ratatui::{
+    layout::{Flex, Position},
+    prelude::*,
+    widgets::{block::Title, *},
+};
+use strum::Display;
+
+use crate::{annotations::Annotations, app::Mode, config};
+
+/// Which part of a crate annotation is edited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display)]
+pub enum AnnotationField {
+    #[default]
+    #[strum(to_string = "Note")]
+    Note,
+    #[strum(to_string = "Tags")]
+    Tags,
+}
+
+/// The personal notes and tags of every crate, and the state of the prompt
+/// editing the ones of a crate.
+#[derive(Debug, Default)]
+pub struct AnnotationEditor {
+    pub annotations: Annotations,
+    /// The crate being annotated.
+    pub crate_name: String,
+    pub field: AnnotationField,
+    pub input: tui_input::Input,
+    /// The mode to return to when the prompt is closed.
+    pub return_mode: Mode,
+    cursor_position: Option<Position>,
+}
+
+impl AnnotationEditor {
+    pub fn new(annotations: Annotations) -> Self {
+        Self {
+            annotations,
+            ..Default::default()
+        }
+    }
+
+    /// Starts editing the note or the tags of a crate.
+    pub fn start(&mut self, crate_name: &str, field: AnnotationField, return_mode: Mode) {
+        let annotation = self
+            .annotations
+            .get(crate_name)
+            .cloned()
+            .unwrap_or_default();
+        let value = match field {
+            AnnotationField::Note => annotation.note,
+            AnnotationField::Tags => annotation.tags.join(", "),
+        };
+        self.crate_name = crate_name.into();
+        self.field = field;
+        self.input = tui_input::Input::new(value);
+        self.return_mode = return_mode;
+    }
+
+    /// Stores the edited note or tags.
+    pub fn submit(&mut self) {
+        let value = self.input.value();
+        match self.field {
+            AnnotationField::Note => self.annotations.set_note(&self.crate_name, value),
+            AnnotationField::Tags => self.annotations.set_tags(&self.crate_name, value),
+        }
+    }
+
+    pub fn cursor_position(&self) -> Option<Position> {
+        self.cursor_position
+    }
+}
+
+/// A prompt over the current view editing the note or tags of a crate.
+pub struct AnnotationEditorWidget;
+
+impl StatefulWidget for AnnotationEditorWidget {
+    type State = AnnotationEditor;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let [center] = Layout::horizontal([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [center] = Layout::vertical([Constraint::Length(5)])
+            .flex(Flex::Center)
+            .areas(center);
+        let hint = match state.field {
+            AnnotationField::Note => "",
+            AnnotationField::Tags => " separated by commas",
+        };
+        let block = Block::bordered()
+            .border_style(config::get().color.base0a)
+            .title(format!(" {} for {}{hint} ", state.field, state.crate_name))
+            .title_style(Style::default().fg(config::get().color.base0d).bold())
+            .title(
+                Title::from(vec![
+                    "Enter".bold(),
+                    " to save, ".into(),
+                    "Esc".bold(),
+                    " to cancel ".into(),
+                ])
+                .position(block::Position::Bottom)
+                .alignment(Alignment::Right),
+            );
+        let inner = block.inner(center).inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        Clear.render(center, buf);
+        block.bg(config::get().color.base00).render(center, buf);
+
+        let scroll = state
+            .input
+            .visual_cursor()
+            .saturating_sub(inner.width.saturating_sub(1) as usize);
+        Paragraph::new(state.input.value())
+            .scroll((0, scroll as u16))
+            .render(inner, buf);
+        let cursor = (state.input.visual_cursor() - scroll) as u16;
+        state.cursor_position = Some(Position::new(inner.x + cursor, inner.y));
+    }
+}
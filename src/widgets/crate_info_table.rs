@@ -1,7 +1,14 @@
 use itertools::Itertools;
 use ratatui::{prelude::*, widgets::*};
 
-use crate::config;
+use crate::{
+    advisory::{Advisory, Severity},
+    annotations::Annotation,
+    config,
+    health::HealthReport,
+    license::LicenseVerdict,
+    rust_version::Compatibility,
+};
 
 #[derive(Debug, Default)]
 pub struct CrateInfo {
@@ -28,12 +35,125 @@ impl CrateInfo {
 
 pub struct CrateInfoTableWidget {
     crate_info: crates_io_api::CrateResponse,
+    health: Option<HealthReport>,
+    compatibility: Option<(semver::Version, Compatibility)>,
+    license_verdict: Option<LicenseVerdict>,
+    advisories: Option<Vec<Advisory>>,
+    annotation: Option<Annotation>,
 }
 
 impl CrateInfoTableWidget {
-    pub fn new(crate_info: crates_io_api::CrateResponse) -> Self {
-        Self { crate_info }
+    pub fn new(crate_info: crates_io_api::CrateResponse, health: Option<HealthReport>) -> Self {
+        Self {
+            crate_info,
+            health,
+            compatibility: None,
+            license_verdict: None,
+            advisories: None,
+            annotation: None,
+        }
+    }
+
+    /// Shows how the crate works with the given toolchain.
+    pub fn compatibility(
+        mut self,
+        compatibility: Option<(semver::Version, Compatibility)>,
+    ) -> Self {
+        self.compatibility = compatibility;
+        self
     }
+
+    /// Shows whether the license of the latest version passes the license
+    /// policy.
+    pub fn license_verdict(mut self, license_verdict: Option<LicenseVerdict>) -> Self {
+        self.license_verdict = license_verdict;
+        self
+    }
+
+    /// Lists the security advisories of the crate, `None` when no advisory
+    /// database is configured.
+    pub fn advisories(mut self, advisories: Option<Vec<Advisory>>) -> Self {
+        self.advisories = advisories;
+        self
+    }
+
+    /// Shows the personal note and tags of the crate, if any.
+    pub fn annotation(mut self, annotation: Option<Annotation>) -> Self {
+        self.annotation = annotation;
+        self
+    }
+}
+
+/// The tags of a crate, colored by how they are commonly used.
+pub fn tags_line(tags: &[String]) -> Line<'static> {
+    let spans = tags.iter().map(|tag| {
+        let color = match tag.as_str() {
+            "approved" => config::get().color.base0b,
+            "avoid" => config::get().color.base08,
+            "evaluate" => config::get().color.base0a,
+            _ => config::get().color.base0d,
+        };
+        format!("#{tag}").fg(color)
+    });
+    Line::from(Itertools::intersperse(spans, " ".into()).collect_vec())
+}
+
+/// Color used to display a health score.
+pub fn health_color(score: u8) -> Color {
+    match score {
+        80.. => config::get().color.base0b,
+        50..=79 => config::get().color.base09,
+        _ => config::get().color.base08,
+    }
+}
+
+/// Color used to display an advisory.
+pub fn advisory_color(advisory: &Advisory) -> Color {
+    match advisory.severity() {
+        Some(Severity::Critical | Severity::High) => config::get().color.base08,
+        Some(Severity::Medium) => config::get().color.base09,
+        Some(_) => config::get().color.base0a,
+        None => config::get().color.base0e,
+    }
+}
+
+/// Two lines per advisory with its id, severity, title, patched versions and
+/// link. Advisories that don't affect `version` are dimmed.
+pub fn advisory_lines(
+    advisories: &[Advisory],
+    version: Option<&semver::Version>,
+) -> Vec<Line<'static>> {
+    advisories
+        .iter()
+        .flat_map(|advisory| {
+            let affected = version.is_none_or(|v| advisory.affects(v));
+            let color = if affected {
+                advisory_color(advisory)
+            } else {
+                config::get().color.base03
+            };
+            let mut heading = vec![
+                format!("\u{26a0} {} ", advisory.id).fg(color).bold(),
+                format!("({}) ", advisory.severity_label()).fg(color),
+                advisory.title.clone().into(),
+            ];
+            if let Some(version) = version.filter(|_| !affected) {
+                heading
+                    .push(format!(" (v{version} is not affected)").fg(config::get().color.base03));
+            }
+            [
+                Line::from(heading),
+                Line::styled(
+                    format!(
+                        "  patched: {} \u{00b7} {}",
+                        advisory.patched_versions(),
+                        advisory.link()
+                    ),
+                    config::get().color.base03,
+                ),
+            ]
+        })
+        .collect()
 }
 
 impl StatefulWidget for CrateInfoTableWidget {
@@ -64,6 +184,135 @@ impl StatefulWidget for CrateInfoTableWidget {
             Row::new(cells)
         })
         .collect_vec();
+
+        if let Some(ref annotation) = self.annotation {
+            if !annotation.tags.is_empty() {
+                rows.push(Row::new(vec![
+                    Cell::from("Tags"),
+                    Cell::from(tags_line(&annotation.tags)),
+                ]));
+            }
+            if !annotation.note.is_empty() {
+                let note = textwrap::wrap(&annotation.note, (area.width as f64 * 0.75) as usize)
+                    .iter()
+                    .map(|s| Line::styled(s.to_string(), config::get().color.base0e))
+                    .collect_vec();
+                let height = note.len();
+                rows.push(
+                    Row::new(vec![Cell::from("Note"), Cell::from(Text::from(note))])
+                        .height(height as u16),
+                );
+            }
+        }
+
+        let latest = ci
+            .versions
+            .iter()
+            .find(|v| v.num == ci.crate_data.max_version);
+        let rust_version = latest.and_then(|v| v.rust_version.clone());
+        let mut msrv = vec![match rust_version {
+            Some(rust_version) => Span::from(rust_version),
+            None => "unspecified".fg(config::get().color.base03),
+        }];
+        match self.compatibility {
+            Some((ref toolchain, Compatibility::Older(ref version))) => msrv.push(
+                format!(" (newest compatible with {toolchain}: {version})")
+                    .fg(config::get().color.base09),
+            ),
+            Some((ref toolchain, Compatibility::Incompatible)) => msrv.push(
+                format!(" (no version compatible with {toolchain})").fg(config::get().color.base08),
+            ),
+            _ => {}
+        }
+        rows.push(Row::new(vec![
+            Cell::from("Rust Version"),
+            Cell::from(Line::from(msrv)),
+        ]));
+
+        let mut license = vec![Line::from(match latest.and_then(|v| v.license.clone()) {
+            Some(license) => Span::from(license),
+            None => "unspecified".fg(config::get().color.base03),
+        })];
+        match self.license_verdict {
+            Some(LicenseVerdict::Allowed) => license[0]
+                .spans
+                .push(" \u{2713} allowed by policy".fg(config::get().color.base0b)),
+            Some(LicenseVerdict::Violation(ref explanation)) => license.extend(
+                textwrap::wrap(
+                    &format!("\u{2717} {explanation}"),
+                    (area.width as f64 * 0.75) as usize,
+                )
+                .iter()
+                .map(|s| Line::styled(s.to_string(), config::get().color.base08)),
+            ),
+            None => {}
+        }
+        let height = license.len();
+        rows.push(
+            Row::new(vec![Cell::from("License"), Cell::from(Text::from(license))])
+                .height(height as u16),
+        );
+
+        if let Some(ref advisories) = self.advisories {
+            let max_version = semver::Version::parse(&ci.crate_data.max_version).ok();
+            let lines = if advisories.is_empty() {
+                vec![Line::styled("none", config::get().color.base03)]
+            } else {
+                advisory_lines(advisories, max_version.as_ref())
+            };
+            let height = lines.len();
+            rows.push(
+                Row::new(vec![
+                    Cell::from("Advisories"),
+                    Cell::from(Text::from(lines)),
+                ])
+                .height(height as u16),
+            );
+        }
+
+        match self.health {
+            Some(ref health) => {
+                let known = health.checks.iter().filter(|c| c.passed.is_some()).count();
+                let passed = health
+                    .checks
+                    .iter()
+                    .filter(|c| c.passed == Some(true))
+                    .count();
+                rows.push(Row::new(vec![
+                    Cell::from("Health"),
+                    Cell::from(Line::from(vec![
+                        format!("{}%", health.score())
+                            .fg(health_color(health.score()))
+                            .bold(),
+                        format!(" ({passed}/{known} checks passed)").into(),
+                    ])),
+                ]));
+                let failed = health
+                    .failed()
+                    .map(|c| {
+                        Line::from(vec![
+                            "\u{2717} ".fg(config::get().color.base08),
+                            format!("{}: {}", c.kind, c.detail).into(),
+                        ])
+                    })
+                    .collect_vec();
+                if !failed.is_empty() {
+                    let height = failed.len();
+                    rows.push(
+                        Row::new(vec![
+                            Cell::from("Failed Checks"),
+                            Cell::from(Text::from(failed)),
+                        ])
+                        .height(height as u16),
+                    );
+                }
+            }
+            None => rows.push(Row::new(vec![
+                Cell::from("Health"),
+                Cell::from("loading...".fg(config::get().color.base03)),
+            ])),
+        }
+
         let keywords = self
             .crate_info
             .keywords
@@ -0,0 +1,164 @@
+use itertools::Itertools;
+use num_format::{Locale, ToFormattedString};
+use ratatui::{layout::Position, prelude::*, widgets::*};
+
+use crate::config;
+
+/// The query, results and selection of `crates-tui pick`.
+#[derive(Debug, Default)]
+pub struct CratePicker {
+    pub input: tui_input::Input,
+    pub crates: Vec<crates_io_api::Crate>,
+    /// The number of crates matching the query on crates.io.
+    pub total: u64,
+    /// Whether a search for the query is pending or running.
+    pub loading: bool,
+    /// Why the last search failed.
+    pub error: Option<String>,
+    table_state: TableState,
+    cursor_position: Option<Position>,
+}
+
+impl CratePicker {
+    /// Starts with `query`, whose results are on their way.
+    pub fn new(query: String) -> Self {
+        Self {
+            input: tui_input::Input::new(query),
+            loading: true,
+            ..Default::default()
+        }
+    }
+
+    /// Shows the results of a search, selecting the first one.
+    pub fn set_results(&mut self, crates: Vec<crates_io_api::Crate>, total: u64) {
+        self.table_state.select((!crates.is_empty()).then_some(0));
+        self.crates = crates;
+        self.total = total;
+        self.loading = false;
+        self.error = None;
+    }
+
+    pub fn selected(&self) -> Option<&crates_io_api::Crate> {
+        self.table_state.selected().and_then(|i| self.crates.get(i))
+    }
+
+    pub fn scroll_previous(&mut self) {
+        if let Some(i) = self.table_state.selected() {
+            self.table_state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    pub fn scroll_next(&mut self) {
+        if let Some(i) = self.table_state.selected() {
+            self.table_state
+                .select(Some((i + 1).min(self.crates.len().saturating_sub(1))));
+        }
+    }
+
+    pub fn cursor_position(&self) -> Option<Position> {
+        self.cursor_position
+    }
+}
+
+/// A prompt above a compact table of search results, with one line per crate.
+pub struct CratePickerWidget;
+
+impl StatefulWidget for CratePickerWidget {
+    type State = CratePicker;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let [prompt_area, table_area, footer_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let status = if state.loading {
+            Line::styled("searching…", config::get().color.base03)
+        } else if let Some(ref error) = state.error {
+            Line::styled(
+                error.lines().next().unwrap_or_default().to_string(),
+                config::get().color.base08,
+            )
+        } else {
+            Line::styled(
+                format!(
+                    "{}/{}",
+                    state.crates.len(),
+                    state.total.to_formatted_string(&Locale::en)
+                ),
+                config::get().color.base03,
+            )
+        };
+        let [input_area, status_area] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length((status.width() as u16).min(prompt_area.width / 2)),
+        ])
+        .spacing(1)
+        .areas(prompt_area);
+        let prefix = "> ";
+        let [prefix_area, input_area] =
+            Layout::horizontal([Constraint::Length(prefix.len() as u16), Constraint::Fill(1)])
+                .areas(input_area);
+        prefix
+            .fg(config::get().color.base0d)
+            .bold()
+            .render(prefix_area, buf);
+        let scroll = state
+            .input
+            .visual_cursor()
+            .saturating_sub(input_area.width.saturating_sub(1) as usize);
+        Paragraph::new(state.input.value())
+            .scroll((0, scroll as u16))
+            .render(input_area, buf);
+        let cursor = (state.input.visual_cursor() - scroll) as u16;
+        state.cursor_position = Some(Position::new(input_area.x + cursor, input_area.y));
+        status.render(status_area, buf);
+
+        let rows = state.crates.iter().map(|c| {
+            Row::new(vec![
+                Line::from(c.name.clone().bold()),
+                Line::from(c.max_version.clone()),
+                Line::styled(
+                    c.downloads.to_formatted_string(&Locale::en),
+                    config::get().color.base03,
+                )
+                .alignment(Alignment::Right),
+                Line::from(
+                    c.description
+                        .as_deref()
+                        .unwrap_or_default()
+                        .split_whitespace()
+                        .join(" "),
+                ),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Max(24),
+                Constraint::Max(12),
+                Constraint::Max(12),
+                Constraint::Fill(1),
+            ],
+        )
+        .column_spacing(2)
+        .style(config::get().color.base05)
+        .highlight_symbol("\u{2022} ")
+        .highlight_style(Style::default().bg(config::get().color.base02))
+        .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, table_area, buf, &mut state.table_state);
+
+        Line::from(vec![
+            "Enter".bold(),
+            " to pick, ".into(),
+            "↑/↓".bold(),
+            " to select, ".into(),
+            "Esc".bold(),
+            " to cancel".into(),
+        ])
+        .style(config::get().color.base03)
+        .render(footer_area, buf);
+    }
+}
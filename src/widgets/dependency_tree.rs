@@ -0,0 +1,403 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+use strum::EnumIs;
+
+use crate::{
+    app::Mode,
+    config,
+    crates_index_helper::DependencyKind,
+    resolver::{NodeId, Resolution},
+};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, EnumIs)]
+pub enum DependencyTreeFocus {
+    #[default]
+    Tree,
+    Features,
+}
+
+/// A flattened line of the dependency tree.
+#[derive(Debug, Clone)]
+struct TreeRow {
+    id: NodeId,
+    prefix: String,
+    kind: DependencyKind,
+    optional: bool,
+    target: Option<String>,
+    /// The node was already listed further up, so its children are not
+    /// repeated.
+    repeated: bool,
+    has_children: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct DependencyTree {
+    pub root: Option<NodeId>,
+    pub default_features: bool,
+    pub features: BTreeSet<String>,
+    pub resolution: Option<Resolution>,
+    pub collapsed: HashSet<NodeId>,
+    pub focus: DependencyTreeFocus,
+    /// The mode to return to when the tree is closed.
+    pub return_mode: Mode,
+    tree_state: TableState,
+    features_state: ListState,
+}
+
+impl DependencyTree {
+    pub fn new(root: NodeId, return_mode: Mode) -> Self {
+        Self {
+            root: Some(root),
+            default_features: true,
+            return_mode,
+            ..Default::default()
+        }
+    }
+
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            DependencyTreeFocus::Tree => DependencyTreeFocus::Features,
+            DependencyTreeFocus::Features => DependencyTreeFocus::Tree,
+        }
+    }
+
+    fn state_mut(&mut self) -> &mut Option<usize> {
+        match self.focus {
+            DependencyTreeFocus::Tree => self.tree_state.selected_mut(),
+            DependencyTreeFocus::Features => self.features_state.selected_mut(),
+        }
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let selected = self.state_mut();
+        *selected = Some(selected.map_or(0, |i| i.saturating_sub(1)));
+    }
+
+    pub fn scroll_next(&mut self) {
+        let selected = self.state_mut();
+        *selected = Some(selected.map_or(0, |i| i.saturating_add(1)));
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        *self.state_mut() = Some(0);
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        // clamped to the number of rows while rendering
+        *self.state_mut() = Some(usize::MAX);
+    }
+
+    /// Feature names shown in the features panel, with `default` first.
+    fn feature_items(&self) -> Vec<String> {
+        let Some(ref resolution) = self.resolution else {
+            return vec![];
+        };
+        std::iter::once("default".to_string())
+            .chain(
+                resolution
+                    .root_features
+                    .iter()
+                    .filter(|f| *f != "default")
+                    .cloned(),
+            )
+            .collect()
+    }
+
+    /// Collapses or expands the selected node, or enables or disables the
+    /// selected feature.
+    ///
+    /// Returns `true` if the features changed and the tree has to be resolved
+    /// again.
+    pub fn toggle_selected(&mut self) -> bool {
+        match self.focus {
+            DependencyTreeFocus::Tree => {
+                let rows = self.rows();
+                if let Some(row) = self
+                    .tree_state
+                    .selected()
+                    .and_then(|i| rows.get(i.min(rows.len().saturating_sub(1))))
+                {
+                    if !self.collapsed.remove(&row.id) && row.has_children && !row.repeated {
+                        self.collapsed.insert(row.id.clone());
+                    }
+                }
+                false
+            }
+            DependencyTreeFocus::Features => {
+                let items = self.feature_items();
+                let Some(feature) = self
+                    .features_state
+                    .selected()
+                    .and_then(|i| items.get(i.min(items.len().saturating_sub(1))))
+                else {
+                    return false;
+                };
+                if feature == "default" {
+                    self.default_features = !self.default_features;
+                } else if !self.features.remove(feature) {
+                    self.features.insert(feature.clone());
+                }
+                true
+            }
+        }
+    }
+
+    /// Flattens the resolution into rows the same way `cargo tree` prints it.
+    fn rows(&self) -> Vec<TreeRow> {
+        let mut rows = vec![];
+        if let Some(ref resolution) = self.resolution {
+            if let Some(ref root) = resolution.root {
+                let mut seen = HashSet::new();
+                self.flatten(
+                    resolution,
+                    root,
+                    (DependencyKind::Normal, false, None),
+                    String::new(),
+                    String::new(),
+                    &mut seen,
+                    &mut rows,
+                );
+            }
+        }
+        rows
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn flatten(
+        &self,
+        resolution: &Resolution,
+        id: &NodeId,
+        (kind, optional, target): (DependencyKind, bool, Option<String>),
+        prefix: String,
+        child_prefix: String,
+        seen: &mut HashSet<NodeId>,
+        rows: &mut Vec<TreeRow>,
+    ) {
+        let repeated = !seen.insert(id.clone());
+        let children = resolution
+            .children(id)
+            .sorted_by(|a, b| a.kind.cmp(&b.kind).then(a.child.cmp(&b.child)))
+            .collect_vec();
+        rows.push(TreeRow {
+            id: id.clone(),
+            prefix,
+            kind,
+            optional,
+            target,
+            repeated,
+            has_children: !children.is_empty(),
+        });
+        if repeated || self.collapsed.contains(id) {
+            return;
+        }
+        let n = children.len();
+        for (i, edge) in children.into_iter().enumerate() {
+            let last = i + 1 == n;
+            self.flatten(
+                resolution,
+                &edge.child,
+                (edge.kind, edge.optional, edge.target.clone()),
+                format!("{child_prefix}{}", if last { "└── " } else { "├── " }),
+                format!("{child_prefix}{}", if last { "    " } else { "│   " }),
+                seen,
+                rows,
+            );
+        }
+    }
+}
+
+pub struct DependencyTreeWidget {
+    loading: bool,
+}
+
+impl DependencyTreeWidget {
+    pub fn new(loading: bool) -> Self {
+        Self { loading }
+    }
+
+    fn block(title: String, focused: bool) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(if focused {
+                config::get().color.base0a
+            } else {
+                config::get().color.base03
+            })
+            .title(title)
+            .title_style(Style::default().fg(config::get().color.base0d).bold())
+    }
+
+    fn summary(
+        resolution: &Resolution,
+        duplicates: &BTreeMap<String, Vec<semver::Version>>,
+    ) -> Text<'static> {
+        let mut lines = vec![Line::from(vec![
+            resolution.unique_crates().to_string().bold(),
+            " unique crates, ".into(),
+            resolution.nodes.len().to_string().bold(),
+            " crate versions".into(),
+        ])];
+        if duplicates.is_empty() {
+            lines.push(Line::from(
+                "No duplicate versions".fg(config::get().color.base0b),
+            ));
+        } else {
+            lines.push(Line::from(vec![
+                "Duplicates: ".fg(config::get().color.base08),
+                duplicates
+                    .iter()
+                    .map(|(name, versions)| format!("{name} ({})", versions.iter().join(", ")))
+                    .join(", ")
+                    .into(),
+            ]));
+        }
+        if !resolution.unresolved.is_empty() {
+            lines.push(Line::from(vec![
+                "Unresolved: ".fg(config::get().color.base08),
+                resolution
+                    .unresolved
+                    .iter()
+                    .map(|(name, req)| format!("{name} {req}"))
+                    .join(", ")
+                    .into(),
+            ]));
+        }
+        Text::from(lines)
+    }
+
+    fn render_tree(&self, area: Rect, buf: &mut Buffer, state: &mut DependencyTree) {
+        let Some(ref resolution) = state.resolution else {
+            return;
+        };
+        let duplicates = resolution.duplicates();
+        let summary = Self::summary(resolution, &duplicates);
+        let [summary_area, tree_area] = Layout::vertical([
+            Constraint::Length(summary.height() as u16 + 1),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        Paragraph::new(summary)
+            .wrap(Wrap { trim: true })
+            .render(summary_area, buf);
+
+        let rows = state.rows();
+        let selected_max = rows.len().saturating_sub(1);
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                let (name, version) = &row.id;
+                let features = resolution.nodes.get(&row.id).cloned().unwrap_or_default();
+                let mut spans = vec![
+                    Span::styled(row.prefix, config::get().color.base03),
+                    Span::styled(
+                        format!("{name} v{version}"),
+                        if duplicates.contains_key(name) {
+                            Style::default().fg(config::get().color.base08).bold()
+                        } else {
+                            Style::default().fg(config::get().color.base05)
+                        },
+                    ),
+                ];
+                match row.kind {
+                    DependencyKind::Build => spans.push(" (build)".fg(config::get().color.base0e)),
+                    DependencyKind::Dev => spans.push(" (dev)".fg(config::get().color.base0e)),
+                    DependencyKind::Normal => {}
+                }
+                if row.optional {
+                    spans.push(" (optional)".fg(config::get().color.base0c));
+                }
+                if let Some(target) = row.target {
+                    spans.push(format!(" [{target}]").fg(config::get().color.base03));
+                }
+                if row.repeated && row.has_children {
+                    spans.push(" (*)".fg(config::get().color.base03));
+                } else if state.collapsed.contains(&row.id) {
+                    spans.push(" [+]".fg(config::get().color.base0a));
+                }
+                if !features.is_empty() {
+                    spans.push(
+                        format!(" features: {}", features.iter().join(", "))
+                            .fg(config::get().color.base03),
+                    );
+                }
+                Row::new([Line::from(spans)])
+            })
+            .collect_vec();
+        *state.tree_state.selected_mut() = Some(
+            state
+                .tree_state
+                .selected()
+                .unwrap_or_default()
+                .min(selected_max),
+        );
+        let table = Table::new(rows, [Constraint::Fill(1)])
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, tree_area, buf, &mut state.tree_state);
+    }
+
+    fn render_features(&self, area: Rect, buf: &mut Buffer, state: &mut DependencyTree) {
+        let items = state
+            .feature_items()
+            .into_iter()
+            .map(|feature| {
+                let enabled = if feature == "default" {
+                    state.default_features
+                } else {
+                    state.features.contains(&feature)
+                };
+                Line::from(vec![
+                    if enabled { "[x] " } else { "[ ] " }.fg(config::get().color.base0a),
+                    feature.fg(config::get().color.base05),
+                ])
+            })
+            .collect_vec();
+        let selected_max = items.len().saturating_sub(1);
+        *state.features_state.selected_mut() = Some(
+            state
+                .features_state
+                .selected()
+                .unwrap_or_default()
+                .min(selected_max),
+        );
+        let list = List::new(items)
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(list, area, buf, &mut state.features_state);
+    }
+}
+
+impl StatefulWidget for DependencyTreeWidget {
+    type State = DependencyTree;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let [tree, features] =
+            Layout::horizontal([Constraint::Percentage(75), Constraint::Percentage(25)])
+                .areas(area);
+
+        let mut title = match state.root {
+            Some((ref name, ref version)) => format!(" {name} v{version} "),
+            None => " Dependency Tree ".into(),
+        };
+        if self.loading {
+            title.push_str("(resolving...) ");
+        }
+        let tree_block = Self::block(title, state.focus.is_tree());
+        let features_block = Self::block(" Features ".into(), state.focus.is_features());
+        let tree_inner = tree_block.inner(tree);
+        let features_inner = features_block.inner(features);
+        tree_block.render(tree, buf);
+        features_block.render(features, buf);
+
+        if state.resolution.is_none() {
+            Line::styled("Resolving dependencies...", config::get().color.base03)
+                .render(tree_inner, buf);
+        }
+        self.render_tree(tree_inner, buf, state);
+        self.render_features(features_inner, buf, state);
+    }
+}
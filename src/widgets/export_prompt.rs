@@ -0,0 +1,149 @@
+use ratatui::{
+    layout::{Flex, Position},
+    prelude::*,
+    widgets::{block::Title, *},
+};
+
+use std::path::PathBuf;
+
+use crate::{app::Mode, config};
+
+/// The state of the prompt asking which file the current view, or a report,
+/// is exported to.
+#[derive(Debug, Default)]
+pub struct ExportPrompt {
+    pub input: tui_input::Input,
+    /// The exported view, shown again when the prompt is closed.
+    pub return_mode: Mode,
+    /// A report written instead of the rows of the exported view.
+    pub report: Option<String>,
+    /// An existing file that is overwritten if the prompt is submitted again.
+    overwrite: Option<PathBuf>,
+    cursor_position: Option<Position>,
+}
+
+impl ExportPrompt {
+    pub fn start(&mut self, file_name: &str, return_mode: Mode) {
+        self.input = tui_input::Input::new(file_name.into());
+        self.return_mode = return_mode;
+        self.report = None;
+        self.overwrite = None;
+    }
+
+    /// Asks which file `report` is written to, suggesting `file_name`.
+    pub fn start_report(&mut self, file_name: &str, report: String, return_mode: Mode) {
+        self.start(file_name, return_mode);
+        self.report = Some(report);
+    }
+
+    /// The path that was entered, or `None` if it names an existing file
+    /// whose overwriting has to be confirmed by submitting it again.
+    pub fn confirmed_path(&mut self) -> Option<PathBuf> {
+        let path = PathBuf::from(self.input.value().trim());
+        if path.exists() && self.overwrite.as_ref() != Some(&path) {
+            self.overwrite = Some(path);
+            return None;
+        }
+        Some(path)
+    }
+
+    pub fn cursor_position(&self) -> Option<Position> {
+        self.cursor_position
+    }
+}
+
+/// A prompt over the exported view for the path of the file to write.
+pub struct ExportPromptWidget;
+
+impl StatefulWidget for ExportPromptWidget {
+    type State = ExportPrompt;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let [center] = Layout::horizontal([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        // the confirmation is only shown while the existing path is entered
+        let overwrite = state
+            .overwrite
+            .as_ref()
+            .filter(|path| path.as_path() == std::path::Path::new(state.input.value().trim()));
+        let height = if overwrite.is_some() { 6 } else { 5 };
+        let [center] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(center);
+        let block = Block::bordered()
+            .border_style(config::get().color.base0a)
+            .title(if state.report.is_some() {
+                " Write the report to a file "
+            } else {
+                " Export to a .csv, .json or .md file "
+            })
+            .title_style(Style::default().fg(config::get().color.base0d).bold())
+            .title(
+                Title::from(vec![
+                    "Enter".bold(),
+                    if overwrite.is_some() {
+                        " to overwrite, ".into()
+                    } else {
+                        " to export, ".into()
+                    },
+                    "Esc".bold(),
+                    " to cancel ".into(),
+                ])
+                .position(block::Position::Bottom)
+                .alignment(Alignment::Right),
+            );
+        let inner = block.inner(center).inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        Clear.render(center, buf);
+        block.bg(config::get().color.base00).render(center, buf);
+        let [inner, warning_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(inner);
+        if let Some(path) = overwrite {
+            Line::styled(
+                format!("`{}` already exists", path.display()),
+                config::get().color.base08,
+            )
+            .render(warning_area, buf);
+        }
+
+        let scroll = state
+            .input
+            .visual_cursor()
+            .saturating_sub(inner.width.saturating_sub(1) as usize);
+        Paragraph::new(state.input.value())
+            .scroll((0, scroll as u16))
+            .render(inner, buf);
+        let cursor = (state.input.visual_cursor() - scroll) as u16;
+        state.cursor_position = Some(Position::new(inner.x + cursor, inner.y));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirms_overwriting_existing_files() {
+        let dir = std::env::temp_dir().join(format!("crates-tui-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let existing = dir.join("crates.csv");
+        std::fs::write(&existing, "name\n").unwrap();
+        let mut prompt = ExportPrompt::default();
+
+        let new = dir.join("new.csv");
+        prompt.start(&new.display().to_string(), Mode::Favorites);
+        assert_eq!(prompt.confirmed_path(), Some(new));
+
+        prompt.start(&existing.display().to_string(), Mode::Favorites);
+        assert_eq!(prompt.confirmed_path(), None);
+        assert_eq!(prompt.confirmed_path(), Some(existing.clone()));
+
+        // the confirmation is asked for again when the prompt is reopened
+        prompt.start(&existing.display().to_string(), Mode::Favorites);
+        assert_eq!(prompt.confirmed_path(), None);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
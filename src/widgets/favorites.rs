@@ -0,0 +1,233 @@
+use itertools::Itertools;
+use ratatui::{layout::Position, prelude::*, widgets::*};
+use strum::Display;
+
+use crate::{
+    config,
+    favorites::{Collection, FavoriteCrate, Favorites},
+};
+
+/// What the text typed in the favorites tab is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum FavoritesPrompt {
+    #[strum(to_string = "Note")]
+    Note,
+    #[strum(to_string = "New collection")]
+    NewCollection,
+    #[strum(to_string = "Rename collection")]
+    RenameCollection,
+}
+
+/// The collections of bookmarked crates and the list state of the favorites
+/// tab.
+#[derive(Debug, Default)]
+pub struct FavoritesView {
+    pub favorites: Favorites,
+    /// Index of the collection that is shown.
+    pub collection: usize,
+    pub prompt: Option<FavoritesPrompt>,
+    pub input: tui_input::Input,
+    list_state: ListState,
+    cursor_position: Option<Position>,
+}
+
+impl FavoritesView {
+    pub fn new(favorites: Favorites) -> Self {
+        let mut view = Self {
+            favorites,
+            ..Default::default()
+        };
+        view.scroll_to_top();
+        view
+    }
+
+    pub fn collection(&self) -> &Collection {
+        &self.favorites.collections[self.collection]
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        self.list_state
+            .selected()
+            .filter(|i| *i < self.collection().crates.len())
+    }
+
+    pub fn selected(&self) -> Option<&FavoriteCrate> {
+        self.selected_index()
+            .and_then(|i| self.collection().crates.get(i))
+    }
+
+    pub fn select(&mut self, index: Option<usize>) {
+        self.list_state.select(index);
+    }
+
+    pub fn select_collection(&mut self, collection: usize) {
+        self.collection = collection.min(self.favorites.collections.len().saturating_sub(1));
+        self.scroll_to_top();
+    }
+
+    pub fn next_collection(&mut self) {
+        self.select_collection((self.collection + 1) % self.favorites.collections.len());
+    }
+
+    pub fn previous_collection(&mut self) {
+        let len = self.favorites.collections.len();
+        self.select_collection((self.collection + len - 1) % len);
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let i = self
+            .list_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.list_state.select(Some(i));
+    }
+
+    pub fn scroll_next(&mut self) {
+        let i = self
+            .list_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(1))
+            .min(self.collection().crates.len().saturating_sub(1));
+        self.list_state.select(Some(i));
+    }
+
+    /// Keeps the selection in the list after a crate was removed.
+    pub fn clamp_selection(&mut self) {
+        let last = self.collection().crates.len().saturating_sub(1);
+        let i = self.list_state.selected().map_or(0, |i| i.min(last));
+        self.list_state.select(Some(i));
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.list_state.select(Some(0));
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.list_state
+            .select(Some(self.collection().crates.len().saturating_sub(1)));
+    }
+
+    /// Starts editing the note of the selected crate or the name of a
+    /// collection.
+    pub fn start_prompt(&mut self, prompt: FavoritesPrompt) {
+        let value = match prompt {
+            FavoritesPrompt::Note => self.selected().map(|c| c.note.clone()).unwrap_or_default(),
+            FavoritesPrompt::NewCollection => String::new(),
+            FavoritesPrompt::RenameCollection => self.collection().name.clone(),
+        };
+        self.input = tui_input::Input::new(value);
+        self.prompt = Some(prompt);
+    }
+
+    pub fn cursor_position(&self) -> Option<Position> {
+        self.cursor_position
+    }
+}
+
+pub struct FavoritesWidget;
+
+impl FavoritesWidget {
+    fn collections(state: &FavoritesView) -> List<'static> {
+        let items = state
+            .favorites
+            .collections
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let line = Line::from(vec![
+                    c.name.clone().into(),
+                    format!(" {}", c.crates.len()).fg(config::get().color.base03),
+                ]);
+                if i == state.collection {
+                    ListItem::new(line).style(
+                        Style::default()
+                            .fg(config::get().color.base0a)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    ListItem::new(line)
+                }
+            })
+            .collect_vec();
+        List::new(items).block(
+            Block::default()
+                .borders(Borders::RIGHT)
+                .border_style(config::get().color.base03)
+                .title("Collections".bold())
+                .padding(Padding::horizontal(1)),
+        )
+    }
+
+    fn crates(state: &FavoritesView) -> List<'static> {
+        let items = state
+            .collection()
+            .crates
+            .iter()
+            .map(|c| {
+                let mut line = Line::from(c.name.clone());
+                if !c.note.is_empty() {
+                    line.spans
+                        .push(format!("  {}", c.note).fg(config::get().color.base03));
+                }
+                ListItem::new(line)
+            })
+            .collect_vec();
+        List::new(items)
+            .style(
+                Style::default()
+                    .fg(config::get().color.base05)
+                    .bg(config::get().color.base00),
+            )
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always)
+    }
+}
+
+impl StatefulWidget for FavoritesWidget {
+    type State = FavoritesView;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = area.inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let [collections, crates_area] =
+            Layout::horizontal([Constraint::Length(24), Constraint::Fill(1)])
+                .spacing(1)
+                .areas(area);
+        Widget::render(Self::collections(state), collections, buf);
+
+        let [title, list_area, prompt] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(if state.prompt.is_some() { 2 } else { 0 }),
+        ])
+        .areas(crates_area);
+        Line::from(state.collection().name.clone().bold()).render(title, buf);
+        if state.collection().crates.is_empty() {
+            Line::styled(
+                "No crates yet, bookmark one from the search results or the summary",
+                config::get().color.base03,
+            )
+            .render(list_area, buf);
+        } else {
+            StatefulWidget::render(Self::crates(state), list_area, buf, &mut state.list_state);
+        }
+
+        state.cursor_position = None;
+        if let Some(kind) = state.prompt {
+            let [_, prompt] = Layout::vertical([Constraint::Length(1); 2]).areas(prompt);
+            let label = format!("{kind}: ");
+            let width = label.len() as u16;
+            Line::from(vec![
+                label.fg(config::get().color.base0a).bold(),
+                state.input.value().to_string().into(),
+            ])
+            .render(prompt, buf);
+            let cursor =
+                (width + state.input.visual_cursor() as u16).min(prompt.width.saturating_sub(1));
+            state.cursor_position = Some(Position::new(prompt.x + cursor, prompt.y));
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::Mode,
+    config,
+    resolver::NodeId,
+    workspace_graph::{FeatureSource, WorkspaceGraph},
+};
+
+/// A resolved package with its unified features.
+#[derive(Debug, Clone)]
+pub struct UnifiedPackage {
+    pub id: NodeId,
+    pub member: bool,
+    pub features: BTreeMap<String, BTreeSet<FeatureSource>>,
+}
+
+#[derive(Debug, Default)]
+pub struct FeatureUnification {
+    /// Packages with at least one feature enabled, or `None` while the
+    /// dependency graph is loading.
+    pub packages: Option<Vec<UnifiedPackage>>,
+    /// Features are only known when the graph comes from `cargo metadata`.
+    pub has_features: bool,
+    /// The package to select once the graph is loaded.
+    pub initial: Option<(String, Option<semver::Version>)>,
+    /// The mode to return to when the view is closed.
+    pub return_mode: Mode,
+    table_state: TableState,
+}
+
+impl FeatureUnification {
+    pub fn new(initial: Option<(String, Option<semver::Version>)>, return_mode: Mode) -> Self {
+        Self {
+            initial,
+            return_mode,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_graph(&mut self, graph: &WorkspaceGraph) {
+        let packages = graph
+            .nodes
+            .keys()
+            .map(|id| UnifiedPackage {
+                id: id.clone(),
+                member: graph.members.contains(id),
+                features: graph.unified_features(id),
+            })
+            .filter(|p| !p.features.is_empty())
+            .collect_vec();
+        if let Some((name, version)) = self.initial.take() {
+            let selected = packages.iter().position(|p| {
+                p.id.0 == name && version.as_ref().is_none_or(|version| p.id.1 == *version)
+            });
+            self.table_state.select(selected);
+        }
+        self.has_features = graph.has_features();
+        self.packages = Some(packages);
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.table_state.select(Some(i));
+    }
+
+    pub fn scroll_next(&mut self) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(1));
+        self.table_state.select(Some(i));
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.table_state.select(Some(0));
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        // clamped to the number of packages while rendering
+        self.table_state.select(Some(usize::MAX));
+    }
+}
+
+pub struct FeatureUnificationWidget;
+
+impl FeatureUnificationWidget {
+    fn block(title: String, focused: bool) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(if focused {
+                config::get().color.base0a
+            } else {
+                config::get().color.base03
+            })
+            .title(title)
+            .title_style(Style::default().fg(config::get().color.base0d).bold())
+    }
+
+    /// One line per feature, followed by what enables it.
+    fn features(package: &UnifiedPackage) -> Text<'static> {
+        let lines = package
+            .features
+            .iter()
+            .map(|(feature, sources)| {
+                let mut spans = vec![
+                    feature.clone().bold(),
+                    " \u{2190} ".fg(config::get().color.base03),
+                ];
+                if sources.is_empty() {
+                    let source = if package.member {
+                        "workspace member"
+                    } else {
+                        "unknown"
+                    };
+                    spans.push(source.fg(config::get().color.base03));
+                }
+                let sources = sources.iter().map(|source| match source {
+                    FeatureSource::Dependent((name, version)) => {
+                        Span::from(format!("{name} {version}"))
+                    }
+                    FeatureSource::Feature(feature) => {
+                        format!("via `{feature}`").fg(config::get().color.base0e)
+                    }
+                });
+                spans.extend(Itertools::intersperse(sources, Span::from(", ")));
+                Line::from(spans)
+            })
+            .collect_vec();
+        Text::from(lines)
+    }
+}
+
+impl StatefulWidget for FeatureUnificationWidget {
+    type State = FeatureUnification;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let Some(ref packages) = state.packages else {
+            let block = Self::block(" Feature Unification ".into(), true);
+            let inner = block.inner(area);
+            block.render(area, buf);
+            Line::styled(
+                "Loading the dependency graph...",
+                config::get().color.base03,
+            )
+            .render(inner, buf);
+            return;
+        };
+        let [list_area, features_area] =
+            Layout::horizontal([Constraint::Percentage(35), Constraint::Fill(1)]).areas(area);
+
+        let rows = packages
+            .iter()
+            .map(|p| {
+                let (name, version) = &p.id;
+                let name = if p.member {
+                    name.clone().fg(config::get().color.base0d).bold()
+                } else {
+                    name.clone().into()
+                };
+                Row::new([
+                    Cell::from(name),
+                    Cell::from(version.to_string()),
+                    Cell::from(p.features.len().to_string()),
+                ])
+            })
+            .collect_vec();
+        *state.table_state.selected_mut() = Some(
+            state
+                .table_state
+                .selected()
+                .unwrap_or_default()
+                .min(rows.len().saturating_sub(1)),
+        );
+        let header = Row::new(["Crate", "Version", "Features"].map(|h| h.bold()));
+        let widths = [
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Length(8),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Self::block(
+                format!(" Packages ({}) ", packages.len()),
+                true,
+            ))
+            .style(
+                Style::default()
+                    .fg(config::get().color.base05)
+                    .bg(config::get().color.base00),
+            )
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, list_area, buf, &mut state.table_state);
+
+        let selected = state.table_state.selected().and_then(|i| packages.get(i));
+        let title = match selected {
+            Some(p) => format!(" Features of {} {} ", p.id.0, p.id.1),
+            None => " Features ".into(),
+        };
+        let block = Self::block(title, false);
+        let inner = block.inner(features_area).inner(&Margin {
+            horizontal: 1,
+            vertical: 0,
+        });
+        block.render(features_area, buf);
+        if !state.has_features {
+            Paragraph::new(
+                "Features are unknown since `cargo metadata` failed and the graph was read from Cargo.lock",
+            )
+            .fg(config::get().color.base03)
+            .wrap(Wrap { trim: true })
+            .render(inner, buf);
+            return;
+        }
+        if let Some(package) = selected {
+            Paragraph::new(Self::features(package))
+                .wrap(Wrap { trim: false })
+                .render(inner, buf);
+        }
+    }
+}
@@ -0,0 +1,553 @@
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use num_format::{Locale, ToFormattedString};
+use ratatui::{prelude::*, widgets::*};
+use strum::{Display, EnumCount, EnumIs, EnumIter, FromRepr, IntoEnumIterator};
+
+use crate::{
+    advisory::Advisory,
+    app::Mode,
+    config,
+    widgets::crate_info_table::{advisory_color, advisory_lines},
+};
+
+#[derive(
+    Default, Debug, Clone, Copy, PartialEq, Eq, EnumIs, EnumCount, FromRepr, Display, EnumIter,
+)]
+pub enum FullCrateInfoTab {
+    #[default]
+    Overview,
+    Versions,
+    Dependencies,
+    Features,
+    Owners,
+    Downloads,
+}
+
+impl FullCrateInfoTab {
+    /// Get the previous tab, if there is no previous tab return the current tab.
+    fn previous(&mut self) {
+        let current_index: usize = *self as usize;
+        let previous_index = current_index.saturating_sub(1);
+        *self = Self::from_repr(previous_index).unwrap_or(*self)
+    }
+
+    /// Get the next tab, if there is no next tab return the current tab.
+    fn next(&mut self) {
+        let current_index = *self as usize;
+        let next_index = current_index.saturating_add(1);
+        *self = Self::from_repr(next_index).unwrap_or(*self)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FullCrateInfo {
+    pub tab: FullCrateInfoTab,
+    /// The mode to return to when the page is closed.
+    pub return_mode: Mode,
+    state: [TableState; FullCrateInfoTab::COUNT],
+}
+
+impl FullCrateInfo {
+    /// Resets the sub-tab and scroll positions, used when a different crate is
+    /// opened, returning to `return_mode` when closed.
+    pub fn open(&mut self, return_mode: Mode) {
+        *self = Self {
+            return_mode,
+            ..Default::default()
+        };
+    }
+
+    pub fn next_tab(&mut self) {
+        self.tab.next()
+    }
+
+    pub fn previous_tab(&mut self) {
+        self.tab.previous()
+    }
+
+    /// The index of the selected version when the versions tab is shown.
+    pub fn selected_version(&self) -> Option<usize> {
+        self.tab
+            .is_versions()
+            .then(|| self.state[self.tab as usize].selected())
+            .flatten()
+    }
+
+    fn state_mut(&mut self) -> &mut TableState {
+        &mut self.state[self.tab as usize]
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let state = self.state_mut();
+        let i = state.selected().map_or(0, |i| i.saturating_sub(1));
+        state.select(Some(i));
+    }
+
+    pub fn scroll_next(&mut self) {
+        let state = self.state_mut();
+        let i = state.selected().map_or(0, |i| i.saturating_add(1));
+        state.select(Some(i));
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.state_mut().select(Some(0));
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        // clamped to the number of rows while rendering
+        self.state_mut().select(Some(usize::MAX));
+    }
+}
+
+pub struct FullCrateInfoWidget {
+    full_crate: crates_io_api::FullCrate,
+    versions: Vec<crates_io_api::Version>,
+    advisories: Vec<Advisory>,
+}
+
+impl FullCrateInfoWidget {
+    pub fn new(
+        full_crate: crates_io_api::FullCrate,
+        versions: Vec<crates_io_api::Version>,
+    ) -> Self {
+        Self {
+            full_crate,
+            versions,
+            advisories: vec![],
+        }
+    }
+
+    /// Flags the versions affected by these security advisories.
+    pub fn advisories(mut self, advisories: Vec<Advisory>) -> Self {
+        self.advisories = advisories;
+        self
+    }
+
+    /// The advisories affecting a version.
+    fn affecting(&self, version: &crates_io_api::Version) -> Vec<Advisory> {
+        let Ok(version) = semver::Version::parse(&version.num) else {
+            return vec![];
+        };
+        self.advisories
+            .iter()
+            .filter(|advisory| advisory.affects(&version))
+            .cloned()
+            .collect()
+    }
+
+    fn tabs(&self, selected: FullCrateInfoTab) -> Tabs<'static> {
+        let titles = FullCrateInfoTab::iter().map(|tab| {
+            format!("  {tab}  ")
+                .fg(config::get().color.base0d)
+                .bg(config::get().color.base00)
+        });
+        Tabs::new(titles)
+            .highlight_style(
+                Style::default()
+                    .fg(config::get().color.base00)
+                    .bg(config::get().color.base0a)
+                    .bold(),
+            )
+            .select(selected as usize)
+            .padding("", "")
+            .divider(" ")
+    }
+
+    fn overview(&self, width: u16) -> (Vec<Row<'static>>, [Constraint; 2]) {
+        let fc = &self.full_crate;
+        let wrap = |s: &str| {
+            textwrap::wrap(s, (width as f64 * 0.75) as usize)
+                .iter()
+                .map(|s| Line::from(s.to_string()))
+                .collect_vec()
+        };
+        let mut rows = vec![
+            ("Name", vec![Line::from(fc.name.clone())]),
+            (
+                "Description",
+                wrap(&fc.description.clone().unwrap_or_default()),
+            ),
+            (
+                "License",
+                vec![Line::from(fc.license.clone().unwrap_or_default())],
+            ),
+            ("Max Version", vec![Line::from(fc.max_version.clone())]),
+        ];
+        if let Some(ref v) = fc.max_stable_version {
+            rows.push(("Max Stable Version", vec![Line::from(v.clone())]));
+        }
+        rows.extend([
+            (
+                "Created At",
+                vec![Line::from(
+                    fc.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                )],
+            ),
+            (
+                "Updated At",
+                vec![Line::from(
+                    fc.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                )],
+            ),
+            (
+                "Downloads",
+                vec![Line::from(
+                    fc.total_downloads.to_formatted_string(&Locale::en),
+                )],
+            ),
+        ]);
+        if let Some(recent) = fc.recent_downloads {
+            rows.push((
+                "Recent Downloads",
+                vec![Line::from(recent.to_formatted_string(&Locale::en))],
+            ));
+        }
+        rows.push((
+            "Reverse Dependencies",
+            vec![Line::from(
+                fc.reverse_dependencies
+                    .meta
+                    .total
+                    .to_formatted_string(&Locale::en),
+            )],
+        ));
+        for (title, link) in [
+            ("Homepage", &fc.homepage),
+            ("Documentation", &fc.documentation),
+            ("Repository", &fc.repository),
+        ] {
+            if let Some(link) = link {
+                rows.push((title, vec![Line::from(link.clone())]));
+            }
+        }
+        rows.push((
+            "Keywords",
+            wrap(&fc.keywords.iter().map(|k| k.keyword.clone()).join(", ")),
+        ));
+        rows.push((
+            "Categories",
+            wrap(&fc.categories.iter().map(|c| c.category.clone()).join(", ")),
+        ));
+        let rows = rows
+            .into_iter()
+            .map(|(title, lines)| {
+                let height = lines.len().max(1) as u16;
+                Row::new([Cell::from(title), Cell::from(Text::from(lines))]).height(height)
+            })
+            .collect_vec();
+        (rows, [Constraint::Fill(1), Constraint::Fill(4)])
+    }
+
+    fn versions(&self) -> (Vec<Row<'static>>, [Constraint; 6]) {
+        let rows = self
+            .versions
+            .iter()
+            .map(|v| {
+                let advisories = self.affecting(v);
+                let flag = match advisories.iter().max_by_key(|a| a.severity()) {
+                    Some(worst) => {
+                        format!("\u{26a0} {}", advisories.iter().map(|a| &a.id).join(", "))
+                            .fg(advisory_color(worst))
+                    }
+                    None => "".into(),
+                };
+                let row = Row::new([
+                    Cell::from(v.num.clone()),
+                    Cell::from(v.created_at.format("%Y-%m-%d").to_string()),
+                    Cell::from(v.downloads.to_formatted_string(&Locale::en)),
+                    Cell::from(v.rust_version.clone().unwrap_or_default()),
+                    Cell::from(if v.yanked { "yanked" } else { "" }),
+                    Cell::from(flag),
+                ]);
+                if v.yanked {
+                    row.fg(config::get().color.base08)
+                } else {
+                    row
+                }
+            })
+            .collect_vec();
+        (
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+            ],
+        )
+    }
+
+    fn dependencies(&self) -> (Vec<Row<'static>>, [Constraint; 5]) {
+        let rows = self
+            .full_crate
+            .versions
+            .first()
+            .map(|v| v.dependencies.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .sorted_by(|a, b| a.kind.cmp(&b.kind).then(a.crate_id.cmp(&b.crate_id)))
+            .map(|d| {
+                Row::new([
+                    d.crate_id,
+                    d.req,
+                    d.kind,
+                    if d.optional {
+                        "optional".into()
+                    } else {
+                        "".into()
+                    },
+                    d.target.unwrap_or_default(),
+                ])
+            })
+            .collect_vec();
+        (
+            rows,
+            [
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+            ],
+        )
+    }
+
+    fn features(&self, width: u16) -> (Vec<Row<'static>>, [Constraint; 2]) {
+        let features: BTreeMap<_, _> = self
+            .full_crate
+            .versions
+            .first()
+            .map(|v| v.features.clone().into_iter().collect())
+            .unwrap_or_default();
+        let rows = features
+            .into_iter()
+            .sorted_by_key(|(name, _)| name != "default")
+            .map(|(name, enables)| {
+                let lines = textwrap::wrap(&enables.join(", "), (width as f64 * 0.75) as usize)
+                    .iter()
+                    .map(|s| Line::from(s.to_string()))
+                    .collect_vec();
+                let height = lines.len().max(1) as u16;
+                Row::new([Cell::from(name), Cell::from(Text::from(lines))]).height(height)
+            })
+            .collect_vec();
+        (rows, [Constraint::Fill(1), Constraint::Fill(3)])
+    }
+
+    fn owners(&self) -> (Vec<Row<'static>>, [Constraint; 4]) {
+        let rows = self
+            .full_crate
+            .owners
+            .iter()
+            .map(|u| {
+                Row::new([
+                    u.login.clone(),
+                    u.name.clone().unwrap_or_default(),
+                    u.kind.clone().unwrap_or_default(),
+                    u.url.clone(),
+                ])
+            })
+            .collect_vec();
+        (
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+            ],
+        )
+    }
+
+    /// Total downloads per version over the period covered by the downloads
+    /// endpoint, sorted by the most downloaded version.
+    fn version_downloads(&self) -> (Vec<Row<'static>>, [Constraint; 2]) {
+        let names: BTreeMap<_, _> = self
+            .versions
+            .iter()
+            .map(|v| (v.id, v.num.clone()))
+            .collect();
+        let rows = self
+            .full_crate
+            .downloads
+            .version_downloads
+            .iter()
+            .map(|d| (d.version, d.downloads))
+            .into_grouping_map()
+            .sum()
+            .into_iter()
+            .sorted_by_key(|(_, downloads)| std::cmp::Reverse(*downloads))
+            .map(|(version, downloads)| {
+                Row::new([
+                    names
+                        .get(&version)
+                        .cloned()
+                        .unwrap_or_else(|| version.to_string()),
+                    downloads.to_formatted_string(&Locale::en),
+                ])
+            })
+            .collect_vec();
+        (rows, [Constraint::Fill(1), Constraint::Fill(1)])
+    }
+
+    fn daily_downloads(&self) -> Vec<u64> {
+        self.full_crate
+            .downloads
+            .version_downloads
+            .iter()
+            .map(|d| (d.date, d.downloads))
+            .chain(
+                self.full_crate
+                    .downloads
+                    .meta
+                    .extra_downloads
+                    .iter()
+                    .map(|d| (d.date, d.downloads)),
+            )
+            .into_grouping_map()
+            .sum()
+            .into_iter()
+            .sorted()
+            .map(|(_, downloads)| downloads)
+            .collect_vec()
+    }
+
+    fn render_table<const N: usize>(
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut TableState,
+        header: [&'static str; N],
+        (rows, widths): (Vec<Row<'static>>, [Constraint; N]),
+    ) {
+        let selected_max = rows.len().saturating_sub(1);
+        let header = Row::new(header.map(|h| Text::from(vec![Line::from(h.bold()), "".into()])))
+            .fg(config::get().color.base05)
+            .bg(config::get().color.base00)
+            .height(2);
+        let table = Table::new(rows, widths)
+            .header(header)
+            .style(
+                Style::default()
+                    .fg(config::get().color.base05)
+                    .bg(config::get().color.base00),
+            )
+            .column_spacing(2)
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        state.select(Some(state.selected().unwrap_or_default().min(selected_max)));
+        StatefulWidget::render(table, area, buf, state);
+    }
+}
+
+impl StatefulWidget for FullCrateInfoWidget {
+    type State = FullCrateInfo;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let [title, tabs, body] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Fill(1),
+        ])
+        .areas(area.inner(&Margin {
+            horizontal: 2,
+            vertical: 1,
+        }));
+
+        Line::from(vec![
+            self.full_crate.name.clone().bold(),
+            " ".into(),
+            format!("v{}", self.full_crate.max_version).fg(config::get().color.base0d),
+        ])
+        .render(title, buf);
+        self.tabs(state.tab).render(tabs, buf);
+
+        let tab = state.tab;
+        let table_state = state.state_mut();
+        match tab {
+            FullCrateInfoTab::Overview => {
+                Self::render_table(body, buf, table_state, ["", ""], self.overview(body.width))
+            }
+            FullCrateInfoTab::Versions => {
+                // details of the advisories affecting the selected version
+                let lines = table_state
+                    .selected()
+                    .and_then(|i| {
+                        self.versions
+                            .get(i.min(self.versions.len().saturating_sub(1)))
+                    })
+                    .map(|v| advisory_lines(&self.affecting(v), None))
+                    .unwrap_or_default();
+                let [table, advisories] =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Length(lines.len() as u16)])
+                        .spacing((!lines.is_empty()) as u16)
+                        .areas(body);
+                Self::render_table(
+                    table,
+                    buf,
+                    table_state,
+                    [
+                        "Version",
+                        "Released",
+                        "Downloads",
+                        "Rust Version",
+                        "",
+                        "Advisories",
+                    ],
+                    self.versions(),
+                );
+                Paragraph::new(lines).render(advisories, buf);
+            }
+            FullCrateInfoTab::Dependencies => Self::render_table(
+                body,
+                buf,
+                table_state,
+                ["Name", "Requirement", "Kind", "", "Target"],
+                self.dependencies(),
+            ),
+            FullCrateInfoTab::Features => Self::render_table(
+                body,
+                buf,
+                table_state,
+                ["Feature", "Enables"],
+                self.features(body.width),
+            ),
+            FullCrateInfoTab::Owners => Self::render_table(
+                body,
+                buf,
+                table_state,
+                ["Login", "Name", "Kind", "URL"],
+                self.owners(),
+            ),
+            FullCrateInfoTab::Downloads => {
+                let [chart, table] =
+                    Layout::vertical([Constraint::Length(10), Constraint::Fill(1)])
+                        .spacing(1)
+                        .areas(body);
+                let daily = self.daily_downloads();
+                // show the most recent days that fit in the available width
+                let daily = &daily[daily.len().saturating_sub(chart.width as usize)..];
+                Sparkline::default()
+                    .block(
+                        Block::default()
+                            .title(format!("Daily downloads (last {} days)", daily.len()))
+                            .title_style(Style::default().fg(config::get().color.base0d).bold()),
+                    )
+                    .data(daily)
+                    .style(config::get().color.base0b)
+                    .render(chart, buf);
+                Self::render_table(
+                    table,
+                    buf,
+                    table_state,
+                    ["Version", "Downloads"],
+                    self.version_downloads(),
+                )
+            }
+        }
+    }
+}
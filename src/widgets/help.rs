@@ -58,7 +58,7 @@ impl StatefulWidget for &HelpWidget {
         let [_, area, _] = Layout::horizontal([Min(0), Percentage(85), Min(0)]).areas(area);
 
         let skip = &mut state.skip;
-        let rows = std::iter::once((Mode::Help, "ESC".into(), Command::SwitchToLastMode))
+        let mut rows = std::iter::once((Mode::Help, "ESC".into(), Command::SwitchToLastMode))
             .chain(vec![(Mode::Help, "".into(), Command::SwitchToLastMode)])
             .chain(get_commands(
                 Mode::PickerShowCrateInfo,
@@ -139,64 +139,316 @@ impl StatefulWidget for &HelpWidget {
                 Mode::PickerShowCrateInfo,
                 Command::CopyCargoAddCommandToClipboard,
             ))
-            .chain(vec![(Mode::Help, "".into(), Command::SwitchToLastMode)])
-            .chain(get_commands(Mode::Summary, Command::Quit))
-            .chain(get_commands(Mode::Summary, Command::ScrollDown))
-            .chain(get_commands(Mode::Summary, Command::ScrollUp))
-            .chain(get_commands(Mode::Summary, Command::PreviousSummaryMode))
-            .chain(get_commands(Mode::Summary, Command::NextSummaryMode))
-            .chain(get_commands(Mode::Summary, Command::SwitchMode(Mode::Help)))
             .chain(get_commands(
-                Mode::Summary,
-                Command::SwitchMode(Mode::Search),
-            ))
-            .chain(get_commands(
-                Mode::Summary,
-                Command::SwitchMode(Mode::Filter),
+                Mode::PickerShowCrateInfo,
+                Command::ShowFullCrateInfo,
             ))
-            .chain(vec![(Mode::Help, "".into(), Command::SwitchToLastMode)])
             .chain(get_commands(
-                Mode::Search,
-                Command::SwitchMode(Mode::PickerHideCrateInfo),
+                Mode::PickerShowCrateInfo,
+                Command::ShowDependencyTree,
             ))
-            .chain(get_commands(Mode::Search, Command::SubmitSearch))
             .chain(get_commands(
-                Mode::Search,
-                Command::ToggleSortBy {
-                    reload: false,
-                    forward: true,
-                },
+                Mode::PickerShowCrateInfo,
+                Command::ToggleHealthColumn,
             ))
             .chain(get_commands(
-                Mode::Search,
-                Command::ToggleSortBy {
-                    reload: false,
-                    forward: false,
-                },
+                Mode::PickerShowCrateInfo,
+                Command::ToggleHideIncompatible,
             ))
             .chain(get_commands(
-                Mode::Search,
-                Command::ToggleSortBy {
-                    reload: true,
-                    forward: true,
-                },
+                Mode::PickerShowCrateInfo,
+                Command::AddDependency,
             ))
             .chain(get_commands(
-                Mode::Search,
-                Command::ToggleSortBy {
-                    reload: true,
-                    forward: false,
-                },
+                Mode::PickerShowCrateInfo,
+                Command::ShowInverseDependencies,
             ))
-            .chain(get_commands(Mode::Search, Command::ScrollSearchResultsUp))
-            .chain(get_commands(Mode::Search, Command::ScrollSearchResultsDown))
             .chain(get_commands(
-                Mode::Filter,
-                Command::SwitchMode(Mode::PickerHideCrateInfo),
+                Mode::PickerShowCrateInfo,
+                Command::ToggleFavorite,
             ))
-            .chain(get_commands(Mode::Filter, Command::ScrollSearchResultsUp))
-            .chain(get_commands(Mode::Filter, Command::ScrollSearchResultsDown))
             .collect_vec();
+        rows.extend(
+            std::iter::once((Mode::Help, "".into(), Command::SwitchToLastMode))
+                .chain(get_commands(
+                    Mode::FullCrateDetails,
+                    Command::CloseFullCrateInfo,
+                ))
+                .chain(get_commands(Mode::FullCrateDetails, Command::ScrollDown))
+                .chain(get_commands(Mode::FullCrateDetails, Command::ScrollUp))
+                .chain(get_commands(
+                    Mode::FullCrateDetails,
+                    Command::PreviousFullCrateInfoTab,
+                ))
+                .chain(get_commands(
+                    Mode::FullCrateDetails,
+                    Command::NextFullCrateInfoTab,
+                ))
+                .chain(get_commands(
+                    Mode::FullCrateDetails,
+                    Command::ShowDependencyTree,
+                ))
+                .chain(vec![(Mode::Help, "".into(), Command::SwitchToLastMode)])
+                .chain(get_commands(
+                    Mode::DependencyTree,
+                    Command::CloseDependencyTree,
+                ))
+                .chain(get_commands(Mode::DependencyTree, Command::ScrollDown))
+                .chain(get_commands(Mode::DependencyTree, Command::ScrollUp))
+                .chain(get_commands(
+                    Mode::DependencyTree,
+                    Command::ToggleDependencyTreeFocus,
+                ))
+                .chain(get_commands(
+                    Mode::DependencyTree,
+                    Command::ToggleDependencyTreeSelection,
+                ))
+                .chain(vec![(Mode::Help, "".into(), Command::SwitchToLastMode)])
+                .chain(get_commands(
+                    Mode::InverseDependencies,
+                    Command::CloseInverseDependencies,
+                ))
+                .chain(get_commands(Mode::InverseDependencies, Command::ScrollDown))
+                .chain(get_commands(Mode::InverseDependencies, Command::ScrollUp))
+                .chain(vec![(Mode::Help, "".into(), Command::SwitchToLastMode)])
+                .chain(get_commands(
+                    Mode::FeatureUnification,
+                    Command::CloseFeatureUnification,
+                ))
+                .chain(get_commands(Mode::FeatureUnification, Command::ScrollDown))
+                .chain(get_commands(Mode::FeatureUnification, Command::ScrollUp))
+                .chain(vec![(Mode::Help, "".into(), Command::SwitchToLastMode)])
+                .chain(get_commands(
+                    Mode::AddDependency,
+                    Command::SubmitAddDependency,
+                ))
+                .chain(get_commands(
+                    Mode::AddDependency,
+                    Command::CancelAddDependency,
+                ))
+                .chain(get_commands(Mode::AddDependency, Command::ScrollDown))
+                .chain(get_commands(Mode::AddDependency, Command::ScrollUp))
+                .chain(vec![(Mode::Help, "".into(), Command::SwitchToLastMode)])
+                .chain(get_commands(Mode::Project, Command::ScrollDown))
+                .chain(get_commands(Mode::Project, Command::ScrollUp))
+                .chain(get_commands(
+                    Mode::Project,
+                    Command::OpenVersionDiffInBrowser,
+                ))
+                .chain(get_commands(
+                    Mode::Project,
+                    Command::BumpDependencyRequirement,
+                ))
+                .chain(get_commands(Mode::Project, Command::ShowLockfile))
+                .chain(get_commands(
+                    Mode::Project,
+                    Command::ShowInverseDependencies,
+                ))
+                .chain(get_commands(Mode::Project, Command::ShowFeatureUnification))
+                .chain(get_commands(Mode::Lockfile, Command::ScrollDown))
+                .chain(get_commands(Mode::Lockfile, Command::ScrollUp))
+                .chain(get_commands(Mode::Lockfile, Command::ToggleDuplicatesOnly))
+                .chain(get_commands(
+                    Mode::Lockfile,
+                    Command::OpenCratesIOUrlInBrowser,
+                ))
+                .chain(get_commands(
+                    Mode::Lockfile,
+                    Command::SwitchMode(Mode::Project),
+                ))
+                .chain(vec![(Mode::Help, "".into(), Command::SwitchToLastMode)])
+                .chain(get_commands(Mode::Summary, Command::Quit))
+                .chain(get_commands(Mode::Summary, Command::ScrollDown))
+                .chain(get_commands(Mode::Summary, Command::ScrollUp))
+                .chain(get_commands(Mode::Summary, Command::PreviousSummaryMode))
+                .chain(get_commands(Mode::Summary, Command::NextSummaryMode))
+                .chain(get_commands(Mode::Summary, Command::ToggleFavorite))
+                .chain(get_commands(Mode::Summary, Command::SwitchMode(Mode::Help)))
+                .chain(get_commands(
+                    Mode::Summary,
+                    Command::SwitchMode(Mode::Search),
+                ))
+                .chain(get_commands(
+                    Mode::Summary,
+                    Command::SwitchMode(Mode::Filter),
+                ))
+                .chain(vec![(Mode::Help, "".into(), Command::SwitchToLastMode)])
+                .chain(get_commands(
+                    Mode::Search,
+                    Command::SwitchMode(Mode::PickerHideCrateInfo),
+                ))
+                .chain(get_commands(Mode::Search, Command::SubmitSearch))
+                .chain(get_commands(
+                    Mode::Search,
+                    Command::ToggleSortBy {
+                        reload: false,
+                        forward: true,
+                    },
+                ))
+                .chain(get_commands(
+                    Mode::Search,
+                    Command::ToggleSortBy {
+                        reload: false,
+                        forward: false,
+                    },
+                ))
+                .chain(get_commands(
+                    Mode::Search,
+                    Command::ToggleSortBy {
+                        reload: true,
+                        forward: true,
+                    },
+                ))
+                .chain(get_commands(
+                    Mode::Search,
+                    Command::ToggleSortBy {
+                        reload: true,
+                        forward: false,
+                    },
+                ))
+                .chain(get_commands(Mode::Search, Command::ScrollSearchResultsUp))
+                .chain(get_commands(Mode::Search, Command::ScrollSearchResultsDown))
+                .chain(get_commands(
+                    Mode::Filter,
+                    Command::SwitchMode(Mode::PickerHideCrateInfo),
+                ))
+                .chain(get_commands(Mode::Filter, Command::ScrollSearchResultsUp))
+                .chain(get_commands(Mode::Filter, Command::ScrollSearchResultsDown)),
+        );
+        rows.extend(
+            std::iter::once((Mode::Help, "".into(), Command::SwitchToLastMode))
+                .chain(get_commands(Mode::Search, Command::PreviousSearchQuery))
+                .chain(get_commands(Mode::Search, Command::NextSearchQuery))
+                .chain(get_commands(Mode::Search, Command::ShowSearchHistory))
+                .chain(get_commands(
+                    Mode::SearchHistory,
+                    Command::SelectSearchHistory,
+                ))
+                .chain(get_commands(
+                    Mode::SearchHistory,
+                    Command::CloseSearchHistory,
+                ))
+                .chain(get_commands(
+                    Mode::SearchHistory,
+                    Command::ClearSearchHistory,
+                ))
+                .chain(vec![(Mode::Help, "".into(), Command::SwitchToLastMode)])
+                .chain(get_commands(Mode::Favorites, Command::ScrollDown))
+                .chain(get_commands(Mode::Favorites, Command::ScrollUp))
+                .chain(get_commands(Mode::Favorites, Command::NextCollection))
+                .chain(get_commands(Mode::Favorites, Command::PreviousCollection))
+                .chain(get_commands(Mode::Favorites, Command::MoveFavoriteDown))
+                .chain(get_commands(Mode::Favorites, Command::MoveFavoriteUp))
+                .chain(get_commands(Mode::Favorites, Command::EditFavoriteNote))
+                .chain(get_commands(Mode::Favorites, Command::NewCollection))
+                .chain(get_commands(Mode::Favorites, Command::RenameCollection))
+                .chain(get_commands(Mode::Favorites, Command::DeleteCollection))
+                .chain(get_commands(Mode::Favorites, Command::ToggleFavorite))
+                .chain(get_commands(
+                    Mode::Favorites,
+                    Command::OpenCratesIOUrlInBrowser,
+                ))
+                .chain(get_commands(
+                    Mode::FavoritesPrompt,
+                    Command::SubmitFavoritesPrompt,
+                ))
+                .chain(get_commands(
+                    Mode::FavoritesPrompt,
+                    Command::CancelFavoritesPrompt,
+                )),
+        );
+        rows.extend(
+            std::iter::once((Mode::Help, "".into(), Command::SwitchToLastMode))
+                .chain(get_commands(Mode::Summary, Command::ShowRecentlyViewed))
+                .chain(get_commands(Mode::RecentlyViewed, Command::ScrollDown))
+                .chain(get_commands(Mode::RecentlyViewed, Command::ScrollUp))
+                .chain(get_commands(
+                    Mode::RecentlyViewed,
+                    Command::OpenCratesIOUrlInBrowser,
+                ))
+                .chain(get_commands(Mode::RecentlyViewed, Command::ToggleFavorite))
+                .chain(get_commands(
+                    Mode::RecentlyViewed,
+                    Command::CloseRecentlyViewed,
+                ))
+                .chain(std::iter::once((
+                    Mode::Help,
+                    "".into(),
+                    Command::SwitchToLastMode,
+                )))
+                .chain(get_commands(
+                    Mode::PickerHideCrateInfo,
+                    Command::ToggleWatch,
+                ))
+                .chain(get_commands(Mode::Summary, Command::ShowWatchlist))
+                .chain(get_commands(Mode::Watchlist, Command::AcknowledgeWatched))
+                .chain(get_commands(
+                    Mode::Watchlist,
+                    Command::AcknowledgeAllWatched,
+                ))
+                .chain(get_commands(Mode::Watchlist, Command::ToggleWatch))
+                .chain(get_commands(Mode::Watchlist, Command::CheckWatchlist))
+                .chain(get_commands(Mode::Watchlist, Command::CloseWatchlist)),
+        );
+        rows.extend(
+            std::iter::once((Mode::Help, "".into(), Command::SwitchToLastMode))
+                .chain(get_commands(
+                    Mode::PickerHideCrateInfo,
+                    Command::EditCrateNote,
+                ))
+                .chain(get_commands(
+                    Mode::PickerHideCrateInfo,
+                    Command::EditCrateTags,
+                ))
+                .chain(get_commands(
+                    Mode::AnnotationPrompt,
+                    Command::SubmitAnnotationPrompt,
+                ))
+                .chain(get_commands(
+                    Mode::AnnotationPrompt,
+                    Command::CancelAnnotationPrompt,
+                )),
+        );
+        rows.extend(
+            std::iter::once((Mode::Help, "".into(), Command::SwitchToLastMode))
+                .chain(get_commands(
+                    Mode::PickerHideCrateInfo,
+                    Command::ShowSavedSearches,
+                ))
+                .chain(get_commands(
+                    Mode::SavedSearches,
+                    Command::SelectSavedSearch,
+                ))
+                .chain(get_commands(
+                    Mode::SavedSearches,
+                    Command::CloseSavedSearches,
+                )),
+        );
+        rows.extend(
+            std::iter::once((Mode::Help, "".into(), Command::SwitchToLastMode))
+                .chain(get_commands(Mode::PickerHideCrateInfo, Command::Export))
+                .chain(get_commands(Mode::Favorites, Command::Export))
+                .chain(get_commands(Mode::Lockfile, Command::Export))
+                .chain(get_commands(
+                    Mode::ExportPrompt,
+                    Command::SubmitExportPrompt,
+                ))
+                .chain(get_commands(
+                    Mode::ExportPrompt,
+                    Command::CancelExportPrompt,
+                )),
+        );
+        rows.extend(
+            std::iter::once((Mode::Help, "".into(), Command::SwitchToLastMode))
+                .chain(get_commands(
+                    Mode::PickerHideCrateInfo,
+                    Command::CopyJustificationReport,
+                ))
+                .chain(get_commands(
+                    Mode::PickerHideCrateInfo,
+                    Command::WriteJustificationReport,
+                )),
+        );
 
         if let Some(mode) = state.mode {
             tracing::debug!("{:?}", mode);
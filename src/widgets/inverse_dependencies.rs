@@ -0,0 +1,214 @@
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::Mode,
+    config,
+    crates_index_helper::DependencyKind,
+    workspace_graph::{GraphEdge, MAX_PATHS},
+};
+
+/// Every path from the workspace members to a crate, like `cargo tree -i`.
+#[derive(Debug, Default)]
+pub struct InverseDependencies {
+    pub name: String,
+    /// Only paths to this version are listed when set.
+    pub version: Option<semver::Version>,
+    /// The edges of each path from a member down to the crate, or `None`
+    /// while the dependency graph is loading.
+    pub paths: Option<Vec<Vec<GraphEdge>>>,
+    /// More than [`MAX_PATHS`] paths lead to the crate.
+    pub truncated: bool,
+    /// The mode to return to when the view is closed.
+    pub return_mode: Mode,
+    list_state: ListState,
+}
+
+impl InverseDependencies {
+    pub fn new(name: String, version: Option<semver::Version>, return_mode: Mode) -> Self {
+        Self {
+            name,
+            version,
+            return_mode,
+            ..Default::default()
+        }
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let i = self
+            .list_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.list_state.select(Some(i));
+    }
+
+    pub fn scroll_next(&mut self) {
+        let i = self
+            .list_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(1));
+        self.list_state.select(Some(i));
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.list_state.select(Some(0));
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        // clamped to the number of paths while rendering
+        self.list_state.select(Some(usize::MAX));
+    }
+}
+
+pub struct InverseDependenciesWidget;
+
+impl InverseDependenciesWidget {
+    /// The spans describing a single step of a path, e.g.
+    /// ` → tokio 1.36.0 (dev) [full]`.
+    fn step(edge: &GraphEdge) -> Vec<Span<'static>> {
+        let (name, version) = &edge.child;
+        let mut spans = vec![
+            " \u{2192} ".fg(config::get().color.base03),
+            format!("{name} {version}").into(),
+        ];
+        let kinds = edge
+            .kinds
+            .iter()
+            .filter(|k| **k != DependencyKind::Normal)
+            .map(|k| format!("{k:?}").to_lowercase())
+            .join(", ");
+        if !kinds.is_empty() {
+            spans.push(format!(" ({kinds})").fg(config::get().color.base0e));
+        }
+        if let Some(ref requested) = edge.features {
+            let features = (requested.default_features)
+                .then(|| "default".to_string())
+                .into_iter()
+                .chain(requested.features.iter().cloned())
+                .join(", ");
+            if !features.is_empty() {
+                spans.push(format!(" [{features}]").fg(config::get().color.base0b));
+            }
+            if let Some(ref enabled_by) = requested.enabled_by {
+                let enabled_by = enabled_by
+                    .iter()
+                    .map(|f| format!("{}/{f}", edge.parent.0))
+                    .join(", ");
+                spans.push(
+                    format!(" optional, enabled by {enabled_by}").fg(config::get().color.base09),
+                );
+            }
+        }
+        spans
+    }
+
+    /// Lays the spans of a path out into lines no wider than `width`,
+    /// breaking between steps.
+    fn wrap(steps: Vec<Vec<Span<'static>>>, width: usize) -> Text<'static> {
+        let mut lines: Vec<Line> = vec![];
+        for step in steps {
+            let step_width = step.iter().map(|s| s.width()).sum::<usize>();
+            match lines.last_mut() {
+                Some(line) if line.width() + step_width <= width => line.spans.extend(step),
+                Some(_) => {
+                    let mut line = vec![Span::from("   ")];
+                    line.extend(step);
+                    lines.push(Line::from(line));
+                }
+                None => lines.push(Line::from(step)),
+            }
+        }
+        Text::from(lines)
+    }
+}
+
+impl StatefulWidget for InverseDependenciesWidget {
+    type State = InverseDependencies;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let target = match state.version {
+            Some(ref version) => format!("{} {version}", state.name),
+            None => state.name.clone(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(config::get().color.base0a)
+            .title(format!(" Why is {target} here? "))
+            .title_style(Style::default().fg(config::get().color.base0d).bold());
+        let inner = block.inner(area).inner(&Margin {
+            horizontal: 1,
+            vertical: 0,
+        });
+        block.render(area, buf);
+
+        let Some(ref paths) = state.paths else {
+            Line::styled(
+                "Loading the dependency graph...",
+                config::get().color.base03,
+            )
+            .render(inner, buf);
+            return;
+        };
+        let [summary, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+
+        let mut line = vec![
+            paths.len().to_string().bold(),
+            if state.truncated {
+                format!(" paths (only the first {MAX_PATHS} are listed)").into()
+            } else {
+                " paths from workspace members".into()
+            },
+        ];
+        if paths.iter().flatten().any(|e| e.features.is_none()) {
+            line.push(
+                ", features are unknown since the graph was read from Cargo.lock"
+                    .fg(config::get().color.base03),
+            );
+        }
+        Line::from(line).render(summary, buf);
+        if paths.is_empty() {
+            Line::styled(
+                format!("{target} is not in the dependency graph of the workspace"),
+                config::get().color.base03,
+            )
+            .render(list_area, buf);
+            return;
+        }
+
+        let width = list_area.width.saturating_sub(2) as usize;
+        let items = paths
+            .iter()
+            .map(|path| match path.first() {
+                Some(first) => {
+                    let member = vec![first.parent.0.clone().fg(config::get().color.base0d).bold()];
+                    let steps = std::iter::once(member)
+                        .chain(path.iter().map(Self::step))
+                        .collect();
+                    ListItem::new(Self::wrap(steps, width))
+                }
+                None => ListItem::new(Line::from(vec![
+                    target.clone().fg(config::get().color.base0d).bold(),
+                    " is a workspace member".into(),
+                ])),
+            })
+            .collect_vec();
+        *state.list_state.selected_mut() = Some(
+            state
+                .list_state
+                .selected()
+                .unwrap_or_default()
+                .min(items.len().saturating_sub(1)),
+        );
+        let list = List::new(items)
+            .style(
+                Style::default()
+                    .fg(config::get().color.base05)
+                    .bg(config::get().color.base00),
+            )
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(list, list_area, buf, &mut state.list_state);
+    }
+}
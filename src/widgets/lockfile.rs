@@ -0,0 +1,210 @@
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{config, project::LockfileEntry};
+
+#[derive(Debug, Default)]
+pub struct LockfileExplorer {
+    pub entries: Vec<LockfileEntry>,
+    /// Only list crates with several locked versions.
+    pub duplicates_only: bool,
+    table_state: TableState,
+}
+
+impl LockfileExplorer {
+    /// Entries listed with the current filter.
+    pub fn visible(&self) -> Vec<&LockfileEntry> {
+        self.entries
+            .iter()
+            .filter(|e| !self.duplicates_only || e.is_duplicate())
+            .collect()
+    }
+
+    pub fn selected(&self) -> Option<&LockfileEntry> {
+        self.table_state
+            .selected()
+            .and_then(|i| self.visible().get(i).copied())
+    }
+
+    pub fn toggle_duplicates_only(&mut self) {
+        self.duplicates_only = !self.duplicates_only;
+        self.table_state.select(Some(0));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.table_state.select(Some(i));
+    }
+
+    pub fn scroll_next(&mut self) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(1))
+            .min(self.visible().len().saturating_sub(1));
+        self.table_state.select(Some(i));
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.table_state.select(Some(0));
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.table_state
+            .select(Some(self.visible().len().saturating_sub(1)));
+    }
+}
+
+pub struct LockfileExplorerWidget {
+    /// Whether crates-tui was started inside a Cargo project.
+    has_project: bool,
+}
+
+impl LockfileExplorerWidget {
+    pub fn new(has_project: bool) -> Self {
+        Self { has_project }
+    }
+
+    fn source(entry: &LockfileEntry) -> Span<'static> {
+        match entry.package.source {
+            _ if entry.package.is_from_registry() => "crates.io".into(),
+            Some(ref source) if source.starts_with("git+") => "git".into(),
+            Some(_) => "other".into(),
+            None => "path".fg(config::get().color.base03),
+        }
+    }
+
+    fn summary(entries: &[LockfileEntry]) -> Line<'static> {
+        let duplicates = entries
+            .iter()
+            .filter(|e| e.is_duplicate())
+            .map(|e| &e.package.name)
+            .unique()
+            .count();
+        let yanked = entries.iter().filter(|e| e.is_yanked()).count();
+        Line::from(vec![
+            entries.len().to_string().bold(),
+            " packages, ".into(),
+            duplicates.to_string().fg(config::get().color.base09).bold(),
+            " crates with multiple versions, ".into(),
+            yanked.to_string().fg(config::get().color.base08).bold(),
+            " yanked".into(),
+        ])
+    }
+}
+
+impl StatefulWidget for LockfileExplorerWidget {
+    type State = LockfileExplorer;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = area.inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        if !self.has_project {
+            Line::styled(
+                "crates-tui was not started inside a Cargo project",
+                config::get().color.base03,
+            )
+            .centered()
+            .render(area, buf);
+            return;
+        }
+        let [summary, table_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(area);
+        let mut summary_line = Self::summary(&state.entries);
+        if state.duplicates_only {
+            summary_line
+                .spans
+                .push(" (showing duplicates only)".fg(config::get().color.base03));
+        }
+        summary_line.render(summary, buf);
+
+        let visible = state.visible();
+        let rows = visible
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                // only show the name on the first version of a crate
+                let name = if i > 0 && visible[i - 1].package.name == e.package.name {
+                    Span::from("")
+                } else if e.is_duplicate() {
+                    format!("{} \u{00d7}{}", e.package.name, e.versions)
+                        .fg(config::get().color.base09)
+                        .bold()
+                } else {
+                    e.package.name.clone().into()
+                };
+                let version = if e.is_yanked() {
+                    Line::from(vec![
+                        e.package.version.to_string().into(),
+                        " \u{26a0} yanked".fg(config::get().color.base08).bold(),
+                    ])
+                } else {
+                    Line::from(e.package.version.to_string())
+                };
+                let latest = match e.latest {
+                    Some(ref latest) if *latest > e.package.version => {
+                        latest.to_string().fg(config::get().color.base0a)
+                    }
+                    Some(ref latest) => latest.to_string().into(),
+                    None => "-".fg(config::get().color.base03),
+                };
+                let rust_version = e
+                    .rust_version
+                    .clone()
+                    .map_or("-".fg(config::get().color.base03), Span::from);
+                Row::new([
+                    Cell::from(name),
+                    Cell::from(version),
+                    Cell::from(Self::source(e)),
+                    Cell::from(latest),
+                    Cell::from(rust_version),
+                    Cell::from(e.parents.join(", ")),
+                ])
+            })
+            .collect_vec();
+
+        let header = Row::new(
+            [
+                "Crate",
+                "Version",
+                "Source",
+                "Latest",
+                "Rust Version",
+                "Parents",
+            ]
+            .map(|h| h.bold()),
+        )
+        .height(1);
+        let widths = [
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+            Constraint::Fill(1),
+            Constraint::Length(12),
+            Constraint::Fill(4),
+        ];
+        *state.table_state.selected_mut() = Some(
+            state
+                .table_state
+                .selected()
+                .unwrap_or_default()
+                .min(rows.len().saturating_sub(1)),
+        );
+        let table = Table::new(rows, widths)
+            .header(header)
+            .style(
+                Style::default()
+                    .fg(config::get().color.base05)
+                    .bg(config::get().color.base00),
+            )
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, table_area, buf, &mut state.table_state);
+    }
+}
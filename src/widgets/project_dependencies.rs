@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    advisory::Advisory,
+    config,
+    crates_index_helper::DependencyKind,
+    project::ProjectDependency,
+    widgets::crate_info_table::{advisory_color, advisory_lines},
+};
+
+#[derive(Debug, Default)]
+pub struct ProjectDependencies {
+    pub dependencies: Vec<ProjectDependency>,
+    /// Why the license of a crate violates the license policy, keyed by name
+    /// and version.
+    pub license_violations: HashMap<(String, semver::Version), String>,
+    /// Security advisories affecting the locked version of a crate, keyed by
+    /// name and version.
+    pub advisories: HashMap<(String, semver::Version), Vec<Advisory>>,
+    table_state: TableState,
+}
+
+impl ProjectDependencies {
+    pub fn license_violation(&self, dependency: &ProjectDependency) -> Option<&str> {
+        let version = dependency.version()?.clone();
+        self.license_violations
+            .get(&(dependency.name.clone(), version))
+            .map(String::as_str)
+    }
+
+    pub fn selected(&self) -> Option<&ProjectDependency> {
+        self.table_state
+            .selected()
+            .and_then(|i| self.dependencies.get(i))
+    }
+
+    pub fn advisories(&self, dependency: &ProjectDependency) -> &[Advisory] {
+        dependency
+            .locked
+            .clone()
+            .and_then(|locked| self.advisories.get(&(dependency.name.clone(), locked)))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.table_state.select(Some(i));
+    }
+
+    pub fn scroll_next(&mut self) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(1))
+            .min(self.dependencies.len().saturating_sub(1));
+        self.table_state.select(Some(i));
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.table_state.select(Some(0));
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.table_state
+            .select(Some(self.dependencies.len().saturating_sub(1)));
+    }
+}
+
+pub struct ProjectDependenciesWidget {
+    /// Whether crates-tui was started inside a Cargo project.
+    has_project: bool,
+}
+
+impl ProjectDependenciesWidget {
+    pub fn new(has_project: bool) -> Self {
+        Self { has_project }
+    }
+
+    fn version_cell(version: &Option<semver::Version>, style: Style) -> Cell<'static> {
+        match version {
+            Some(version) => Cell::from(version.to_string()).style(style),
+            None => Cell::from("-".fg(config::get().color.base03)),
+        }
+    }
+
+    fn summary(state: &ProjectDependencies) -> Line<'static> {
+        let dependencies = &state.dependencies;
+        let breaking = dependencies.iter().filter(|d| d.is_breaking()).count();
+        let updatable = dependencies.iter().filter(|d| d.is_updatable()).count();
+        let mut line = Line::from(vec![
+            dependencies.len().to_string().bold(),
+            " dependencies, ".into(),
+            breaking.to_string().fg(config::get().color.base08).bold(),
+            " with breaking upgrades, ".into(),
+            updatable.to_string().fg(config::get().color.base0a).bold(),
+            " with compatible updates".into(),
+        ]);
+        let violations = dependencies
+            .iter()
+            .filter(|d| state.license_violation(d).is_some())
+            .count();
+        let vulnerable = dependencies
+            .iter()
+            .filter(|d| !state.advisories(d).is_empty())
+            .count();
+        if vulnerable > 0 {
+            line.spans.extend([
+                ", ".into(),
+                vulnerable.to_string().fg(config::get().color.base08).bold(),
+                " with security advisories".into(),
+            ]);
+        }
+        if violations > 0 {
+            line.spans.extend([
+                ", ".into(),
+                violations.to_string().fg(config::get().color.base08).bold(),
+                " with disallowed licenses".into(),
+            ]);
+        }
+        line
+    }
+}
+
+impl StatefulWidget for ProjectDependenciesWidget {
+    type State = ProjectDependencies;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = area.inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        if !self.has_project {
+            Line::styled(
+                "crates-tui was not started inside a Cargo project",
+                config::get().color.base03,
+            )
+            .centered()
+            .render(area, buf);
+            return;
+        }
+        // details of the selected dependency's problems
+        let mut details = vec![];
+        if let Some(d) = state.selected() {
+            if let Some(explanation) = state.license_violation(d) {
+                details.push(Line::from(vec![
+                    format!("\u{2717} {}: ", d.name)
+                        .fg(config::get().color.base08)
+                        .bold(),
+                    explanation.to_string().fg(config::get().color.base08),
+                ]));
+            }
+            details.extend(advisory_lines(state.advisories(d), None));
+        }
+        let [summary, table_area, details_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(details.len() as u16),
+        ])
+        .areas(area);
+        Self::summary(state).render(summary, buf);
+        Paragraph::new(details).render(details_area, buf);
+
+        let rows = state
+            .dependencies
+            .iter()
+            .enumerate()
+            .map(|(i, d)| {
+                // only show the member on the first of its rows
+                let member = if i > 0 && state.dependencies[i - 1].member == d.member {
+                    String::new()
+                } else {
+                    d.member.clone()
+                };
+                let kind = match (d.kind, &d.target) {
+                    (DependencyKind::Normal, None) => String::new(),
+                    (kind, None) => format!("{kind:?}").to_lowercase(),
+                    (kind, Some(target)) => format!("{kind:?} [{target}]").to_lowercase(),
+                };
+                let compatible_style = if d.is_updatable() {
+                    Style::default().fg(config::get().color.base0a)
+                } else {
+                    Style::default()
+                };
+                let latest_style = if d.is_breaking() {
+                    Style::default().fg(config::get().color.base08).bold()
+                } else {
+                    Style::default()
+                };
+                Row::new([
+                    Cell::from(member.bold()),
+                    Cell::from(match state.license_violation(d) {
+                        Some(_) => Line::from(vec![
+                            d.name.clone().into(),
+                            " \u{2717}".fg(config::get().color.base08).bold(),
+                        ]),
+                        None => Line::from(d.name.clone()),
+                    }),
+                    Cell::from(kind.fg(config::get().color.base0e)),
+                    Cell::from(d.req.to_string()),
+                    match state.advisories(d).iter().max_by_key(|a| a.severity()) {
+                        Some(worst) => Cell::from(Line::from(vec![
+                            d.locked.iter().join("").into(),
+                            " \u{26a0}".fg(advisory_color(worst)).bold(),
+                        ])),
+                        None => Self::version_cell(&d.locked, Style::default()),
+                    },
+                    Self::version_cell(&d.compatible, compatible_style),
+                    Self::version_cell(&d.latest, latest_style),
+                ])
+            })
+            .collect_vec();
+
+        let header = Row::new(
+            [
+                "Member",
+                "Crate",
+                "Kind",
+                "Requirement",
+                "Locked",
+                "Compatible",
+                "Latest",
+            ]
+            .map(|h| h.bold()),
+        )
+        .height(1);
+        let widths = [
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ];
+        *state.table_state.selected_mut() = Some(
+            state
+                .table_state
+                .selected()
+                .unwrap_or_default()
+                .min(state.dependencies.len().saturating_sub(1)),
+        );
+        let table = Table::new(rows, widths)
+            .header(header)
+            .style(
+                Style::default()
+                    .fg(config::get().color.base05)
+                    .bg(config::get().color.base00),
+            )
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, table_area, buf, &mut state.table_state);
+    }
+}
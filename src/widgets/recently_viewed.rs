@@ -0,0 +1,117 @@
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::Mode,
+    config,
+    recently_viewed::{viewed_ago, RecentlyViewed, ViewedCrate},
+};
+
+/// The crates whose details were opened and the list state of the recently
+/// viewed list.
+#[derive(Debug, Default)]
+pub struct RecentlyViewedView {
+    pub recent: RecentlyViewed,
+    /// The mode to return to when the list is closed.
+    pub return_mode: Mode,
+    list_state: ListState,
+}
+
+impl RecentlyViewedView {
+    pub fn new(recent: RecentlyViewed) -> Self {
+        Self {
+            recent,
+            ..Default::default()
+        }
+    }
+
+    /// Shows the list from the top, returning to `return_mode` when closed.
+    pub fn open(&mut self, return_mode: Mode) {
+        self.return_mode = return_mode;
+        self.list_state.select(Some(0));
+    }
+
+    pub fn selected(&self) -> Option<&ViewedCrate> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.recent.crates.get(i))
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let i = self
+            .list_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.list_state.select(Some(i));
+    }
+
+    pub fn scroll_next(&mut self) {
+        let i = self
+            .list_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(1))
+            .min(self.recent.crates.len().saturating_sub(1));
+        self.list_state.select(Some(i));
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.list_state.select(Some(0));
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.list_state
+            .select(Some(self.recent.crates.len().saturating_sub(1)));
+    }
+}
+
+pub struct RecentlyViewedWidget;
+
+impl StatefulWidget for RecentlyViewedWidget {
+    type State = RecentlyViewedView;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = area.inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let [title, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(area);
+        Line::from(vec![
+            "Recently viewed".bold(),
+            format!(" {}", state.recent.crates.len()).fg(config::get().color.base03),
+        ])
+        .render(title, buf);
+        if state.recent.crates.is_empty() {
+            Line::styled(
+                "Crates show up here once their details are opened",
+                config::get().color.base03,
+            )
+            .render(list_area, buf);
+            return;
+        }
+
+        let now = chrono::Utc::now();
+        let name_width = list_area.width.saturating_sub(2 + 20) as usize;
+        let items = state
+            .recent
+            .crates
+            .iter()
+            .map(|c| {
+                ListItem::new(Line::from(vec![
+                    format!("{:name_width$}", c.name).into(),
+                    format!("{:>20}", viewed_ago(c.viewed_at, now)).fg(config::get().color.base03),
+                ]))
+            })
+            .collect_vec();
+        let list = List::new(items)
+            .style(
+                Style::default()
+                    .fg(config::get().color.base05)
+                    .bg(config::get().color.base00),
+            )
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(list, list_area, buf, &mut state.list_state);
+    }
+}
@@ -0,0 +1,130 @@
+use itertools::Itertools;
+use ratatui::{
+    layout::Flex,
+    prelude::*,
+    widgets::{block::Title, *},
+};
+
+use crate::{app::Mode, config};
+
+/// The list state of the saved searches picker.
+#[derive(Debug, Default)]
+pub struct SavedSearchesView {
+    /// The mode to return to when the picker is closed.
+    pub return_mode: Mode,
+    list_state: ListState,
+}
+
+impl SavedSearchesView {
+    /// Shows the picker from the top, returning to `return_mode` when closed.
+    pub fn open(&mut self, return_mode: Mode) {
+        self.return_mode = return_mode;
+        self.list_state.select(Some(0));
+    }
+
+    /// The name of the selected saved search.
+    pub fn selected(&self) -> Option<String> {
+        self.list_state
+            .selected()
+            .and_then(|i| config::get().saved_searches.get(i))
+            .map(|s| s.name.clone())
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let i = self
+            .list_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.list_state.select(Some(i));
+    }
+
+    pub fn scroll_next(&mut self) {
+        let i = self
+            .list_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(1))
+            .min(config::get().saved_searches.len().saturating_sub(1));
+        self.list_state.select(Some(i));
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.list_state.select(Some(0));
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.list_state
+            .select(Some(config::get().saved_searches.len().saturating_sub(1)));
+    }
+}
+
+/// A picker over the saved searches from the configuration.
+pub struct SavedSearchesWidget;
+
+impl StatefulWidget for SavedSearchesWidget {
+    type State = SavedSearchesView;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let [center] = Layout::horizontal([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [center] = Layout::vertical([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(center);
+        let searches = &config::get().saved_searches;
+        let block = Block::bordered()
+            .border_style(config::get().color.base0a)
+            .title(format!(" Saved searches ({}) ", searches.len()))
+            .title_style(Style::default().fg(config::get().color.base0d).bold())
+            .title(
+                Title::from(vec![
+                    "Enter".bold(),
+                    " to run, ".into(),
+                    "Esc".bold(),
+                    " to cancel ".into(),
+                ])
+                .position(block::Position::Bottom)
+                .alignment(Alignment::Right),
+            );
+        let inner = block.inner(center).inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        Clear.render(center, buf);
+        block.bg(config::get().color.base00).render(center, buf);
+
+        if searches.is_empty() {
+            Text::from(vec![
+                Line::styled("No saved searches", config::get().color.base03),
+                Line::styled(
+                    "Add [[saved_searches]] entries to the configuration file",
+                    config::get().color.base03,
+                ),
+            ])
+            .render(inner, buf);
+            return;
+        }
+        let items = searches
+            .iter()
+            .map(|search| {
+                let mut line = Line::from(vec![
+                    search.name.clone().bold(),
+                    "  ".into(),
+                    search.query.clone().into(),
+                    format!(" ({:?})", search.sort).fg(config::get().color.base0d),
+                ]);
+                if !search.filter.is_empty() {
+                    line.spans.push(
+                        format!("  filter: {}", search.filter).fg(config::get().color.base03),
+                    );
+                }
+                ListItem::new(line)
+            })
+            .collect_vec();
+        let list = List::new(items)
+            .style(Style::default().fg(config::get().color.base05))
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(list, inner, buf, &mut state.list_state);
+    }
+}
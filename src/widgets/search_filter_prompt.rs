@@ -48,11 +48,13 @@ impl<'a> SearchFilterPromptWidget<'a> {
         }
     }
 
-    fn input_block(&self) -> Block {
+    fn input_block(&self) -> Block<'_> {
         let line = if self.mode.is_filter() {
             vec!["Filter: ".into(), "Enter".bold(), " to submit".into()]
         } else if self.mode.is_search() {
             vec!["Search: ".into(), "Enter".bold(), " to submit".into()]
+        } else if self.mode.is_search_history() {
+            vec!["Search: ".into(), "Enter".bold(), " to recall".into()]
         } else if self.mode.is_summary() {
             let help = config::get()
                 .key_bindings
@@ -72,16 +74,240 @@ impl<'a> SearchFilterPromptWidget<'a> {
                 .into_iter()
                 .next()
                 .unwrap_or_default();
+            let recent = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::ShowRecentlyViewed)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
             vec![
                 open_in_browser.bold(),
                 " to open in browser, ".into(),
                 search.bold(),
                 " to enter search, ".into(),
+                recent.bold(),
+                " for recently viewed, ".into(),
                 help.bold(),
                 " for help".into(),
             ]
         } else if self.mode.is_help() {
             vec!["ESC".bold(), " to return".into()]
+        } else if self.mode.is_dependency_tree() {
+            let focus = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::ToggleDependencyTreeFocus)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let toggle = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::ToggleDependencyTreeSelection)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let back = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::CloseDependencyTree)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![
+                toggle.bold(),
+                " to collapse / toggle feature, ".into(),
+                focus.bold(),
+                " to switch panel, ".into(),
+                back.bold(),
+                " to return".into(),
+            ]
+        } else if self.mode.is_inverse_dependencies() {
+            let back = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::CloseInverseDependencies)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![back.bold(), " to return".into()]
+        } else if self.mode.is_feature_unification() {
+            let back = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::CloseFeatureUnification)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![back.bold(), " to return".into()]
+        } else if self.mode.is_project() {
+            let open = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::OpenVersionDiffInBrowser)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let bump = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::BumpDependencyRequirement)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let help = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::SwitchMode(Mode::Help))
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![
+                open.bold(),
+                " to open version diff, ".into(),
+                bump.bold(),
+                " to bump requirement, ".into(),
+                help.bold(),
+                " for help".into(),
+            ]
+        } else if self.mode.is_lockfile() {
+            let duplicates = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::ToggleDuplicatesOnly)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let help = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::SwitchMode(Mode::Help))
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![
+                duplicates.bold(),
+                " to toggle duplicates only, ".into(),
+                help.bold(),
+                " for help".into(),
+            ]
+        } else if self.mode.is_favorites() {
+            let note = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::EditFavoriteNote)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let next = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::NextCollection)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let help = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::SwitchMode(Mode::Help))
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![
+                note.bold(),
+                " to edit note, ".into(),
+                next.bold(),
+                " for next collection, ".into(),
+                help.bold(),
+                " for help".into(),
+            ]
+        } else if self.mode.is_watchlist() {
+            let acknowledge = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::AcknowledgeWatched)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let check = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::CheckWatchlist)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let help = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::SwitchMode(Mode::Help))
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![
+                acknowledge.bold(),
+                " to acknowledge, ".into(),
+                check.bold(),
+                " to check again, ".into(),
+                help.bold(),
+                " for help".into(),
+            ]
+        } else if self.mode.is_recently_viewed() {
+            let open_in_browser = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::OpenCratesIOUrlInBrowser)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let back = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::CloseRecentlyViewed)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![
+                open_in_browser.bold(),
+                " to open in browser, ".into(),
+                back.bold(),
+                " to return".into(),
+            ]
+        } else if self.mode.is_favorites_prompt() {
+            let submit = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::SubmitFavoritesPrompt)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let cancel = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::CancelFavoritesPrompt)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![
+                submit.bold(),
+                " to save, ".into(),
+                cancel.bold(),
+                " to cancel".into(),
+            ]
+        } else if self.mode.is_add_dependency() {
+            vec!["Adding a dependency to the current project".into()]
+        } else if self.mode.is_annotation_prompt() {
+            vec!["Editing personal notes and tags".into()]
+        } else if self.mode.is_saved_searches() {
+            vec!["Picking a saved search".into()]
+        } else if self.mode.is_export_prompt() {
+            vec!["Exporting the current view".into()]
+        } else if self.mode.is_full_crate_details() {
+            let next_tab = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::NextFullCrateInfoTab)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let back = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::CloseFullCrateInfo)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let help = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::SwitchMode(Mode::Help))
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![
+                next_tab.bold(),
+                " for next tab, ".into(),
+                back.bold(),
+                " to return, ".into(),
+                help.bold(),
+                " for help".into(),
+            ]
         } else {
             let search = config::get()
                 .key_bindings
@@ -125,7 +351,9 @@ impl<'a> SearchFilterPromptWidget<'a> {
             )
             .fg(config::get().color.base05)
             .border_style(match self.mode {
-                Mode::Search => Style::default().fg(config::get().color.base0a),
+                Mode::Search | Mode::SearchHistory => {
+                    Style::default().fg(config::get().color.base0a)
+                }
                 Mode::Filter => Style::default().fg(config::get().color.base0b),
                 _ => Style::default().fg(config::get().color.base06),
             });
@@ -148,10 +376,18 @@ impl<'a> SearchFilterPromptWidget<'a> {
                 .into_iter()
                 .next()
                 .unwrap_or_default();
+            let history = config::get()
+                .key_bindings
+                .get_config_for_command(Mode::Search, Command::ShowSearchHistory)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
             input_block
                 .title(Line::from(vec![
                     toggle_sort.bold(),
-                    " to toggle sort".into(),
+                    " to toggle sort, ".into(),
+                    history.bold(),
+                    " for history".into(),
                 ]))
                 .title_alignment(Alignment::Right)
                 .title(
@@ -176,7 +412,20 @@ impl<'a> SearchFilterPromptWidget<'a> {
         let scroll = self.input.cursor().saturating_sub(width.saturating_sub(4));
         let text = if self.mode.focused() {
             Line::from(vec![self.input.value().into()])
-        } else if self.mode.is_summary() || self.mode.is_help() {
+        } else if self.mode.is_summary()
+            || self.mode.is_help()
+            || self.mode.is_full_crate_details()
+            || self.mode.is_add_dependency()
+            || self.mode.is_project()
+            || self.mode.is_lockfile()
+            || self.mode.is_favorites()
+            || self.mode.is_favorites_prompt()
+            || self.mode.is_recently_viewed()
+            || self.mode.is_watchlist()
+            || self.mode.is_annotation_prompt()
+            || self.mode.is_saved_searches()
+            || self.mode.is_export_prompt()
+        {
             Line::from(vec![])
         } else {
             Line::from(vec![
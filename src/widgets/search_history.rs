@@ -0,0 +1,231 @@
+use itertools::Itertools;
+use ratatui::{
+    layout::{Flex, Position},
+    prelude::*,
+    widgets::{block::Title, *},
+};
+
+use crate::{
+    config,
+    search_history::{SearchHistory, SearchHistoryEntry},
+};
+
+/// The submitted searches, the position while cycling through them in the
+/// search prompt, and the state of the fuzzy finder over them.
+#[derive(Debug, Default)]
+pub struct SearchHistoryView {
+    pub history: SearchHistory,
+    /// The entry shown in the search prompt while cycling through the
+    /// history, and the query and sort that were set before.
+    browsing: Option<(usize, SearchHistoryEntry)>,
+    /// The fuzzy finder query.
+    pub input: tui_input::Input,
+    /// Indices of the entries matching the fuzzy finder query.
+    matches: Vec<usize>,
+    list_state: ListState,
+    cursor_position: Option<Position>,
+}
+
+impl SearchHistoryView {
+    pub fn new(history: SearchHistory) -> Self {
+        Self {
+            history,
+            ..Default::default()
+        }
+    }
+
+    /// The older entry to show in the search prompt, which currently shows
+    /// `current`.
+    pub fn previous(&mut self, current: SearchHistoryEntry) -> Option<SearchHistoryEntry> {
+        let i = match self.browsing {
+            Some((0, _)) => return None,
+            Some((i, _)) => i - 1,
+            None => self.history.entries.len().checked_sub(1)?,
+        };
+        let draft = self.browsing.take().map_or(current, |(_, draft)| draft);
+        self.browsing = Some((i, draft));
+        self.history.entries.get(i).cloned()
+    }
+
+    /// The newer entry to show in the search prompt, or the query and sort
+    /// that were set before cycling through the history.
+    pub fn next(&mut self) -> Option<SearchHistoryEntry> {
+        let (i, draft) = self.browsing.take()?;
+        match self.history.entries.get(i + 1) {
+            Some(entry) => {
+                self.browsing = Some((i + 1, draft));
+                Some(entry.clone())
+            }
+            None => Some(draft),
+        }
+    }
+
+    pub fn reset_browsing(&mut self) {
+        self.browsing = None;
+    }
+
+    /// Opens the fuzzy finder with an initial query.
+    pub fn open_finder(&mut self, query: &str) {
+        self.input = tui_input::Input::new(query.into());
+        self.update_matches();
+    }
+
+    pub fn update_matches(&mut self) {
+        self.matches = self.history.matches(self.input.value());
+        self.list_state
+            .select((!self.matches.is_empty()).then_some(0));
+    }
+
+    pub fn selected(&self) -> Option<&SearchHistoryEntry> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.matches.get(i))
+            .and_then(|i| self.history.entries.get(*i))
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let i = self
+            .list_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.list_state.select(Some(i));
+    }
+
+    pub fn scroll_next(&mut self) {
+        let i = self
+            .list_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(1))
+            .min(self.matches.len().saturating_sub(1));
+        self.list_state.select(Some(i));
+    }
+
+    pub fn cursor_position(&self) -> Option<Position> {
+        self.cursor_position
+    }
+}
+
+/// A fuzzy finder over the submitted searches.
+pub struct SearchHistoryWidget;
+
+impl SearchHistoryWidget {
+    /// The query with the characters matching the pattern highlighted.
+    fn highlight(query: &str, pattern: &str) -> Vec<Span<'static>> {
+        let mut pattern = pattern
+            .to_lowercase()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect_vec()
+            .into_iter()
+            .peekable();
+        query
+            .chars()
+            .map(|c| {
+                if pattern.peek() == c.to_lowercase().next().as_ref() {
+                    pattern.next();
+                    c.to_string().fg(config::get().color.base0a).bold()
+                } else {
+                    c.to_string().into()
+                }
+            })
+            .collect()
+    }
+}
+
+impl StatefulWidget for SearchHistoryWidget {
+    type State = SearchHistoryView;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let [center] = Layout::horizontal([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [center] = Layout::vertical([Constraint::Percentage(60)])
+            .flex(Flex::Center)
+            .areas(center);
+        let block = Block::bordered()
+            .border_style(config::get().color.base0a)
+            .title(format!(
+                " Search history ({}/{}) ",
+                state.matches.len(),
+                state.history.entries.len()
+            ))
+            .title_style(Style::default().fg(config::get().color.base0d).bold())
+            .title(
+                Title::from(vec![
+                    "Enter".bold(),
+                    " to recall, ".into(),
+                    "Esc".bold(),
+                    " to cancel ".into(),
+                ])
+                .position(block::Position::Bottom)
+                .alignment(Alignment::Right),
+            );
+        let inner = block.inner(center).inner(&Margin {
+            horizontal: 1,
+            vertical: 0,
+        });
+        Clear.render(center, buf);
+        block.bg(config::get().color.base00).render(center, buf);
+
+        let [query, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+        Line::from(vec![
+            "> ".fg(config::get().color.base0a).bold(),
+            state.input.value().to_string().into(),
+        ])
+        .render(query, buf);
+        let cursor = (2 + state.input.visual_cursor() as u16).min(query.width.saturating_sub(1));
+        state.cursor_position = Some(Position::new(query.x + cursor, query.y));
+
+        let pattern = state.input.value();
+        let items = state
+            .matches
+            .iter()
+            .filter_map(|i| state.history.entries.get(*i))
+            .map(|entry| {
+                let mut line = Line::from(Self::highlight(&entry.query, pattern));
+                line.spans
+                    .push(format!("  ({:?})", entry.sort).fg(config::get().color.base03));
+                ListItem::new(line)
+            })
+            .collect_vec();
+        if items.is_empty() {
+            Line::styled("No matching searches", config::get().color.base03).render(list_area, buf);
+            return;
+        }
+        let list = List::new(items)
+            .style(Style::default().fg(config::get().color.base05))
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(list, list_area, buf, &mut state.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crates_io_api::Sort;
+
+    use super::*;
+
+    #[test]
+    fn cycles_through_queries_with_their_sort() {
+        let mut history = SearchHistory::default();
+        history.push("serde", Sort::Downloads, 10);
+        history.push("tokio", Sort::RecentUpdates, 10);
+        let mut view = SearchHistoryView::new(history);
+        let draft = SearchHistoryEntry {
+            query: "ax".into(),
+            sort: Sort::Relevance,
+        };
+
+        let tokio = view.previous(draft.clone()).unwrap();
+        assert_eq!(tokio.sort, Sort::RecentUpdates);
+        let serde = view.previous(tokio.clone()).unwrap();
+        assert_eq!(serde.sort, Sort::Downloads);
+        assert_eq!(view.previous(serde), None);
+        assert_eq!(view.next(), Some(tokio));
+        assert_eq!(view.next(), Some(draft));
+        assert_eq!(view.next(), None);
+    }
+}
@@ -1,13 +1,31 @@
+use std::collections::{HashMap, HashSet};
+
 use itertools::Itertools;
 use num_format::{Locale, ToFormattedString};
 use ratatui::{prelude::*, widgets::*};
 
-use crate::config;
+use crate::{
+    config,
+    health::HealthReport,
+    project::UsedCrate,
+    rust_version::Compatibility,
+    widgets::crate_info_table::{health_color, tags_line},
+};
 
 #[derive(Debug, Default)]
 pub struct SearchResultsTable {
     pub crates: Vec<crates_io_api::Crate>,
-    pub versions: Vec<crates_io_api::Version>,
+    pub health: HashMap<String, HealthReport>,
+    pub compatibility: HashMap<String, Compatibility>,
+    /// Why the license of each crate violates the license policy, keyed by
+    /// name.
+    pub license_violations: HashMap<String, String>,
+    /// Crates the current project depends on, keyed by name.
+    pub used: HashMap<String, UsedCrate>,
+    /// Names of the crates bookmarked in any collection.
+    pub favorites: HashSet<String>,
+    /// Personal tags of the crates that have any, keyed by name.
+    pub tags: HashMap<String, Vec<String>>,
     pub table_state: TableState,
     pub scrollbar_state: ScrollbarState,
 }
@@ -87,11 +105,21 @@ impl SearchResultsTable {
 
 pub struct SearchResultsTableWidget {
     highlight: bool,
+    show_health: bool,
 }
 
 impl SearchResultsTableWidget {
     pub fn new(highlight: bool) -> Self {
-        Self { highlight }
+        Self {
+            highlight,
+            show_health: false,
+        }
+    }
+
+    /// Adds a column with the health score of each crate.
+    pub fn show_health(mut self, show_health: bool) -> Self {
+        self.show_health = show_health;
+        self
     }
 }
 
@@ -108,26 +136,40 @@ impl StatefulWidget for SearchResultsTableWidget {
             .track_style(config::get().color.base06)
             .render(scrollbar_area, buf, &mut state.scrollbar_state);
 
-        let widths = [
+        let mut widths = vec![
             Constraint::Length(1),
             Constraint::Max(20),
             Constraint::Min(0),
+            Constraint::Max(12),
             Constraint::Max(10),
         ];
-        let (areas, spacers) =
-            Layout::horizontal(widths)
-                .spacing(1)
-                .split_with_spacers(area.inner(&Margin {
-                    horizontal: 1,
-                    vertical: 0,
-                }));
+        let show_tags = !state.tags.is_empty();
+        if show_tags {
+            widths.push(Constraint::Max(16));
+        }
+        if self.show_health {
+            widths.push(Constraint::Max(6));
+        }
+        let (areas, spacers) = Layout::horizontal(widths.clone())
+            .spacing(1)
+            .split_with_spacers(area.inner(&Margin {
+                horizontal: 1,
+                vertical: 0,
+            }));
         let description_area = areas[2];
         let text_wrap_width = description_area.width as usize;
 
         let selected = state.selected().unwrap_or_default();
         let table_widget = {
+            let mut header = vec!["Name", "Description", "Version", "Downloads"];
+            if show_tags {
+                header.push("Tags");
+            }
+            if self.show_health {
+                header.push("Health");
+            }
             let header = Row::new(
-                ["Name", "Description", "Downloads"]
+                header
                     .iter()
                     .map(|h| Text::from(vec!["".into(), Line::from(h.bold()), "".into()])),
             )
@@ -150,34 +192,85 @@ impl StatefulWidget for SearchResultsTableWidget {
                 .collect_vec();
                 desc.insert(0, "".into());
                 let height = desc.len();
-                Row::new([
-                    Text::from(vec!["".into(), Line::from(item.name.clone()), "".into()]),
+                let msrv = match state.compatibility.get(&item.name) {
+                    Some(Compatibility::Older(version)) => {
+                        Line::from(format!("\u{26a0} v{version}").fg(config::get().color.base09))
+                    }
+                    Some(Compatibility::Incompatible) => {
+                        Line::from("\u{2717} newer rust".fg(config::get().color.base08))
+                    }
+                    _ => "".into(),
+                };
+                let used = state.used.get(&item.name);
+                let mut name = if used.is_some() {
+                    Line::from(item.name.clone().fg(config::get().color.base0b).bold())
+                } else {
+                    Line::from(item.name.clone())
+                };
+                if state.favorites.contains(&item.name) {
+                    name.spans.push(" \u{2605}".fg(config::get().color.base0a));
+                }
+                if state.license_violations.contains_key(&item.name) {
+                    name.spans
+                        .push(" \u{2717}".fg(config::get().color.base08).bold());
+                }
+                let locked = match used {
+                    Some(used) if !used.locked.is_empty() => Line::from(
+                        format!("\u{2713} {}", used.locked.iter().join(", "))
+                            .fg(config::get().color.base0b),
+                    ),
+                    Some(used) => Line::from(
+                        format!("\u{2713} {}", used.requirements.iter().join(", "))
+                            .fg(config::get().color.base0b),
+                    ),
+                    None => "".into(),
+                };
+                let mut cells = vec![
+                    Text::from(vec!["".into(), name, msrv]),
                     Text::from(desc),
+                    Text::from(vec![
+                        "".into(),
+                        Line::from(item.max_version.clone()),
+                        locked,
+                    ]),
                     Text::from(vec![
                         "".into(),
                         Line::from(item.downloads.to_formatted_string(&Locale::en)),
                         "".into(),
                     ]),
-                ])
-                .style({
-                    let s = Style::default()
-                        .fg(config::get().color.base05)
-                        .bg(match i % 2 {
-                            0 => config::get().color.base00,
-                            1 => config::get().color.base01,
-                            _ => unreachable!("Cannot reach this line"),
-                        });
-                    if i == selected {
-                        s.bg(config::get().color.base02)
-                    } else {
-                        s
-                    }
-                })
-                .height(height.saturating_add(1) as u16)
+                ];
+                if show_tags {
+                    let tags = state.tags.get(&item.name).map_or(&[][..], |t| t.as_slice());
+                    cells.push(Text::from(vec!["".into(), tags_line(tags), "".into()]));
+                }
+                if self.show_health {
+                    let score = match state.health.get(&item.name) {
+                        Some(health) => Line::from(
+                            format!("{}%", health.score()).fg(health_color(health.score())),
+                        ),
+                        None => Line::from("…".fg(config::get().color.base03)),
+                    };
+                    cells.push(Text::from(vec!["".into(), score, "".into()]));
+                }
+                Row::new(cells)
+                    .style({
+                        let s = Style::default()
+                            .fg(config::get().color.base05)
+                            .bg(match i % 2 {
+                                0 => config::get().color.base00,
+                                1 => config::get().color.base01,
+                                _ => unreachable!("Cannot reach this line"),
+                            });
+                        if i == selected {
+                            s.bg(config::get().color.base02)
+                        } else {
+                            s
+                        }
+                    })
+                    .height(height.saturating_add(1) as u16)
             });
 
-            let widths = [Constraint::Max(20), Constraint::Min(0), Constraint::Max(10)];
-            Table::new(rows, widths)
+            Table::new(rows, widths.iter().skip(1).copied())
                 .header(header)
                 .column_spacing(1)
                 .highlight_symbol(Text::from(vec![
@@ -199,10 +292,10 @@ impl StatefulWidget for SearchResultsTableWidget {
                     std::iter::once(" ".into())
                         .chain(std::iter::once(" ".into()))
                         .chain(std::iter::once(" ".into()))
-                        .chain(
-                            std::iter::repeat("│".fg(config::get().color.base0f))
-                                .take(space.height as usize),
-                        )
+                        .chain(std::iter::repeat_n(
+                            "│".fg(config::get().color.base0f),
+                            space.height as usize,
+                        ))
                         .map(Line::from)
                         .collect_vec(),
                 )
@@ -1,10 +1,25 @@
 use itertools::Itertools;
 use ratatui::{layout::Flex, prelude::*, widgets::*};
+use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIs, EnumIter, FromRepr};
 
 use crate::config;
 
-#[derive(Default, Debug, Clone, Copy, EnumIs, FromRepr, Display, EnumIter)]
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIs,
+    FromRepr,
+    Display,
+    EnumIter,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
 pub enum SummaryMode {
     #[default]
     NewCrates,
@@ -80,6 +95,21 @@ impl Summary {
         }
     }
 
+    /// The name of the selected crate, unless keywords or categories are
+    /// shown.
+    pub fn selected_crate_name(&self) -> Option<String> {
+        let summary = self.summary_data.as_ref()?;
+        let i = self.selected(self.mode)? - 1;
+        let crates = match self.mode {
+            SummaryMode::NewCrates => &summary.new_crates,
+            SummaryMode::MostDownloaded => &summary.most_downloaded,
+            SummaryMode::JustUpdated => &summary.just_updated,
+            SummaryMode::MostRecentlyDownloaded => &summary.most_recently_downloaded,
+            SummaryMode::PopularKeywords | SummaryMode::PopularCategories => return None,
+        };
+        crates.get(i).map(|c| c.name.clone())
+    }
+
     pub fn get_state_mut(&mut self, mode: SummaryMode) -> &mut ListState {
         &mut self.state[mode as usize]
     }
@@ -8,6 +8,8 @@ pub enum SelectedTab {
     #[default]
     Summary,
     Search,
+    Project,
+    Favorites,
     None,
 }
 
@@ -29,6 +31,8 @@ impl Widget for &SelectedTab {
         match self {
             SelectedTab::Summary => self.render_tab_summary(area, buf),
             SelectedTab::Search => self.render_tab_search(area, buf),
+            SelectedTab::Project => self.render_tab_project(area, buf),
+            SelectedTab::Favorites => self.render_tab_favorites(area, buf),
             SelectedTab::None => (),
         }
     }
@@ -57,6 +61,18 @@ impl SelectedTab {
             .render(area, buf)
     }
 
+    fn render_tab_project(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new("Project")
+            .block(self.block())
+            .render(area, buf)
+    }
+
+    fn render_tab_favorites(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new("Favorites")
+            .block(self.block())
+            .render(area, buf)
+    }
+
     fn block(&self) -> Block<'static> {
         Block::default()
             .borders(Borders::ALL)
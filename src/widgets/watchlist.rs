@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+    app::Mode,
+    config,
+    watchlist::{Changes, CrateSnapshot, WatchedCrate, Watchlist},
+};
+
+/// The watched crates, their state on crates.io and the table state of the
+/// watchlist view.
+#[derive(Debug, Default)]
+pub struct WatchlistView {
+    pub watchlist: Watchlist,
+    /// The state of the watched crates fetched since launch, keyed by crate
+    /// name.
+    pub current: HashMap<String, CrateSnapshot>,
+    /// Why the last check of a watched crate failed, keyed by crate name.
+    pub failed: HashMap<String, String>,
+    /// Whether the watched crates are being fetched.
+    pub loading: bool,
+    /// The mode to return to when the view is closed.
+    pub return_mode: Mode,
+    table_state: TableState,
+}
+
+impl WatchlistView {
+    pub fn new(watchlist: Watchlist) -> Self {
+        Self {
+            watchlist,
+            ..Default::default()
+        }
+    }
+
+    /// Shows the view from the top, returning to `return_mode` when closed.
+    pub fn open(&mut self, return_mode: Mode) {
+        self.return_mode = return_mode;
+        self.table_state.select(Some(0));
+    }
+
+    /// What happened to a crate since it was acknowledged, if it was fetched.
+    pub fn changes(&self, watched: &WatchedCrate) -> Option<Changes> {
+        let acknowledged = watched.acknowledged.as_ref()?;
+        let current = self.current.get(&watched.name)?;
+        Some(Changes::between(acknowledged, current))
+    }
+
+    /// The names of the crates that changed since they were acknowledged.
+    pub fn changed(&self) -> Vec<String> {
+        self.watchlist
+            .crates
+            .iter()
+            .filter(|c| self.changes(c).is_some_and(|changes| !changes.is_empty()))
+            .map(|c| c.name.clone())
+            .collect()
+    }
+
+    pub fn selected(&self) -> Option<&WatchedCrate> {
+        self.table_state
+            .selected()
+            .and_then(|i| self.watchlist.crates.get(i))
+    }
+
+    pub fn clamp_selection(&mut self) {
+        let last = self.watchlist.crates.len().saturating_sub(1);
+        let i = self.table_state.selected().map_or(0, |i| i.min(last));
+        self.table_state.select(Some(i));
+    }
+
+    pub fn scroll_previous(&mut self) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.table_state.select(Some(i));
+    }
+
+    pub fn scroll_next(&mut self) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(1))
+            .min(self.watchlist.crates.len().saturating_sub(1));
+        self.table_state.select(Some(i));
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.table_state.select(Some(0));
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.table_state
+            .select(Some(self.watchlist.crates.len().saturating_sub(1)));
+    }
+}
+
+pub struct WatchlistWidget;
+
+impl WatchlistWidget {
+    fn version(watched: &WatchedCrate, changes: Option<&Changes>) -> Line<'static> {
+        match changes {
+            Some(Changes {
+                version: Some((from, to)),
+                new_versions,
+                ..
+            }) => Line::from(vec![
+                format!("{from} \u{2192} ").into(),
+                to.clone().fg(config::get().color.base0a).bold(),
+                format!(" (+{new_versions})").fg(config::get().color.base03),
+            ]),
+            _ => watched
+                .acknowledged
+                .as_ref()
+                .map_or(Line::from(""), |a| Line::from(a.version.clone())),
+        }
+    }
+
+    fn owners(changes: &Changes) -> Line<'static> {
+        let added = changes
+            .owners_added
+            .iter()
+            .map(|o| format!("+{o}").fg(config::get().color.base0b));
+        let removed = changes
+            .owners_removed
+            .iter()
+            .map(|o| format!("-{o}").fg(config::get().color.base08));
+        Line::from(Itertools::intersperse(added.chain(removed), " ".into()).collect_vec())
+    }
+}
+
+impl StatefulWidget for WatchlistWidget {
+    type State = WatchlistView;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = area.inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let [title, table_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(area);
+        let changed = state.changed().len();
+        let mut title_line = Line::from(vec![
+            "Watchlist".bold(),
+            format!(" {}", state.watchlist.crates.len()).fg(config::get().color.base03),
+        ]);
+        if changed > 0 {
+            title_line.spans.push(
+                format!("  {changed} changed since last seen")
+                    .fg(config::get().color.base0a)
+                    .bold(),
+            );
+        }
+        let failed = state
+            .watchlist
+            .crates
+            .iter()
+            .filter(|c| state.failed.contains_key(&c.name))
+            .count();
+        if failed > 0 {
+            title_line.spans.push(
+                format!("  {failed} could not be checked")
+                    .fg(config::get().color.base08)
+                    .bold(),
+            );
+        }
+        if state.loading {
+            title_line
+                .spans
+                .push("  checking crates.io...".fg(config::get().color.base03));
+        }
+        title_line.render(title, buf);
+        if state.watchlist.crates.is_empty() {
+            Line::styled(
+                "Watch crates from the search results to be told about new releases, yanks and owner changes",
+                config::get().color.base03,
+            )
+            .render(table_area, buf);
+            return;
+        }
+
+        let rows = state
+            .watchlist
+            .crates
+            .iter()
+            .map(|watched| {
+                let changes = state.changes(watched);
+                let failure = state.failed.get(&watched.name);
+                let marker = match changes {
+                    _ if failure.is_some() => "!".fg(config::get().color.base08).bold(),
+                    Some(ref changes) if !changes.is_empty() => {
+                        "\u{25cf}".fg(config::get().color.base0a)
+                    }
+                    Some(_) => "".into(),
+                    None => "?".fg(config::get().color.base03),
+                };
+                let yanked = match failure {
+                    Some(failure) => Line::styled(
+                        format!("check failed: {failure}"),
+                        config::get().color.base08,
+                    ),
+                    None => changes.as_ref().map_or(Line::from(""), |c| {
+                        Line::styled(c.yanked.join(", "), config::get().color.base08)
+                    }),
+                };
+                Row::new([
+                    Cell::from(marker),
+                    Cell::from(watched.name.clone()),
+                    Cell::from(Self::version(watched, changes.as_ref())),
+                    Cell::from(yanked),
+                    Cell::from(changes.as_ref().map_or(Line::from(""), Self::owners)),
+                ])
+            })
+            .collect_vec();
+        let header =
+            Row::new(["", "Crate", "Version", "Newly yanked", "Owner changes"].map(|h| h.bold()))
+                .height(1);
+        let widths = [
+            Constraint::Length(1),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(3),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .style(
+                Style::default()
+                    .fg(config::get().color.base05)
+                    .bg(config::get().color.base00),
+            )
+            .highlight_symbol("\u{2022} ")
+            .highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, table_area, buf, &mut state.table_state);
+    }
+}
@@ -0,0 +1,470 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use itertools::Itertools;
+
+use crate::{
+    crates_index_helper::DependencyKind,
+    project::{Lockfile, Project},
+    resolver::NodeId,
+};
+
+/// Features a package requests on one of its dependencies.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestedFeatures {
+    /// Features listed on the dependency, or enabled with `dep/feature` by
+    /// an active feature of the parent.
+    pub features: BTreeSet<String>,
+    pub default_features: bool,
+    /// Active features of the parent that enable the dependency when it is
+    /// optional.
+    pub enabled_by: Option<BTreeSet<String>>,
+}
+
+/// A dependency between two resolved packages of the workspace.
+#[derive(Debug, Clone)]
+pub struct GraphEdge {
+    pub parent: NodeId,
+    pub child: NodeId,
+    pub kinds: BTreeSet<DependencyKind>,
+    /// Only known when the graph is built from `cargo metadata`.
+    pub features: Option<RequestedFeatures>,
+}
+
+/// The resolved dependency graph of the workspace.
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceGraph {
+    pub members: BTreeSet<NodeId>,
+    /// Every resolved package with its unified features, which are empty
+    /// when the graph is built from `Cargo.lock`.
+    pub nodes: BTreeMap<NodeId, BTreeSet<String>>,
+    pub edges: Vec<GraphEdge>,
+    /// Indices of the edges pointing to each package.
+    parents: HashMap<NodeId, Vec<usize>>,
+    /// The `[features]` table of each package, empty when the graph is built
+    /// from `Cargo.lock`.
+    feature_tables: HashMap<NodeId, BTreeMap<String, Vec<String>>>,
+}
+
+/// Why a feature of a package is enabled.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FeatureSource {
+    /// Requested by a package depending on it.
+    Dependent(NodeId),
+    /// Enabled by another feature of the same package.
+    Feature(String),
+}
+
+/// Maximum number of paths listed for a single crate.
+pub const MAX_PATHS: usize = 1000;
+
+fn dependency_kind(kind: cargo_metadata::DependencyKind) -> DependencyKind {
+    match kind {
+        cargo_metadata::DependencyKind::Development => DependencyKind::Dev,
+        cargo_metadata::DependencyKind::Build => DependencyKind::Build,
+        _ => DependencyKind::Normal,
+    }
+}
+
+/// Splits an entry of a `[features]` table into the dependency key it refers
+/// to, the feature it enables on it, and whether it is a weak `dep?/feature`
+/// reference.
+fn parse_feature_value(value: &str) -> (&str, Option<&str>, bool) {
+    let value = value.strip_prefix("dep:").unwrap_or(value);
+    match value.split_once('/') {
+        Some((key, feature)) => (key.trim_end_matches('?'), Some(feature), key.ends_with('?')),
+        None => (value, None, false),
+    }
+}
+
+impl WorkspaceGraph {
+    fn new(
+        members: BTreeSet<NodeId>,
+        nodes: BTreeMap<NodeId, BTreeSet<String>>,
+        edges: Vec<GraphEdge>,
+    ) -> Self {
+        let parents = edges
+            .iter()
+            .enumerate()
+            .map(|(i, edge)| (edge.child.clone(), i))
+            .into_group_map();
+        Self {
+            members,
+            nodes,
+            edges,
+            parents,
+            feature_tables: HashMap::new(),
+        }
+    }
+
+    /// Builds the graph from the output of `cargo metadata`, including the
+    /// features each package requests on its dependencies.
+    pub fn from_metadata(metadata: &cargo_metadata::Metadata) -> Result<Self, String> {
+        let resolve = metadata
+            .resolve
+            .as_ref()
+            .ok_or("`cargo metadata` did not resolve the dependencies")?;
+        let packages = metadata
+            .packages
+            .iter()
+            .map(|p| (&p.id, p))
+            .collect::<HashMap<_, _>>();
+        let id =
+            |package: &cargo_metadata::Package| (package.name.clone(), package.version.clone());
+        let members = metadata
+            .workspace_members
+            .iter()
+            .filter_map(|member| packages.get(member).map(|p| id(p)))
+            .collect();
+        let nodes = resolve
+            .nodes
+            .iter()
+            .filter_map(|node| {
+                let package = packages.get(&node.id)?;
+                Some((id(package), node.features.iter().cloned().collect()))
+            })
+            .collect();
+        let mut edges = vec![];
+        for node in &resolve.nodes {
+            let Some(parent) = packages.get(&node.id) else {
+                continue;
+            };
+            let active = node.features.iter().collect::<HashSet<_>>();
+            for dep in &node.deps {
+                let Some(child) = packages.get(&dep.pkg) else {
+                    continue;
+                };
+                let declared = parent
+                    .dependencies
+                    .iter()
+                    .filter(|d| d.name == child.name && d.req.matches(&child.version))
+                    // only the declarations of the kinds this edge was resolved for
+                    .filter(|d| dep.dep_kinds.iter().any(|k| k.kind == d.kind))
+                    .collect_vec();
+                let keys = declared
+                    .iter()
+                    .map(|d| d.rename.clone().unwrap_or_else(|| d.name.clone()))
+                    .collect::<HashSet<_>>();
+                let mut features = declared
+                    .iter()
+                    .flat_map(|d| d.features.iter().cloned())
+                    .collect::<BTreeSet<_>>();
+                let mut enabled_by = BTreeSet::new();
+                for (name, values) in &parent.features {
+                    if !active.contains(name) {
+                        continue;
+                    }
+                    for value in values {
+                        let (key, feature, weak) = parse_feature_value(value);
+                        if !keys.contains(key) {
+                            continue;
+                        }
+                        if let Some(feature) = feature {
+                            features.insert(feature.to_string());
+                        }
+                        if !weak {
+                            enabled_by.insert(name.clone());
+                        }
+                    }
+                }
+                // optional dependencies get an implicit feature of the same
+                // name unless they are referred to with `dep:`
+                for key in &keys {
+                    if active.contains(key) && !parent.features.contains_key(key) {
+                        enabled_by.insert(key.clone());
+                    }
+                }
+                let optional = !declared.is_empty() && declared.iter().all(|d| d.optional);
+                edges.push(GraphEdge {
+                    parent: id(parent),
+                    child: id(child),
+                    kinds: dep
+                        .dep_kinds
+                        .iter()
+                        .map(|k| dependency_kind(k.kind))
+                        .collect(),
+                    features: Some(RequestedFeatures {
+                        features,
+                        default_features: declared.iter().any(|d| d.uses_default_features),
+                        enabled_by: optional.then_some(enabled_by),
+                    }),
+                });
+            }
+        }
+        let mut graph = Self::new(members, nodes, edges);
+        graph.feature_tables = metadata
+            .packages
+            .iter()
+            .map(|p| (id(p), p.features.clone()))
+            .collect();
+        Ok(graph)
+    }
+
+    /// Builds the graph from `Cargo.lock`, which doesn't record dependency
+    /// kinds or features.
+    pub fn from_lockfile(lockfile: &Lockfile, members: BTreeSet<NodeId>) -> Self {
+        let ids = lockfile
+            .packages
+            .iter()
+            .map(|p| (p.name.clone(), p.version.clone()))
+            .collect_vec();
+        let nodes = ids.iter().map(|id| (id.clone(), BTreeSet::new())).collect();
+        let edges = lockfile
+            .packages
+            .iter()
+            .zip(&ids)
+            .flat_map(|(package, parent)| {
+                let ids = &ids;
+                package.dependency_ids().filter_map(move |(name, version)| {
+                    let child = ids.iter().find(|(n, v)| {
+                        n == name && version.is_none_or(|version| v.to_string() == version)
+                    })?;
+                    Some(GraphEdge {
+                        parent: parent.clone(),
+                        child: child.clone(),
+                        kinds: BTreeSet::new(),
+                        features: None,
+                    })
+                })
+            })
+            .collect();
+        Self::new(members, nodes, edges)
+    }
+
+    /// Whether features are known, which needs the graph to be built from
+    /// `cargo metadata`.
+    pub fn has_features(&self) -> bool {
+        !self.feature_tables.is_empty()
+    }
+
+    /// The unified features of a package, each with the dependents and
+    /// features that enable it.
+    ///
+    /// Features of workspace members that nothing else enables come from
+    /// the command line, so they have no source.
+    pub fn unified_features(&self, id: &NodeId) -> BTreeMap<String, BTreeSet<FeatureSource>> {
+        let Some(active) = self.nodes.get(id) else {
+            return BTreeMap::new();
+        };
+        let table = self.feature_tables.get(id);
+        active
+            .iter()
+            .map(|feature| {
+                let mut sources = BTreeSet::new();
+                for &i in self.parents.get(id).into_iter().flatten() {
+                    let edge = &self.edges[i];
+                    let Some(ref requested) = edge.features else {
+                        continue;
+                    };
+                    if requested.features.contains(feature)
+                        || (feature == "default" && requested.default_features)
+                    {
+                        sources.insert(FeatureSource::Dependent(edge.parent.clone()));
+                    }
+                }
+                for (name, values) in table.into_iter().flatten() {
+                    if name != feature && active.contains(name) && values.contains(feature) {
+                        sources.insert(FeatureSource::Feature(name.clone()));
+                    }
+                }
+                (feature.clone(), sources)
+            })
+            .collect()
+    }
+
+    /// Resolved packages named `name`, optionally only the given version.
+    pub fn find(&self, name: &str, version: Option<&semver::Version>) -> Vec<NodeId> {
+        self.nodes
+            .keys()
+            .filter(|(n, v)| n == name && version.is_none_or(|version| v == version))
+            .cloned()
+            .collect()
+    }
+
+    /// Every path from a workspace member to `target`, as the edges leading
+    /// from the member down to the target.
+    ///
+    /// A path to a target that is itself a member is empty. At most
+    /// [`MAX_PATHS`] paths are returned, so the second value tells whether
+    /// some were left out.
+    pub fn paths_to(&self, target: &NodeId) -> (Vec<Vec<&GraphEdge>>, bool) {
+        let mut paths = vec![];
+        let mut stack = vec![];
+        let mut visiting = HashSet::new();
+        let complete = self.collect_paths(target, &mut stack, &mut visiting, &mut paths);
+        let paths = paths
+            .into_iter()
+            .sorted_by(|a, b| a.iter().map(|e| &e.parent).cmp(b.iter().map(|e| &e.parent)))
+            .collect();
+        (paths, !complete)
+    }
+
+    /// Walks up from `id` depth first, returning `false` once the limit is
+    /// reached.
+    fn collect_paths<'a>(
+        &'a self,
+        id: &NodeId,
+        stack: &mut Vec<&'a GraphEdge>,
+        visiting: &mut HashSet<NodeId>,
+        paths: &mut Vec<Vec<&'a GraphEdge>>,
+    ) -> bool {
+        if self.members.contains(id) {
+            if paths.len() >= MAX_PATHS {
+                return false;
+            }
+            paths.push(stack.iter().rev().copied().collect());
+        }
+        visiting.insert(id.clone());
+        for &i in self.parents.get(id).into_iter().flatten() {
+            let edge = &self.edges[i];
+            if visiting.contains(&edge.parent) {
+                continue;
+            }
+            stack.push(edge);
+            let complete = self.collect_paths(&edge.parent, stack, visiting, paths);
+            stack.pop();
+            if !complete {
+                return false;
+            }
+        }
+        visiting.remove(id);
+        true
+    }
+}
+
+/// Loads the resolved dependency graph of the workspace in the background.
+///
+/// This runs a full `cargo metadata`, which may need to download crates, and
+/// falls back to `Cargo.lock` when that fails.
+pub async fn request_workspace_graph(
+    project: Project,
+    graph: Arc<Mutex<Option<WorkspaceGraph>>>,
+) -> Result<(), String> {
+    let manifest_path: PathBuf = project.metadata.workspace_root.join("Cargo.toml").into();
+    let loaded = tokio::task::spawn_blocking(move || {
+        let metadata = cargo_metadata::MetadataCommand::new()
+            .manifest_path(&manifest_path)
+            .exec()
+            .map_err(|err| err.to_string())
+            .and_then(|metadata| WorkspaceGraph::from_metadata(&metadata));
+        metadata.unwrap_or_else(|err| {
+            tracing::warn!("Falling back to Cargo.lock for the dependency graph: {err}");
+            let members = project
+                .metadata
+                .workspace_packages()
+                .into_iter()
+                .map(|p| (p.name.clone(), p.version.clone()))
+                .collect();
+            WorkspaceGraph::from_lockfile(&project.lockfile, members)
+        })
+    })
+    .await
+    .map_err(|err| format!("Unable to load the dependency graph: {err}"))?;
+    *graph.lock().unwrap() = Some(loaded);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn id(name: &str) -> NodeId {
+        (name.into(), semver::Version::new(1, 0, 0))
+    }
+
+    #[test]
+    fn finds_every_path_from_members() {
+        let lockfile: Lockfile = toml::from_str(
+            r#"
+[[package]]
+name = "app"
+version = "1.0.0"
+dependencies = ["a", "b", "lib"]
+
+[[package]]
+name = "lib"
+version = "1.0.0"
+dependencies = ["b"]
+
+[[package]]
+name = "a"
+version = "1.0.0"
+dependencies = ["b"]
+
+[[package]]
+name = "b"
+version = "1.0.0"
+"#,
+        )
+        .unwrap();
+        let graph = WorkspaceGraph::from_lockfile(&lockfile, [id("app"), id("lib")].into());
+        let (paths, truncated) = graph.paths_to(&id("b"));
+        let paths = paths
+            .iter()
+            .map(|path| path.iter().map(|e| e.parent.0.as_str()).join(" > "))
+            .collect_vec();
+        assert_eq!(paths, vec!["app", "app > a", "app > lib", "lib"]);
+        assert!(!truncated);
+        let (paths, _) = graph.paths_to(&id("app"));
+        assert!(paths.len() == 1 && paths[0].is_empty());
+    }
+
+    #[test]
+    fn explains_unified_features() {
+        let requested = |features: &[&str], default_features| RequestedFeatures {
+            features: features.iter().map(|f| f.to_string()).collect(),
+            default_features,
+            enabled_by: None,
+        };
+        let edge = |parent: &str, features| GraphEdge {
+            parent: id(parent),
+            child: id("tokio"),
+            kinds: [DependencyKind::Normal].into(),
+            features: Some(features),
+        };
+        let active = ["default", "full", "rt", "net"].map(String::from);
+        let mut graph = WorkspaceGraph::new(
+            [id("app")].into(),
+            [(id("tokio"), active.into())].into(),
+            vec![
+                edge("app", requested(&["rt"], true)),
+                edge("hyper", requested(&["full"], false)),
+            ],
+        );
+        graph.feature_tables.insert(
+            id("tokio"),
+            [
+                ("default".to_string(), vec![]),
+                (
+                    "full".to_string(),
+                    vec!["rt".to_string(), "net".to_string()],
+                ),
+                ("rt".to_string(), vec![]),
+                ("net".to_string(), vec![]),
+            ]
+            .into(),
+        );
+        let features = graph.unified_features(&id("tokio"));
+        assert_eq!(
+            features["rt"],
+            [
+                FeatureSource::Dependent(id("app")),
+                FeatureSource::Feature("full".into())
+            ]
+            .into()
+        );
+        assert_eq!(
+            features["full"],
+            [FeatureSource::Dependent(id("hyper"))].into()
+        );
+        assert_eq!(
+            features["default"],
+            [FeatureSource::Dependent(id("app"))].into()
+        );
+    }
+}